@@ -0,0 +1,90 @@
+//! Allocations and time spent parsing messages, to compare the default
+//! build with the `smol_str` feature:
+//!
+//! ```sh
+//! cargo bench --bench compact_str
+//! cargo bench --bench compact_str --features smol_str
+//! ```
+
+use mcp_schema::*;
+use serde_json::json;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const ITERATIONS: usize = 200;
+
+fn tool_calls() -> String {
+    let requests: Vec<_> = (0..500)
+        .map(|i| {
+            json!({
+                "jsonrpc": "2.0",
+                "id": i,
+                "method": "tools/call",
+                "params": { "name": format!("tool_{}", i % 20) }
+            })
+        })
+        .collect();
+    json!(requests).to_string()
+}
+
+fn resource_contents() -> String {
+    let contents: Vec<_> = (0..500)
+        .map(|i| {
+            json!({
+                "uri": format!("file:///src/{}.rs", i),
+                "mimeType": "text/x-rust",
+                "text": ""
+            })
+        })
+        .collect();
+    json!({ "contents": contents }).to_string()
+}
+
+fn bench<T: serde::de::DeserializeOwned>(name: &str, input: &str) {
+    // Warm up, so one-time allocations are not counted.
+    let _: T = serde_json::from_str(input).unwrap();
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let parsed: T = serde_json::from_str(input).unwrap();
+        std::hint::black_box(parsed);
+    }
+    let elapsed = start.elapsed() / ITERATIONS as u32;
+    let allocations = (ALLOCATIONS.load(Ordering::Relaxed) - before) / ITERATIONS;
+    println!(
+        "{:<24} {:>8} allocations {:>10.1?} per parse",
+        name, allocations, elapsed
+    );
+}
+
+fn main() {
+    println!(
+        "smol_str feature: {}",
+        if cfg!(feature = "smol_str") {
+            "on"
+        } else {
+            "off"
+        }
+    );
+    bench::<Vec<JSONRPCRequest<CallToolParams>>>("tools/call (500)", &tool_calls());
+    bench::<ReadResourceResult>("resources/read (500)", &resource_contents());
+}
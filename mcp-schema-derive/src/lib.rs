@@ -0,0 +1,121 @@
+//! The `#[mcp_extras]` attribute of `mcp-schema`. Use it through the
+//! re-export `mcp_schema::mcp_extras`.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::parse::Parser;
+use syn::{parse_macro_input, Data, DeriveInput, Fields};
+
+/// Adds the fields every MCP object carries to a struct with named fields:
+///
+/// - `meta: Option<HashMap<String, Value>>`, serialized as `_meta`, as the
+///   first field;
+/// - `extra: HashMap<String, Value>`, flattened, as the last field, which
+///   rejects unknown fields when `mcp-schema` is built with `strict-fields`;
+///
+/// and implements `HasMeta` and `Extras` for it. With
+/// `#[mcp_extras(extra_only)]` only `extra` and `Extras` are added, for
+/// objects the spec gives no `_meta`.
+///
+/// The attribute must come before `#[derive(Serialize, Deserialize)]` so
+/// the derives see the added fields.
+#[proc_macro_attribute]
+pub fn mcp_extras(args: TokenStream, input: TokenStream) -> TokenStream {
+    let extra_only = match parse_args(args) {
+        Ok(extra_only) => extra_only,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let mut item = parse_macro_input!(input as DeriveInput);
+    match expand(&mut item, extra_only) {
+        Ok(impls) => quote!(#item #impls).into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn parse_args(args: TokenStream) -> syn::Result<bool> {
+    let mut extra_only = false;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("extra_only") {
+            extra_only = true;
+            Ok(())
+        } else {
+            Err(meta.error("expected `extra_only`"))
+        }
+    });
+    parser.parse(args)?;
+    Ok(extra_only)
+}
+
+fn expand(item: &mut DeriveInput, extra_only: bool) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match &mut item.data {
+        Data::Struct(data) => match &mut data.fields {
+            Fields::Named(fields) => fields,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &item.ident,
+                    "#[mcp_extras] needs a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &item.ident,
+                "#[mcp_extras] can only be used on structs",
+            ))
+        }
+    };
+    for field in &fields.named {
+        let name = field.ident.as_ref().expect("named field");
+        if name == "extra" || (!extra_only && name == "meta") {
+            return Err(syn::Error::new_spanned(
+                name,
+                "field is added by #[mcp_extras]",
+            ));
+        }
+    }
+
+    fields.named.push(syn::Field::parse_named.parse2(quote! {
+        #[serde(flatten, deserialize_with = "::mcp_schema::deserialize_extra")]
+        pub extra: ::std::collections::HashMap<::std::string::String, ::serde_json::Value>
+    })?);
+    if !extra_only {
+        fields.named.insert(
+            0,
+            syn::Field::parse_named.parse2(quote! {
+                #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
+                pub meta: ::std::option::Option<
+                    ::std::collections::HashMap<::std::string::String, ::serde_json::Value>,
+                >
+            })?,
+        );
+    }
+
+    let name = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+    let has_meta = (!extra_only).then(|| {
+        quote! {
+            impl #impl_generics ::mcp_schema::HasMeta for #name #ty_generics #where_clause {
+                fn meta(&self) -> ::std::option::Option<&::std::collections::HashMap<::std::string::String, ::serde_json::Value>> {
+                    self.meta.as_ref()
+                }
+
+                fn meta_mut(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::serde_json::Value> {
+                    self.meta.get_or_insert_with(::std::collections::HashMap::new)
+                }
+            }
+        }
+    });
+    Ok(quote! {
+        #has_meta
+
+        impl #impl_generics ::mcp_schema::Extras for #name #ty_generics #where_clause {
+            fn extra(&self) -> &::std::collections::HashMap<::std::string::String, ::serde_json::Value> {
+                &self.extra
+            }
+
+            fn extra_mut(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::serde_json::Value> {
+                &mut self.extra
+            }
+        }
+    })
+}
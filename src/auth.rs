@@ -0,0 +1,301 @@
+//! Pieces of the HTTP authorization flow: `WWW-Authenticate` challenges and
+//! bearer tokens.
+//!
+//! A server that requires authorization answers `401` with a `Bearer`
+//! challenge whose `resource_metadata` parameter points at its protected
+//! resource metadata (see [`crate::ProtectedResourceMetadata`]); the client
+//! follows it to the authorization server and retries with a
+//! [`BearerToken`].
+
+use std::fmt;
+use std::str::FromStr;
+
+pub const WWW_AUTHENTICATE_HEADER: &str = "www-authenticate";
+
+/// A `WWW-Authenticate` value that does not follow RFC 9110 syntax.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MalformedChallenge(pub String);
+
+impl fmt::Display for MalformedChallenge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "malformed WWW-Authenticate challenge {:?}", self.0)
+    }
+}
+
+impl std::error::Error for MalformedChallenge {}
+
+/// One authentication challenge: a scheme and either a token68 or a list of
+/// parameters.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Challenge {
+    pub scheme: String,
+    pub token68: Option<String>,
+    pub params: Vec<(String, String)>,
+}
+
+impl Challenge {
+    pub fn new(scheme: impl Into<String>) -> Self {
+        Challenge {
+            scheme: scheme.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn bearer() -> Self {
+        Self::new("Bearer")
+    }
+
+    pub fn param(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params.push((name.into(), value.into()));
+        self
+    }
+
+    /// Points the client at the protected resource metadata document.
+    pub fn with_resource_metadata(self, url: impl Into<String>) -> Self {
+        self.param("resource_metadata", url)
+    }
+
+    pub fn is_bearer(&self) -> bool {
+        self.scheme.eq_ignore_ascii_case("bearer")
+    }
+
+    /// The value of a parameter; names are case-insensitive.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The URL of the protected resource metadata (RFC 9728).
+    pub fn resource_metadata(&self) -> Option<&str> {
+        self.get("resource_metadata")
+    }
+
+    /// The scopes the request needs, from the space-separated `scope`.
+    pub fn scopes(&self) -> impl Iterator<Item = &str> {
+        self.get("scope").unwrap_or_default().split_whitespace()
+    }
+
+    /// The error code (RFC 6750), e.g. `invalid_token`.
+    pub fn error(&self) -> Option<&str> {
+        self.get("error")
+    }
+}
+
+fn is_tchar(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+fn is_token68_char(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"-._~+/".contains(&b)
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, b: u8) -> bool {
+        let matched = self.peek() == Some(b);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn skip_while(&mut self, f: impl Fn(u8) -> bool) -> &'a str {
+        let start = self.pos;
+        while self.peek().is_some_and(&f) {
+            self.pos += 1;
+        }
+        // Only ASCII bytes are consumed, so the slice is valid UTF-8.
+        std::str::from_utf8(&self.input[start..self.pos]).unwrap_or_default()
+    }
+
+    fn skip_ws(&mut self) {
+        self.skip_while(|b| b == b' ' || b == b'\t');
+    }
+
+    fn token(&mut self) -> Option<&'a str> {
+        Some(self.skip_while(is_tchar)).filter(|token| !token.is_empty())
+    }
+
+    fn quoted_string(&mut self) -> Option<String> {
+        let mut value = Vec::new();
+        loop {
+            match self.peek()? {
+                b'"' => {
+                    self.pos += 1;
+                    return String::from_utf8(value).ok();
+                }
+                b'\\' => {
+                    self.pos += 1;
+                    value.push(self.peek()?);
+                }
+                b => value.push(b),
+            }
+            self.pos += 1;
+        }
+    }
+
+    /// A token68 followed by the end of the challenge.
+    fn token68(&mut self) -> Option<&'a str> {
+        let start = self.pos;
+        self.skip_while(is_token68_char);
+        if self.pos > start {
+            self.skip_while(|b| b == b'=');
+        }
+        let end = self.pos;
+        self.skip_ws();
+        if end > start && matches!(self.peek(), None | Some(b',')) {
+            return std::str::from_utf8(&self.input[start..end]).ok();
+        }
+        self.pos = start;
+        None
+    }
+
+    fn challenge(&mut self) -> Option<Challenge> {
+        let mut challenge = Challenge::new(self.token()?);
+        if !matches!(self.peek(), Some(b' ' | b'\t')) {
+            return Some(challenge);
+        }
+        self.skip_ws();
+        if let Some(token68) = self.token68() {
+            challenge.token68 = Some(token68.to_string());
+            return Some(challenge);
+        }
+        loop {
+            let start = self.pos;
+            self.skip_while(|b| b == b',' || b == b' ' || b == b'\t');
+            let Some(name) = self.token() else {
+                self.pos = start;
+                break;
+            };
+            self.skip_ws();
+            if !self.eat(b'=') {
+                // A token without `=` starts the next challenge.
+                self.pos = start;
+                break;
+            }
+            self.skip_ws();
+            let value = if self.eat(b'"') {
+                self.quoted_string()?
+            } else {
+                self.token()?.to_string()
+            };
+            challenge.params.push((name.to_string(), value));
+            self.skip_ws();
+        }
+        Some(challenge)
+    }
+}
+
+/// Parses a `WWW-Authenticate` value, which may hold several challenges.
+pub fn parse_challenges(header: &str) -> Result<Vec<Challenge>, MalformedChallenge> {
+    let malformed = || MalformedChallenge(header.to_string());
+    let mut parser = Parser {
+        input: header.as_bytes(),
+        pos: 0,
+    };
+    let mut challenges = Vec::new();
+    loop {
+        parser.skip_while(|b| b == b',' || b == b' ' || b == b'\t');
+        if parser.peek().is_none() {
+            return Ok(challenges);
+        }
+        challenges.push(parser.challenge().ok_or_else(malformed)?);
+        parser.skip_ws();
+        if !matches!(parser.peek(), None | Some(b',')) {
+            return Err(malformed());
+        }
+    }
+}
+
+/// The `resource_metadata` URL of the first `Bearer` challenge in a
+/// `WWW-Authenticate` value.
+pub fn resource_metadata_url(header: &str) -> Option<String> {
+    parse_challenges(header)
+        .ok()?
+        .into_iter()
+        .filter(Challenge::is_bearer)
+        .find_map(|challenge| challenge.resource_metadata().map(str::to_string))
+}
+
+impl FromStr for Challenge {
+    type Err = MalformedChallenge;
+
+    /// Parses a value holding exactly one challenge.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut challenges = parse_challenges(s)?;
+        match challenges.len() {
+            1 => Ok(challenges.remove(0)),
+            _ => Err(MalformedChallenge(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Challenge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.scheme)?;
+        if let Some(token68) = &self.token68 {
+            return write!(f, " {}", token68);
+        }
+        for (i, (name, value)) in self.params.iter().enumerate() {
+            f.write_str(if i == 0 { " " } else { ", " })?;
+            write!(f, "{}=\"", name)?;
+            for c in value.chars() {
+                if c == '"' || c == '\\' {
+                    f.write_str("\\")?;
+                }
+                write!(f, "{}", c)?;
+            }
+            f.write_str("\"")?;
+        }
+        Ok(())
+    }
+}
+
+/// An OAuth access token sent as `Authorization: Bearer <token>`.
+///
+/// `Debug` does not print the token, so it can sit in structs that get
+/// logged.
+#[derive(Clone, PartialEq, Eq)]
+pub struct BearerToken(String);
+
+impl BearerToken {
+    pub fn new(token: impl Into<String>) -> Self {
+        BearerToken(token.into())
+    }
+
+    /// Parses an `Authorization` header value of the `Bearer` scheme.
+    pub fn from_authorization_header(value: &str) -> Option<Self> {
+        let (scheme, token) = value.trim().split_once(' ')?;
+        let token = token.trim_start();
+        let valid = scheme.eq_ignore_ascii_case("bearer")
+            && !token.is_empty()
+            && token.trim_end_matches('=').bytes().all(is_token68_char);
+        valid.then(|| BearerToken(token.to_string()))
+    }
+
+    /// The value to send in the `Authorization` header.
+    pub fn authorization_header(&self) -> String {
+        format!("Bearer {}", self.0)
+    }
+
+    /// The raw token.
+    pub fn secret(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for BearerToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BearerToken([redacted])")
+    }
+}
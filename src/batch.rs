@@ -0,0 +1,191 @@
+//! JSON-RPC batch support: correlating the responses of a batch with the
+//! requests that produced them.
+
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// One entry of a batch response: either a result or an error.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum JSONRPCBatchResponseItem<U = Value> {
+    Error(JSONRPCError),
+    Response(JSONRPCResponse<U>),
+}
+
+impl<U> JSONRPCBatchResponseItem<U> {
+    /// The id of the request answered; `None` for an error with a null id.
+    pub fn id(&self) -> Option<&RequestId> {
+        match self {
+            JSONRPCBatchResponseItem::Error(error) => error.id.as_ref(),
+            JSONRPCBatchResponseItem::Response(response) => Some(&response.id),
+        }
+    }
+}
+
+/// The response to a JSON-RPC batch.
+pub type JSONRPCBatchResponse<U = Value> = Vec<JSONRPCBatchResponseItem<U>>;
+
+/// An entry of a batch request. Requests have an id; notifications do not.
+pub trait BatchEntry {
+    fn request_id(&self) -> Option<RequestId>;
+}
+
+impl<T> BatchEntry for JSONRPCRequest<T> {
+    fn request_id(&self) -> Option<RequestId> {
+        Some(self.id.clone())
+    }
+}
+
+impl<T> BatchEntry for JSONRPCNotification<T> {
+    fn request_id(&self) -> Option<RequestId> {
+        None
+    }
+}
+
+impl BatchEntry for ClientRequest {
+    #[allow(deprecated)]
+    fn request_id(&self) -> Option<RequestId> {
+        Some(match self {
+            ClientRequest::Ping { id, .. }
+            | ClientRequest::Initialize { id, .. }
+            | ClientRequest::Complete { id, .. }
+            | ClientRequest::SetLevel { id, .. }
+            | ClientRequest::GetPrompt { id, .. }
+            | ClientRequest::ListPrompts { id, .. }
+            | ClientRequest::ListResources { id, .. }
+            | ClientRequest::ListResourceTemplates { id, .. }
+            | ClientRequest::ReadResource { id, .. }
+            | ClientRequest::Subscribe { id, .. }
+            | ClientRequest::Unsubscribe { id, .. }
+            | ClientRequest::CallTool { id, .. }
+            | ClientRequest::ListTools { id, .. }
+            | ClientRequest::ElicitationCreate { id, .. } => id.clone(),
+        })
+    }
+}
+
+impl BatchEntry for ClientNotification {
+    fn request_id(&self) -> Option<RequestId> {
+        None
+    }
+}
+
+impl BatchEntry for ServerRequest {
+    fn request_id(&self) -> Option<RequestId> {
+        Some(match self {
+            ServerRequest::Ping { id, .. }
+            | ServerRequest::CreateMessage { id, .. }
+            | ServerRequest::ListRoots { id, .. }
+            | ServerRequest::ElicitationCreate { id, .. } => id.clone(),
+        })
+    }
+}
+
+impl BatchEntry for ServerNotification {
+    fn request_id(&self) -> Option<RequestId> {
+        None
+    }
+}
+
+/// Raw messages: an entry is a request if it carries a valid `id`.
+impl BatchEntry for Value {
+    fn request_id(&self) -> Option<RequestId> {
+        self.get("id")
+            .and_then(|id| RequestId::deserialize(id).ok())
+    }
+}
+
+/// The outcome for one entry of a batch request.
+#[derive(Debug, Clone)]
+pub enum CorrelatedEntry<'a, Q, U = Value> {
+    /// A request and its successful response.
+    Response(&'a Q, JSONRPCResponse<U>),
+    /// A request and its error response.
+    Error(&'a Q, JSONRPCError),
+    /// A request for which the batch response carried nothing.
+    Missing(&'a Q),
+    /// A notification; JSON-RPC never answers these.
+    Notification(&'a Q),
+}
+
+/// The result of matching a batch response against its batch request.
+#[derive(Debug, Clone)]
+pub struct BatchCorrelation<'a, Q, U = Value> {
+    /// One entry per batch request entry, in the original order (so
+    /// notifications keep their position relative to requests).
+    pub entries: Vec<CorrelatedEntry<'a, Q, U>>,
+    /// Responses whose id matched no request in the batch, or that answered
+    /// a request already answered earlier in the response.
+    pub unmatched: Vec<JSONRPCBatchResponseItem<U>>,
+}
+
+impl<Q, U> BatchCorrelation<'_, Q, U> {
+    /// Whether every request got exactly one response and nothing else came back.
+    pub fn is_complete(&self) -> bool {
+        self.unmatched.is_empty()
+            && !self
+                .entries
+                .iter()
+                .any(|entry| matches!(entry, CorrelatedEntry::Missing(_)))
+    }
+
+    /// The requests that received no response.
+    pub fn missing(&self) -> impl Iterator<Item = &Q> {
+        self.entries.iter().filter_map(|entry| match entry {
+            CorrelatedEntry::Missing(request) => Some(*request),
+            _ => None,
+        })
+    }
+}
+
+/// Matches the responses of a JSON-RPC batch back to the requests of the
+/// batch by id. JSON-RPC allows a server to answer batch entries in any
+/// order, so positional matching is not enough.
+#[derive(Debug, Clone)]
+pub struct BatchCorrelator<'a, Q> {
+    requests: &'a [Q],
+}
+
+impl<'a, Q: BatchEntry> BatchCorrelator<'a, Q> {
+    pub fn new(requests: &'a [Q]) -> Self {
+        BatchCorrelator { requests }
+    }
+
+    /// Correlates `responses` with the batch requests.
+    pub fn correlate<U>(&self, responses: JSONRPCBatchResponse<U>) -> BatchCorrelation<'a, Q, U> {
+        let mut entries: Vec<CorrelatedEntry<'a, Q, U>> = self
+            .requests
+            .iter()
+            .map(|request| match request.request_id() {
+                Some(_) => CorrelatedEntry::Missing(request),
+                None => CorrelatedEntry::Notification(request),
+            })
+            .collect();
+        let mut unmatched = Vec::new();
+
+        for response in responses {
+            let slot = self.requests.iter().position(|request| {
+                request
+                    .request_id()
+                    .is_some_and(|id| response.id() == Some(&id))
+            });
+            match slot {
+                Some(index) if matches!(entries[index], CorrelatedEntry::Missing(_)) => {
+                    let request = &self.requests[index];
+                    entries[index] = match response {
+                        JSONRPCBatchResponseItem::Response(response) => {
+                            CorrelatedEntry::Response(request, response)
+                        }
+                        JSONRPCBatchResponseItem::Error(error) => {
+                            CorrelatedEntry::Error(request, error)
+                        }
+                    };
+                }
+                _ => unmatched.push(response),
+            }
+        }
+
+        BatchCorrelation { entries, unmatched }
+    }
+}
@@ -0,0 +1,550 @@
+//! Fluent construction of requests, filling in the JSON-RPC envelope:
+//!
+//! ```
+//! use mcp_schema::ClientRequest;
+//!
+//! let request = ClientRequest::call_tool(1, "search")
+//!     .arg("query", "rust")?
+//!     .arg("limit", 10)?
+//!     .build();
+//! assert_eq!(request.to_string(), "tools/call #1 name=search");
+//! # Ok::<(), serde_json::Error>(())
+//! ```
+//!
+//! Every constructor takes the request id first; a
+//! [`RequestIdGenerator`](crate::RequestIdGenerator) can supply it.
+
+use crate::elicitation::RequestedSchema;
+use crate::types::*;
+use serde::Serialize;
+use std::collections::HashMap;
+
+impl From<i64> for RequestId {
+    fn from(id: i64) -> Self {
+        RequestId::Number(id)
+    }
+}
+
+impl From<String> for RequestId {
+    fn from(id: String) -> Self {
+        RequestId::String(id)
+    }
+}
+
+impl From<&str> for RequestId {
+    fn from(id: &str) -> Self {
+        RequestId::String(id.to_string())
+    }
+}
+
+/// Builds a [`JSONRPCRequest`]; see [`JSONRPCRequest::builder`].
+#[derive(Debug, Clone)]
+pub struct JSONRPCRequestBuilder<T> {
+    method: McpStr,
+    id: RequestId,
+    params: T,
+}
+
+impl<T> JSONRPCRequest<T> {
+    /// A request for `method` with `params`, with `jsonrpc` set to
+    /// [`JSONRPC_VERSION`].
+    pub fn new(id: impl Into<RequestId>, method: impl Into<McpStr>, params: T) -> Self {
+        JSONRPCRequest {
+            json_rpc: JsonRpcVersion,
+            method: method.into(),
+            id: id.into(),
+            params,
+        }
+    }
+
+    /// Starts request `id` for `method` with `params`.
+    pub fn builder(
+        id: impl Into<RequestId>,
+        method: impl Into<McpStr>,
+        params: T,
+    ) -> JSONRPCRequestBuilder<T> {
+        JSONRPCRequestBuilder {
+            method: method.into(),
+            id: id.into(),
+            params,
+        }
+    }
+}
+
+impl<T> JSONRPCRequestBuilder<T> {
+    pub fn build(self) -> JSONRPCRequest<T> {
+        JSONRPCRequest::new(self.id, self.method, self.params)
+    }
+}
+
+impl<T> JSONRPCNotification<T> {
+    /// A notification for `method` with `params`, with `jsonrpc` set to
+    /// [`JSONRPC_VERSION`].
+    pub fn new(method: impl Into<McpStr>, params: T) -> Self {
+        JSONRPCNotification {
+            json_rpc: JsonRpcVersion,
+            method: method.into(),
+            params,
+        }
+    }
+}
+
+impl<U> JSONRPCResponse<U> {
+    /// A successful response to the request `id`, with `jsonrpc` set to
+    /// [`JSONRPC_VERSION`].
+    pub fn new(id: impl Into<RequestId>, result: U) -> Self {
+        JSONRPCResponse {
+            json_rpc: JsonRpcVersion,
+            id: id.into(),
+            result,
+        }
+    }
+}
+
+/// Builds a [`ClientRequest`]. Start from the constructor named after the
+/// method, e.g. [`ClientRequest::call_tool`]; builders for methods with
+/// optional parameters have setters for them.
+#[derive(Debug, Clone)]
+pub struct ClientRequestBuilder<P> {
+    id: RequestId,
+    params: P,
+    wrap: fn(RequestId, P) -> ClientRequest,
+}
+
+impl<P> ClientRequestBuilder<P> {
+    fn new(id: impl Into<RequestId>, params: P, wrap: fn(RequestId, P) -> ClientRequest) -> Self {
+        ClientRequestBuilder {
+            id: id.into(),
+            params,
+            wrap,
+        }
+    }
+
+    /// Mutable access to the parameters, for fields without a setter.
+    pub fn params_mut(&mut self) -> &mut P {
+        &mut self.params
+    }
+
+    pub fn build(self) -> ClientRequest {
+        (self.wrap)(self.id, self.params)
+    }
+}
+
+impl ClientRequestBuilder<InitializeParams> {
+    pub fn protocol_version(mut self, version: impl Into<String>) -> Self {
+        self.params.protocol_version = version.into();
+        self
+    }
+
+    pub fn capabilities(mut self, capabilities: ClientCapabilities) -> Self {
+        self.params.capabilities = capabilities;
+        self
+    }
+}
+
+impl ClientRequestBuilder<CompleteParams> {
+    /// Adds the value of an argument the user has already filled in.
+    pub fn context_argument(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params
+            .context
+            .get_or_insert_with(CompleteContext::default)
+            .arguments
+            .insert(name.into(), value.into());
+        self
+    }
+}
+
+impl ClientRequestBuilder<GetPromptParams> {
+    /// Adds a prompt argument.
+    pub fn argument(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params
+            .arguments
+            .get_or_insert_with(HashMap::new)
+            .insert(name.into(), value.into());
+        self
+    }
+}
+
+macro_rules! paginated_setters {
+    ($($params:ident),* $(,)?) => {$(
+        impl ClientRequestBuilder<$params> {
+            /// Requests the page after `cursor`.
+            pub fn cursor(mut self, cursor: impl Into<Cursor>) -> Self {
+                self.params.0.cursor = Some(cursor.into());
+                self
+            }
+
+            /// Asks the server to report progress under `token`.
+            pub fn progress_token(mut self, token: impl Into<ProgressToken>) -> Self {
+                self.params.0 = self.params.0.with_progress_token(token);
+                self
+            }
+        }
+    )*};
+}
+
+paginated_setters!(
+    ListPromptsParams,
+    ListResourcesParams,
+    ListResourceTemplatesParams,
+    ListToolsParams,
+);
+
+impl ClientRequestBuilder<CallToolParams> {
+    /// Adds a tool argument, failing if `value` does not serialize to JSON.
+    pub fn arg(
+        mut self,
+        name: impl Into<String>,
+        value: impl Serialize,
+    ) -> Result<Self, serde_json::Error> {
+        let value = serde_json::to_value(value)?;
+        self.params
+            .arguments
+            .get_or_insert_with(HashMap::new)
+            .insert(name.into(), value);
+        Ok(self)
+    }
+
+    /// Asks the server to report progress under `token`.
+    pub fn progress_token(mut self, token: impl Into<ProgressToken>) -> Self {
+        self.params
+            .meta
+            .get_or_insert_with(RequestMeta::default)
+            .progress_token = Some(token.into());
+        self
+    }
+}
+
+fn paginated<P: From<PaginatedParams>>(
+    id: impl Into<RequestId>,
+    wrap: fn(RequestId, P) -> ClientRequest,
+) -> ClientRequestBuilder<P> {
+    ClientRequestBuilder::new(id, PaginatedParams::first_page().into(), wrap)
+}
+
+impl ClientRequest {
+    pub fn ping(id: impl Into<RequestId>) -> ClientRequestBuilder<PingParams> {
+        ClientRequestBuilder::new(id, PingParams {}, |id, params| ClientRequest::Ping {
+            json_rpc: JsonRpcVersion,
+            id,
+            params,
+        })
+    }
+
+    /// An `initialize` request for the latest protocol version, declaring no
+    /// capabilities.
+    pub fn initialize(
+        id: impl Into<RequestId>,
+        client_info: Implementation,
+    ) -> ClientRequestBuilder<InitializeParams> {
+        let params = InitializeParams {
+            protocol_version: LATEST_PROTOCOL_VERSION.to_string(),
+            capabilities: ClientCapabilities {
+                experimental: None,
+                roots: None,
+                sampling: None,
+                elicitation: None,
+                extra: HashMap::new(),
+            },
+            client_info,
+        };
+        ClientRequestBuilder::new(id, params, |id, params| ClientRequest::Initialize {
+            json_rpc: JsonRpcVersion,
+            id,
+            params,
+        })
+    }
+
+    /// A `completion/complete` request for the argument `name`, of which
+    /// the user has typed `value` so far.
+    pub fn complete(
+        id: impl Into<RequestId>,
+        r#ref: ReferenceType,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> ClientRequestBuilder<CompleteParams> {
+        let params = CompleteParams {
+            r#ref,
+            argument: CompleteArgument {
+                name: name.into(),
+                value: value.into(),
+                extra: HashMap::new(),
+            },
+            context: None,
+            extra: HashMap::new(),
+        };
+        ClientRequestBuilder::new(id, params, |id, params| ClientRequest::Complete {
+            json_rpc: JsonRpcVersion,
+            id,
+            params,
+        })
+    }
+
+    pub fn set_level(
+        id: impl Into<RequestId>,
+        level: LoggingLevel,
+    ) -> ClientRequestBuilder<SetLevelParams> {
+        let params = SetLevelParams {
+            level,
+            extra: HashMap::new(),
+        };
+        ClientRequestBuilder::new(id, params, |id, params| ClientRequest::SetLevel {
+            json_rpc: JsonRpcVersion,
+            id,
+            params,
+        })
+    }
+
+    pub fn get_prompt(
+        id: impl Into<RequestId>,
+        name: impl Into<McpStr>,
+    ) -> ClientRequestBuilder<GetPromptParams> {
+        let params = GetPromptParams {
+            name: name.into(),
+            arguments: None,
+            extra: HashMap::new(),
+        };
+        ClientRequestBuilder::new(id, params, |id, params| ClientRequest::GetPrompt {
+            json_rpc: JsonRpcVersion,
+            id,
+            params,
+        })
+    }
+
+    pub fn list_prompts(id: impl Into<RequestId>) -> ClientRequestBuilder<ListPromptsParams> {
+        paginated(id, |id, params| ClientRequest::ListPrompts {
+            json_rpc: JsonRpcVersion,
+            id,
+            params,
+        })
+    }
+
+    pub fn list_resources(id: impl Into<RequestId>) -> ClientRequestBuilder<ListResourcesParams> {
+        paginated(id, |id, params| ClientRequest::ListResources {
+            json_rpc: JsonRpcVersion,
+            id,
+            params,
+        })
+    }
+
+    pub fn list_resource_templates(
+        id: impl Into<RequestId>,
+    ) -> ClientRequestBuilder<ListResourceTemplatesParams> {
+        paginated(id, |id, params| ClientRequest::ListResourceTemplates {
+            json_rpc: JsonRpcVersion,
+            id,
+            params,
+        })
+    }
+
+    pub fn read_resource(
+        id: impl Into<RequestId>,
+        uri: impl Into<McpStr>,
+    ) -> ClientRequestBuilder<ReadResourceParams> {
+        let params = ReadResourceParams {
+            uri: uri.into(),
+            extra: HashMap::new(),
+        };
+        ClientRequestBuilder::new(id, params, |id, params| ClientRequest::ReadResource {
+            json_rpc: JsonRpcVersion,
+            id,
+            params,
+        })
+    }
+
+    pub fn subscribe(
+        id: impl Into<RequestId>,
+        uri: impl Into<McpStr>,
+    ) -> ClientRequestBuilder<SubscribeParams> {
+        let params = SubscribeParams {
+            uri: uri.into(),
+            extra: HashMap::new(),
+        };
+        ClientRequestBuilder::new(id, params, |id, params| ClientRequest::Subscribe {
+            json_rpc: JsonRpcVersion,
+            id,
+            params,
+        })
+    }
+
+    pub fn unsubscribe(
+        id: impl Into<RequestId>,
+        uri: impl Into<McpStr>,
+    ) -> ClientRequestBuilder<UnsubscribeParams> {
+        let params = UnsubscribeParams {
+            uri: uri.into(),
+            extra: HashMap::new(),
+        };
+        ClientRequestBuilder::new(id, params, |id, params| ClientRequest::Unsubscribe {
+            json_rpc: JsonRpcVersion,
+            id,
+            params,
+        })
+    }
+
+    pub fn call_tool(
+        id: impl Into<RequestId>,
+        name: impl Into<McpStr>,
+    ) -> ClientRequestBuilder<CallToolParams> {
+        let params = CallToolParams {
+            meta: None,
+            name: name.into(),
+            arguments: None,
+            extra: HashMap::new(),
+        };
+        ClientRequestBuilder::new(id, params, |id, params| ClientRequest::CallTool {
+            json_rpc: JsonRpcVersion,
+            id,
+            params,
+        })
+    }
+
+    pub fn list_tools(id: impl Into<RequestId>) -> ClientRequestBuilder<ListToolsParams> {
+        paginated(id, |id, params| ClientRequest::ListTools {
+            json_rpc: JsonRpcVersion,
+            id,
+            params,
+        })
+    }
+
+    #[deprecated(
+        note = "elicitation/create is a server request; use `ServerRequest::elicitation_create`"
+    )]
+    pub fn elicitation_create(
+        id: impl Into<RequestId>,
+        message: impl Into<String>,
+        requested_schema: RequestedSchema,
+    ) -> ClientRequestBuilder<ElicitationCreateParams> {
+        let params = ElicitationCreateParams {
+            message: message.into(),
+            requested_schema,
+            extra: HashMap::new(),
+        };
+        #[allow(deprecated)]
+        ClientRequestBuilder::new(id, params, |id, params| ClientRequest::ElicitationCreate {
+            json_rpc: JsonRpcVersion,
+            id,
+            params,
+        })
+    }
+}
+
+/// Builds a [`ServerRequest`], like [`ClientRequestBuilder`] does for client
+/// requests. Start from the constructor named after the method, e.g.
+/// [`ServerRequest::create_message`].
+#[derive(Debug, Clone)]
+pub struct ServerRequestBuilder<P, C = SamplingContent> {
+    id: RequestId,
+    params: P,
+    wrap: fn(RequestId, P) -> ServerRequest<C>,
+}
+
+impl<P, C> ServerRequestBuilder<P, C> {
+    fn new(
+        id: impl Into<RequestId>,
+        params: P,
+        wrap: fn(RequestId, P) -> ServerRequest<C>,
+    ) -> Self {
+        ServerRequestBuilder {
+            id: id.into(),
+            params,
+            wrap,
+        }
+    }
+
+    /// Mutable access to the parameters, for fields without a setter.
+    pub fn params_mut(&mut self) -> &mut P {
+        &mut self.params
+    }
+
+    pub fn build(self) -> ServerRequest<C> {
+        (self.wrap)(self.id, self.params)
+    }
+}
+
+impl<C> ServerRequestBuilder<CreateMessageParams<C>, C> {
+    pub fn system_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.params.system_prompt = Some(prompt.into());
+        self
+    }
+
+    pub fn temperature(mut self, temperature: f64) -> Self {
+        self.params.temperature = Some(temperature);
+        self
+    }
+
+    pub fn model_preferences(mut self, preferences: ModelPreferences) -> Self {
+        self.params.model_preferences = Some(preferences);
+        self
+    }
+
+    /// Adds a sequence that stops generation.
+    pub fn stop_sequence(mut self, sequence: impl Into<String>) -> Self {
+        self.params
+            .stop_sequences
+            .get_or_insert_with(Vec::new)
+            .push(sequence.into());
+        self
+    }
+}
+
+impl<C> ServerRequest<C> {
+    pub fn ping(id: impl Into<RequestId>) -> ServerRequestBuilder<PingParams, C> {
+        ServerRequestBuilder::new(id, PingParams {}, |id, params| ServerRequest::Ping {
+            json_rpc: JsonRpcVersion,
+            id,
+            params,
+        })
+    }
+
+    /// A `sampling/createMessage` request for `messages`, generating at
+    /// most `max_tokens` tokens.
+    pub fn create_message(
+        id: impl Into<RequestId>,
+        messages: Vec<SamplingMessage<C>>,
+        max_tokens: i64,
+    ) -> ServerRequestBuilder<CreateMessageParams<C>, C> {
+        let params = CreateMessageParams {
+            messages,
+            model_preferences: None,
+            system_prompt: None,
+            include_context: None,
+            temperature: None,
+            max_tokens,
+            stop_sequences: None,
+            metadata: None,
+            extra: HashMap::new(),
+        };
+        ServerRequestBuilder::new(id, params, |id, params| ServerRequest::CreateMessage {
+            json_rpc: JsonRpcVersion,
+            id,
+            params,
+        })
+    }
+
+    pub fn list_roots(id: impl Into<RequestId>) -> ServerRequestBuilder<ListRootsParams, C> {
+        let params = ListRootsParams::default();
+        ServerRequestBuilder::new(id, params, |id, params| ServerRequest::ListRoots {
+            json_rpc: JsonRpcVersion,
+            id,
+            params,
+        })
+    }
+
+    pub fn elicitation_create(
+        id: impl Into<RequestId>,
+        message: impl Into<String>,
+        requested_schema: RequestedSchema,
+    ) -> ServerRequestBuilder<ElicitationCreateParams, C> {
+        let params = ElicitationCreateParams {
+            message: message.into(),
+            requested_schema,
+            extra: HashMap::new(),
+        };
+        ServerRequestBuilder::new(id, params, |id, params| ServerRequest::ElicitationCreate {
+            json_rpc: JsonRpcVersion,
+            id,
+            params,
+        })
+    }
+}
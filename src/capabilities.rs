@@ -0,0 +1,217 @@
+//! Set operations on capabilities, for aggregator servers that proxy several
+//! upstream MCP servers and must advertise one combined capability set,
+//! and for proxies that forward several clients' requests to one server.
+
+use crate::types::*;
+use serde_json::Value;
+use std::collections::HashMap;
+
+fn merge_flag(a: Option<bool>, b: Option<bool>) -> Option<bool> {
+    match (a, b) {
+        (None, None) => None,
+        (a, b) => Some(a.unwrap_or(false) || b.unwrap_or(false)),
+    }
+}
+
+fn intersect_flag(a: Option<bool>, b: Option<bool>) -> Option<bool> {
+    match (a, b) {
+        (None, None) => None,
+        (a, b) => Some(a.unwrap_or(false) && b.unwrap_or(false)),
+    }
+}
+
+fn merge_option<T>(a: Option<T>, b: Option<T>, merge: impl FnOnce(T, T) -> T) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(merge(a, b)),
+        (a, b) => a.or(b),
+    }
+}
+
+fn intersect_option<T>(a: Option<T>, b: Option<T>, intersect: impl FnOnce(T, T) -> T) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(intersect(a, b)),
+        _ => None,
+    }
+}
+
+/// Union of two maps; on key conflicts the entry from `a` wins.
+fn merge_map(mut a: HashMap<String, Value>, b: HashMap<String, Value>) -> HashMap<String, Value> {
+    for (key, value) in b {
+        a.entry(key).or_insert(value);
+    }
+    a
+}
+
+/// Entries of `a` whose keys are also present in `b`.
+fn intersect_map(
+    mut a: HashMap<String, Value>,
+    b: HashMap<String, Value>,
+) -> HashMap<String, Value> {
+    a.retain(|key, _| b.contains_key(key));
+    a
+}
+
+impl PromptsCapability {
+    pub fn merge(self, other: Self) -> Self {
+        PromptsCapability {
+            list_changed: merge_flag(self.list_changed, other.list_changed),
+        }
+    }
+
+    pub fn intersect(self, other: Self) -> Self {
+        PromptsCapability {
+            list_changed: intersect_flag(self.list_changed, other.list_changed),
+        }
+    }
+}
+
+impl ResourcesCapability {
+    pub fn merge(self, other: Self) -> Self {
+        ResourcesCapability {
+            subscribe: merge_flag(self.subscribe, other.subscribe),
+            list_changed: merge_flag(self.list_changed, other.list_changed),
+        }
+    }
+
+    pub fn intersect(self, other: Self) -> Self {
+        ResourcesCapability {
+            subscribe: intersect_flag(self.subscribe, other.subscribe),
+            list_changed: intersect_flag(self.list_changed, other.list_changed),
+        }
+    }
+}
+
+impl ToolsCapability {
+    pub fn merge(self, other: Self) -> Self {
+        ToolsCapability {
+            list_changed: merge_flag(self.list_changed, other.list_changed),
+        }
+    }
+
+    pub fn intersect(self, other: Self) -> Self {
+        ToolsCapability {
+            list_changed: intersect_flag(self.list_changed, other.list_changed),
+        }
+    }
+}
+
+impl RootsCapability {
+    pub fn merge(self, other: Self) -> Self {
+        RootsCapability {
+            list_changed: merge_flag(self.list_changed, other.list_changed),
+        }
+    }
+
+    pub fn intersect(self, other: Self) -> Self {
+        RootsCapability {
+            list_changed: intersect_flag(self.list_changed, other.list_changed),
+        }
+    }
+}
+
+impl SamplingCapability {
+    /// Union of both settings; on conflicts the settings from `self` win.
+    pub fn merge(self, other: Self) -> Self {
+        SamplingCapability {
+            extra: merge_map(self.extra, other.extra),
+        }
+    }
+
+    /// Settings declared by both sides, with the values from `self`.
+    pub fn intersect(self, other: Self) -> Self {
+        SamplingCapability {
+            extra: intersect_map(self.extra, other.extra),
+        }
+    }
+}
+
+impl ElicitationCapability {
+    /// Union of both settings; on conflicts the settings from `self` win.
+    pub fn merge(self, other: Self) -> Self {
+        ElicitationCapability {
+            extra: merge_map(self.extra, other.extra),
+        }
+    }
+
+    /// Settings declared by both sides, with the values from `self`.
+    pub fn intersect(self, other: Self) -> Self {
+        ElicitationCapability {
+            extra: intersect_map(self.extra, other.extra),
+        }
+    }
+}
+
+impl ExperimentalCapabilities {
+    /// Union of both sets; on conflicts the settings from `self` win.
+    pub fn merge(self, other: Self) -> Self {
+        ExperimentalCapabilities(merge_map(self.0, other.0))
+    }
+
+    /// Capabilities declared by both sets, with the settings from `self`.
+    pub fn intersect(self, other: Self) -> Self {
+        ExperimentalCapabilities(intersect_map(self.0, other.0))
+    }
+}
+
+impl ServerCapabilities {
+    /// The capabilities offered by either server: a feature is advertised if
+    /// any side supports it, and a sub-flag (`listChanged`, `subscribe`) is
+    /// set if any side sets it. Map entries from `self` win on conflicts.
+    pub fn merge(self, other: Self) -> Self {
+        ServerCapabilities {
+            experimental: merge_option(self.experimental, other.experimental, |a, b| a.merge(b)),
+            logging: merge_option(self.logging, other.logging, merge_map),
+            prompts: merge_option(self.prompts, other.prompts, |a, b| a.merge(b)),
+            resources: merge_option(self.resources, other.resources, |a, b| a.merge(b)),
+            tools: merge_option(self.tools, other.tools, |a, b| a.merge(b)),
+            completions: merge_option(self.completions, other.completions, merge_map),
+            extra: merge_map(self.extra, other.extra),
+        }
+    }
+
+    /// The capabilities offered by both servers: a feature is advertised
+    /// only if both sides support it, and a sub-flag only if both set it.
+    pub fn intersect(self, other: Self) -> Self {
+        ServerCapabilities {
+            experimental: intersect_option(self.experimental, other.experimental, |a, b| {
+                a.intersect(b)
+            }),
+            logging: intersect_option(self.logging, other.logging, intersect_map),
+            prompts: intersect_option(self.prompts, other.prompts, |a, b| a.intersect(b)),
+            resources: intersect_option(self.resources, other.resources, |a, b| a.intersect(b)),
+            tools: intersect_option(self.tools, other.tools, |a, b| a.intersect(b)),
+            completions: intersect_option(self.completions, other.completions, intersect_map),
+            extra: intersect_map(self.extra, other.extra),
+        }
+    }
+}
+
+impl ClientCapabilities {
+    /// The capabilities offered by either client, combined the way
+    /// [`ServerCapabilities::merge`] combines servers'.
+    pub fn merge(self, other: Self) -> Self {
+        ClientCapabilities {
+            experimental: merge_option(self.experimental, other.experimental, |a, b| a.merge(b)),
+            roots: merge_option(self.roots, other.roots, |a, b| a.merge(b)),
+            sampling: merge_option(self.sampling, other.sampling, |a, b| a.merge(b)),
+            elicitation: merge_option(self.elicitation, other.elicitation, |a, b| a.merge(b)),
+            extra: merge_map(self.extra, other.extra),
+        }
+    }
+
+    /// The capabilities offered by both clients: a feature is advertised
+    /// only if both sides support it, and `listChanged` only if both set it.
+    pub fn intersect(self, other: Self) -> Self {
+        ClientCapabilities {
+            experimental: intersect_option(self.experimental, other.experimental, |a, b| {
+                a.intersect(b)
+            }),
+            roots: intersect_option(self.roots, other.roots, |a, b| a.intersect(b)),
+            sampling: intersect_option(self.sampling, other.sampling, |a, b| a.intersect(b)),
+            elicitation: intersect_option(self.elicitation, other.elicitation, |a, b| {
+                a.intersect(b)
+            }),
+            extra: intersect_map(self.extra, other.extra),
+        }
+    }
+}
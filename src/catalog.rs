@@ -0,0 +1,118 @@
+//! In-memory catalogs of named entries (prompts, tools, ...) with stable
+//! pagination and change detection.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+/// The default number of entries per list page.
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+
+/// The difference between two versions of a list, by key.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// Keys present in both versions whose definition changed.
+    pub changed: Vec<String>,
+}
+
+impl ListDiff {
+    /// Whether nothing changed, i.e. no `list_changed` notification is due.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Entries keyed and ordered by name. Pages are cut by key, so a cursor
+/// (the last key of the previous page) stays valid while entries are added
+/// or removed between requests.
+#[derive(Debug, Clone)]
+pub(crate) struct Catalog<T> {
+    entries: BTreeMap<String, T>,
+    pub(crate) page_size: usize,
+}
+
+impl<T> Default for Catalog<T> {
+    fn default() -> Self {
+        Catalog {
+            entries: BTreeMap::new(),
+            page_size: DEFAULT_PAGE_SIZE,
+        }
+    }
+}
+
+impl<T: Clone + Serialize> Catalog<T> {
+    pub(crate) fn insert(&mut self, key: String, entry: T) -> Option<T> {
+        self.entries.insert(key, entry)
+    }
+
+    pub(crate) fn remove(&mut self, key: &str) -> Option<T> {
+        self.entries.remove(key)
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<&T> {
+        self.entries.get(key)
+    }
+
+    pub(crate) fn contains(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub(crate) fn values(&self) -> impl Iterator<Item = &T> {
+        self.entries.values()
+    }
+
+    /// The page following `cursor` (or the first page), and the cursor of
+    /// the next page if there is one.
+    pub(crate) fn page(&self, cursor: Option<&str>) -> (Vec<T>, Option<String>) {
+        let start = match cursor {
+            Some(cursor) => Bound::Excluded(cursor),
+            None => Bound::Unbounded,
+        };
+        let mut range = self
+            .entries
+            .range::<str, _>((start, Bound::Unbounded))
+            .peekable();
+        let mut items = Vec::new();
+        let mut last = None;
+        while items.len() < self.page_size.max(1) {
+            match range.next() {
+                Some((key, entry)) => {
+                    items.push(entry.clone());
+                    last = Some(key);
+                }
+                None => break,
+            }
+        }
+        let next_cursor = range.peek().and(last).cloned();
+        (items, next_cursor)
+    }
+
+    pub(crate) fn diff(&self, previous: &Catalog<T>) -> ListDiff {
+        // Entries that fail to serialize cannot be compared, so they count
+        // as changed.
+        let as_value = |entry: &T| serde_json::to_value(entry).ok();
+        let mut diff = ListDiff::default();
+        for (key, entry) in &self.entries {
+            match previous.entries.get(key) {
+                None => diff.added.push(key.clone()),
+                Some(old) => match (as_value(old), as_value(entry)) {
+                    (Some(old), Some(new)) if old == new => {}
+                    _ => diff.changed.push(key.clone()),
+                },
+            }
+        }
+        diff.removed = previous
+            .entries
+            .keys()
+            .filter(|key| !self.entries.contains_key(*key))
+            .cloned()
+            .collect();
+        diff
+    }
+}
@@ -0,0 +1,281 @@
+//! Chunked reads of large resources.
+//!
+//! A server can answer `resources/read` for a large resource with several
+//! contents entries, one per chunk, each carrying a [`ResourceChunkMeta`]
+//! under [`meta_keys::RESOURCE_CHUNK`] in its `_meta`. Clients put the chunks
+//! back together with a [`ChunkAssembler`].
+//!
+//! Byte ranges count bytes of the UTF-8 text, or decoded bytes of a blob.
+//! Blob chunks are cut at multiples of 3 bytes, so each chunk is valid
+//! base64 on its own and the chunks concatenate to the original.
+
+use crate::extras::HasMeta;
+use crate::meta_keys;
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+
+/// The position of one chunk within the full contents of a resource.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceChunkMeta {
+    /// Zero-based position of the chunk.
+    pub index: u32,
+    /// Number of chunks the contents were split into.
+    pub total: u32,
+    /// The bytes of the full contents this chunk holds.
+    pub byte_range: Range<u64>,
+}
+
+/// Why chunks could not be reassembled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChunkError {
+    /// A contents entry has no well-formed [`ResourceChunkMeta`].
+    MissingChunkMeta,
+    /// A chunk arrived out of order.
+    OutOfOrder { expected: u32, found: u32 },
+    /// A chunk belongs to other contents: its URI, kind, or total differs,
+    /// or its byte range does not continue the previous chunk.
+    Mismatch,
+    /// A chunk's metadata is inconsistent: its total is zero or its index
+    /// is not below its total.
+    InvalidChunkMeta,
+    /// A chunk arrived after the last one.
+    AlreadyComplete,
+    /// A blob to split is not padded base64.
+    InvalidBase64,
+}
+
+impl fmt::Display for ChunkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChunkError::MissingChunkMeta => f.write_str("contents have no chunk metadata"),
+            ChunkError::OutOfOrder { expected, found } => {
+                write!(f, "expected chunk {}, got chunk {}", expected, found)
+            }
+            ChunkError::Mismatch => f.write_str("chunk does not continue the previous chunks"),
+            ChunkError::InvalidChunkMeta => f.write_str("chunk index is not below its total"),
+            ChunkError::AlreadyComplete => f.write_str("all chunks were already received"),
+            ChunkError::InvalidBase64 => f.write_str("blob is not padded base64"),
+        }
+    }
+}
+
+impl std::error::Error for ChunkError {}
+
+impl ResourceContents {
+    fn meta_slot(&mut self) -> &mut Option<HashMap<String, serde_json::Value>> {
+        match self {
+            ResourceContents::Text(text) => &mut text.meta,
+            ResourceContents::Blob(blob) => &mut blob.meta,
+        }
+    }
+
+    pub fn uri(&self) -> &str {
+        match self {
+            ResourceContents::Text(text) => &text.uri,
+            ResourceContents::Blob(blob) => &blob.uri,
+        }
+    }
+
+    /// The chunk position, if these contents are one chunk of a larger
+    /// whole.
+    pub fn chunk_meta(&self) -> Option<ResourceChunkMeta> {
+        self.get_meta_key(meta_keys::RESOURCE_CHUNK)
+    }
+
+    pub fn with_chunk_meta(mut self, chunk: ResourceChunkMeta) -> Self {
+        self.set_meta_key(meta_keys::RESOURCE_CHUNK, chunk)
+            .expect("chunk meta serializes");
+        self
+    }
+}
+
+/// Byte offsets at which to cut `text` into pieces of at most `max_bytes`,
+/// keeping characters whole.
+fn text_cuts(text: &str, max_bytes: usize) -> Vec<usize> {
+    let mut cuts = vec![0];
+    let mut start = 0;
+    while text.len() - start > max_bytes {
+        let mut end = start + max_bytes;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        if end == start {
+            // A character wider than `max_bytes` goes into a chunk alone.
+            end = start + 1;
+            while !text.is_char_boundary(end) {
+                end += 1;
+            }
+        }
+        cuts.push(end);
+        start = end;
+    }
+    cuts.push(text.len());
+    cuts
+}
+
+/// Whether `blob` is standard base64 with padding, so that it can be cut at
+/// any multiple of 4 characters.
+#[allow(clippy::manual_is_multiple_of)]
+fn is_base64(blob: &str) -> bool {
+    let data = blob.trim_end_matches('=');
+    blob.len() % 4 == 0
+        && blob.len() - data.len() <= 2
+        && data
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/')
+}
+
+/// Splits `contents` into chunks of at most `max_bytes` each, tagged with
+/// their [`ResourceChunkMeta`]. Contents that already fit are returned
+/// unchanged, without chunk metadata.
+///
+/// Text is only cut at character boundaries, so a chunk can exceed
+/// `max_bytes` by up to three bytes if `max_bytes` is smaller than a
+/// character. Blob chunks are rounded down to a multiple of 3 bytes (at
+/// least 3), which is 4 base64 characters per 3 bytes; a blob that is not
+/// padded base64 is rejected with [`ChunkError::InvalidBase64`].
+pub fn split_contents(
+    contents: &ResourceContents,
+    max_bytes: usize,
+) -> Result<Vec<ResourceContents>, ChunkError> {
+    let max_bytes = max_bytes.max(1);
+    let pieces: Vec<(ResourceContents, Range<u64>)> = match contents {
+        ResourceContents::Text(text) => {
+            if text.text.len() <= max_bytes {
+                return Ok(vec![contents.clone()]);
+            }
+            text_cuts(&text.text, max_bytes)
+                .windows(2)
+                .map(|w| {
+                    let chunk = TextResourceContents {
+                        text: text.text[w[0]..w[1]].to_string(),
+                        ..text.clone()
+                    };
+                    (ResourceContents::Text(chunk), w[0] as u64..w[1] as u64)
+                })
+                .collect()
+        }
+        ResourceContents::Blob(blob) => {
+            if !is_base64(&blob.blob) {
+                return Err(ChunkError::InvalidBase64);
+            }
+            let decoded_len = blob.blob.trim_end_matches('=').len() * 3 / 4;
+            if decoded_len <= max_bytes {
+                return Ok(vec![contents.clone()]);
+            }
+            let per_chunk = (max_bytes / 3).max(1) * 3;
+            let chars_per_chunk = per_chunk / 3 * 4;
+            (0..blob.blob.len())
+                .step_by(chars_per_chunk)
+                .map(|start| {
+                    let end = (start + chars_per_chunk).min(blob.blob.len());
+                    let bytes_start = (start / 4 * 3) as u64;
+                    let bytes_end = ((start / 4 * 3) + per_chunk).min(decoded_len) as u64;
+                    let chunk = BlobResourceContents {
+                        blob: blob.blob[start..end].to_string(),
+                        ..blob.clone()
+                    };
+                    (ResourceContents::Blob(chunk), bytes_start..bytes_end)
+                })
+                .collect()
+        }
+    };
+    let total = pieces.len() as u32;
+    Ok(pieces
+        .into_iter()
+        .enumerate()
+        .map(|(index, (chunk, byte_range))| {
+            chunk.with_chunk_meta(ResourceChunkMeta {
+                index: index as u32,
+                total,
+                byte_range,
+            })
+        })
+        .collect())
+}
+
+/// Reassembles chunks produced by [`split_contents`], in order.
+#[derive(Debug, Clone, Default)]
+pub struct ChunkAssembler {
+    contents: Option<ResourceContents>,
+    next: u32,
+    total: u32,
+    bytes: u64,
+}
+
+impl ChunkAssembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the next chunk, returning the full contents once the last chunk
+    /// has been added. Contents without chunk metadata are complete as they
+    /// are when nothing has been added yet.
+    pub fn push(
+        &mut self,
+        chunk: ResourceContents,
+    ) -> Result<Option<ResourceContents>, ChunkError> {
+        if self.total > 0 && self.next == self.total {
+            return Err(ChunkError::AlreadyComplete);
+        }
+        let Some(meta) = chunk.chunk_meta() else {
+            if self.contents.is_none() {
+                self.next = 1;
+                self.total = 1;
+                return Ok(Some(chunk));
+            }
+            return Err(ChunkError::MissingChunkMeta);
+        };
+        if meta.total == 0 || meta.index >= meta.total {
+            return Err(ChunkError::InvalidChunkMeta);
+        }
+        if meta.index != self.next {
+            return Err(ChunkError::OutOfOrder {
+                expected: self.next,
+                found: meta.index,
+            });
+        }
+        if meta.byte_range.start != self.bytes || meta.byte_range.end < meta.byte_range.start {
+            return Err(ChunkError::Mismatch);
+        }
+        match (&mut self.contents, chunk) {
+            (None, mut chunk) => {
+                let slot = chunk.meta_slot();
+                if let Some(entries) = slot {
+                    entries.remove(meta_keys::RESOURCE_CHUNK);
+                }
+                if slot.as_ref().is_some_and(HashMap::is_empty) {
+                    *slot = None;
+                }
+                self.total = meta.total;
+                self.contents = Some(chunk);
+            }
+            (Some(ResourceContents::Text(whole)), ResourceContents::Text(part))
+                if whole.uri == part.uri && meta.total == self.total =>
+            {
+                whole.text.push_str(&part.text)
+            }
+            (Some(ResourceContents::Blob(whole)), ResourceContents::Blob(part))
+                if whole.uri == part.uri && meta.total == self.total =>
+            {
+                whole.blob.push_str(&part.blob)
+            }
+            _ => return Err(ChunkError::Mismatch),
+        }
+        self.next += 1;
+        self.bytes = meta.byte_range.end;
+        if self.next == self.total {
+            return Ok(self.contents.take());
+        }
+        Ok(None)
+    }
+
+    /// Whether every chunk has been added.
+    pub fn is_complete(&self) -> bool {
+        self.total > 0 && self.next == self.total
+    }
+}
@@ -0,0 +1,81 @@
+//! Helpers for servers that offer argument completion.
+
+use crate::types::*;
+use std::collections::HashMap;
+
+/// Supplies values for `completion/complete`.
+///
+/// `context` holds argument values the client has already resolved, keyed
+/// by argument name; it is empty when the client sent none.
+pub trait CompletionProvider {
+    fn complete(
+        &self,
+        reference: &ReferenceType,
+        argument: &CompleteArgument,
+        context: &HashMap<String, String>,
+    ) -> CompletionData;
+
+    /// Answers a `completion/complete` request.
+    fn complete_request(&self, params: &CompleteParams) -> CompleteResult {
+        let empty = HashMap::new();
+        let context = params
+            .context
+            .as_ref()
+            .map_or(&empty, |context| &context.arguments);
+        CompleteResult::new(CompletePayload {
+            completion: self.complete(&params.r#ref, &params.argument, context),
+        })
+    }
+}
+
+/// A [`CompletionProvider`] backed by fixed candidate lists.
+///
+/// Candidates are registered per reference and argument name, and offered
+/// when they start with the value typed so far (case-sensitively), capped at
+/// [`MAX_COMPLETION_VALUES`].
+#[derive(Debug, Clone, Default)]
+pub struct StaticCompletions {
+    candidates: HashMap<(String, String), Vec<String>>,
+}
+
+impl StaticCompletions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the candidates for `argument` of `reference`, replacing
+    /// any registered before.
+    pub fn with<I, S>(mut self, reference: &ReferenceType, argument: &str, values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.candidates.insert(
+            (reference.to_string(), argument.to_string()),
+            values.into_iter().map(Into::into).collect(),
+        );
+        self
+    }
+}
+
+impl CompletionProvider for StaticCompletions {
+    fn complete(
+        &self,
+        reference: &ReferenceType,
+        argument: &CompleteArgument,
+        _context: &HashMap<String, String>,
+    ) -> CompletionData {
+        match self
+            .candidates
+            .get(&(reference.to_string(), argument.name.clone()))
+        {
+            Some(values) => CompletionData::from_values(
+                values
+                    .iter()
+                    .filter(|value| value.starts_with(&argument.value))
+                    .cloned(),
+            ),
+            None => CompletionData::empty(),
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! The `mcp.json` client configuration format, shared by Claude Desktop,
+//! Cursor, and other hosts:
+//!
+//! ```json
+//! {
+//!   "mcpServers": {
+//!     "files": { "command": "npx", "args": ["-y", "server-files", "/tmp"] },
+//!     "remote": { "url": "https://mcp.example.com/mcp", "headers": { "Authorization": "Bearer ..." } }
+//!   }
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// A client configuration listing the servers to launch or connect to.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpConfig {
+    #[serde(default)]
+    pub mcp_servers: BTreeMap<String, ServerConfig>,
+
+    /// Host-specific settings outside `mcpServers`.
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// How to reach one server: a process to spawn or a URL to connect to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ServerConfig {
+    Stdio(StdioServerConfig),
+    Remote(RemoteServerConfig),
+}
+
+/// A server launched as a child process speaking MCP over stdio.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StdioServerConfig {
+    /// `"stdio"` when present; most hosts infer it from `command`.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// A server reached over HTTP (Streamable HTTP or the older SSE transport).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteServerConfig {
+    /// `"http"`, `"streamable-http"`, or `"sse"` when present.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// A problem found by [`McpConfig::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    EmptyServerName,
+    /// The server has an empty `command`.
+    EmptyCommand(String),
+    /// The server's `url` is not an `http` or `https` URL.
+    InvalidUrl {
+        server: String,
+        url: String,
+    },
+    /// The server's `type` does not match how it is configured.
+    TypeMismatch {
+        server: String,
+        r#type: String,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::EmptyServerName => f.write_str("server name is empty"),
+            ConfigError::EmptyCommand(server) => {
+                write!(f, "server {:?} has an empty command", server)
+            }
+            ConfigError::InvalidUrl { server, url } => {
+                write!(f, "server {:?} has invalid url {:?}", server, url)
+            }
+            ConfigError::TypeMismatch { server, r#type } => {
+                write!(f, "server {:?} has mismatched type {:?}", server, r#type)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl McpConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn server(mut self, name: impl Into<String>, config: impl Into<ServerConfig>) -> Self {
+        self.mcp_servers.insert(name.into(), config.into());
+        self
+    }
+
+    /// Checks every server entry, returning the first problem found.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for (name, server) in &self.mcp_servers {
+            if name.trim().is_empty() {
+                return Err(ConfigError::EmptyServerName);
+            }
+            server.validate(name)?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether `url` is an absolute `http` or `https` URL.
+pub(crate) fn is_http_url(url: &str) -> bool {
+    ["http://", "https://"].iter().any(|scheme| {
+        url.len() > scheme.len()
+            && url
+                .get(..scheme.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+    })
+}
+
+impl ServerConfig {
+    fn validate(&self, name: &str) -> Result<(), ConfigError> {
+        let mismatch = |r#type: &str| ConfigError::TypeMismatch {
+            server: name.to_string(),
+            r#type: r#type.to_string(),
+        };
+        match self {
+            ServerConfig::Stdio(config) => {
+                if config.command.trim().is_empty() {
+                    return Err(ConfigError::EmptyCommand(name.to_string()));
+                }
+                match config.r#type.as_deref() {
+                    None | Some("stdio") => Ok(()),
+                    Some(other) => Err(mismatch(other)),
+                }
+            }
+            ServerConfig::Remote(config) => {
+                if !is_http_url(&config.url) {
+                    return Err(ConfigError::InvalidUrl {
+                        server: name.to_string(),
+                        url: config.url.clone(),
+                    });
+                }
+                match config.r#type.as_deref() {
+                    None | Some("http") | Some("streamable-http") | Some("sse") => Ok(()),
+                    Some(other) => Err(mismatch(other)),
+                }
+            }
+        }
+    }
+}
+
+impl StdioServerConfig {
+    pub fn new(command: impl Into<String>) -> Self {
+        StdioServerConfig {
+            command: command.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.insert(key.into(), value.into());
+        self
+    }
+}
+
+impl RemoteServerConfig {
+    pub fn new(url: impl Into<String>) -> Self {
+        RemoteServerConfig {
+            url: url.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+}
+
+impl From<StdioServerConfig> for ServerConfig {
+    fn from(config: StdioServerConfig) -> Self {
+        ServerConfig::Stdio(config)
+    }
+}
+
+impl From<RemoteServerConfig> for ServerConfig {
+    fn from(config: RemoteServerConfig) -> Self {
+        ServerConfig::Remote(config)
+    }
+}
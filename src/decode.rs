@@ -0,0 +1,382 @@
+//! Lenient decoding of messages from peers that deviate slightly from the
+//! JSON-RPC and MCP specs, and strict decoding that rejects envelopes the
+//! serde derives would otherwise accept.
+
+use crate::types::*;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// A deviation from the spec that was tolerated while decoding.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeWarning {
+    /// The `jsonrpc` member was missing and has been assumed to be `"2.0"`.
+    MissingJsonRpc,
+    /// The `jsonrpc` member had a non-standard value (e.g. `"2"`).
+    NonStandardJsonRpc(Value),
+    /// The `id` was an integral floating-point number (e.g. `1.0`).
+    FloatId(f64),
+    /// A snake_case member of `params` or `result` was renamed to camelCase.
+    SnakeCaseKey { from: String, to: String },
+}
+
+impl fmt::Display for DecodeWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeWarning::MissingJsonRpc => f.write_str("missing \"jsonrpc\" member"),
+            DecodeWarning::NonStandardJsonRpc(value) => {
+                write!(f, "non-standard \"jsonrpc\" value {}", value)
+            }
+            DecodeWarning::FloatId(id) => write!(f, "floating-point id {}", id),
+            DecodeWarning::SnakeCaseKey { from, to } => {
+                write!(f, "snake_case member {:?} read as {:?}", from, to)
+            }
+        }
+    }
+}
+
+/// Size limits for text content, guarding a host's context window against
+/// multi-megabyte tool output. All limits are off by default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DecodeLimits {
+    /// Maximum number of characters in a single text content block.
+    pub max_text_chars: Option<usize>,
+    /// Maximum number of UTF-8 bytes in a single text content block.
+    pub max_text_bytes: Option<usize>,
+}
+
+/// A text content block exceeded a [`DecodeLimits`] limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LimitExceeded {
+    TextChars { limit: usize, actual: usize },
+    TextBytes { limit: usize, actual: usize },
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitExceeded::TextChars { limit, actual } => write!(
+                f,
+                "text content has {} characters, limit is {}",
+                actual, limit
+            ),
+            LimitExceeded::TextBytes { limit, actual } => {
+                write!(f, "text content has {} bytes, limit is {}", actual, limit)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LimitExceeded {}
+
+impl DecodeLimits {
+    /// Checks a piece of text against the limits.
+    pub fn check_text(&self, text: &str) -> Result<(), LimitExceeded> {
+        if let Some(limit) = self.max_text_bytes {
+            if text.len() > limit {
+                return Err(LimitExceeded::TextBytes {
+                    limit,
+                    actual: text.len(),
+                });
+            }
+        }
+        if let Some(limit) = self.max_text_chars {
+            let actual = text.chars().count();
+            if actual > limit {
+                return Err(LimitExceeded::TextChars { limit, actual });
+            }
+        }
+        Ok(())
+    }
+
+    /// Truncates `content` to fit the limits, returning whether it was cut.
+    pub fn enforce(&self, content: &mut TextContent) -> bool {
+        let mut truncated = false;
+        if let Some(limit) = self.max_text_chars {
+            truncated |= content.truncate_to(limit);
+        }
+        if let Some(limit) = self.max_text_bytes {
+            truncated |= content.truncate_to_bytes(limit);
+        }
+        truncated
+    }
+
+    /// Truncates every text block in `contents`, returning how many were cut.
+    pub fn enforce_all(&self, contents: &mut [PromptContent]) -> usize {
+        contents
+            .iter_mut()
+            .filter_map(|content| match content {
+                PromptContent::Text(text) => Some(self.enforce(text)),
+                _ => None,
+            })
+            .filter(|truncated| *truncated)
+            .count()
+    }
+
+    /// Checks every text content block (`{"type": "text", "text": ...}`)
+    /// anywhere inside a raw message.
+    pub fn check_value(&self, value: &Value) -> Result<(), LimitExceeded> {
+        match value {
+            Value::Object(object) => {
+                if let (Some("text"), Some(Value::String(text))) = (
+                    object.get("type").and_then(Value::as_str),
+                    object.get("text"),
+                ) {
+                    self.check_text(text)?;
+                }
+                object
+                    .values()
+                    .try_for_each(|value| self.check_value(value))
+            }
+            Value::Array(values) => values.iter().try_for_each(|value| self.check_value(value)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A decoded value together with the deviations tolerated while decoding it.
+#[derive(Debug, Clone)]
+pub struct Decoded<T> {
+    pub value: T,
+    pub warnings: Vec<DecodeWarning>,
+}
+
+/// Decodes messages while tolerating deviations seen in real-world peers,
+/// recording each one as a [`DecodeWarning`] instead of failing.
+///
+/// Every tolerance is enabled by default and can be switched off
+/// individually. The repaired message is then decoded with the normal serde
+/// implementation, so anything beyond these deviations still fails.
+#[derive(Debug, Clone)]
+pub struct LenientDecoder {
+    /// Accept messages without a `jsonrpc` member.
+    pub allow_missing_jsonrpc: bool,
+    /// Accept `jsonrpc` values other than `"2.0"`, such as `"2"` or `2`.
+    pub allow_nonstandard_jsonrpc: bool,
+    /// Accept integral floating-point ids such as `1.0`.
+    pub allow_float_ids: bool,
+    /// Rename snake_case members of `params` and `result` to camelCase,
+    /// unless the camelCase member is also present. Only top-level members
+    /// the spec defines are renamed, so vendor fields and free-form payloads
+    /// such as tool arguments keep their keys.
+    pub normalize_snake_case: bool,
+    /// Size limits for text content; a message exceeding them fails to decode.
+    pub limits: DecodeLimits,
+}
+
+impl Default for LenientDecoder {
+    fn default() -> Self {
+        LenientDecoder {
+            allow_missing_jsonrpc: true,
+            allow_nonstandard_jsonrpc: true,
+            allow_float_ids: true,
+            normalize_snake_case: true,
+            limits: DecodeLimits::default(),
+        }
+    }
+}
+
+impl LenientDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses and decodes a message from JSON text.
+    pub fn decode_str<T: DeserializeOwned>(&self, input: &str) -> serde_json::Result<Decoded<T>> {
+        self.decode(serde_json::from_str(input)?)
+    }
+
+    /// Decodes a message from a JSON value.
+    pub fn decode<T: DeserializeOwned>(&self, mut value: Value) -> serde_json::Result<Decoded<T>> {
+        use serde::de::Error;
+
+        self.limits
+            .check_value(&value)
+            .map_err(serde_json::Error::custom)?;
+        let mut warnings = Vec::new();
+        if let Value::Object(message) = &mut value {
+            self.repair(message, &mut warnings);
+        }
+        Ok(Decoded {
+            value: serde_json::from_value(value)?,
+            warnings,
+        })
+    }
+
+    fn repair(&self, message: &mut Map<String, Value>, warnings: &mut Vec<DecodeWarning>) {
+        match message.get("jsonrpc") {
+            None if self.allow_missing_jsonrpc => {
+                warnings.push(DecodeWarning::MissingJsonRpc);
+                message.insert("jsonrpc".into(), Value::from(crate::JSONRPC_VERSION));
+            }
+            Some(version)
+                if self.allow_nonstandard_jsonrpc && version != crate::JSONRPC_VERSION =>
+            {
+                warnings.push(DecodeWarning::NonStandardJsonRpc(version.clone()));
+                message.insert("jsonrpc".into(), Value::from(crate::JSONRPC_VERSION));
+            }
+            _ => {}
+        }
+
+        if self.allow_float_ids {
+            if let Some(id) = message.get_mut("id") {
+                if let Some(float) = id.as_f64().filter(|_| id.is_f64()) {
+                    if float.fract() == 0.0 && float.abs() < i64::MAX as f64 {
+                        warnings.push(DecodeWarning::FloatId(float));
+                        *id = Value::from(float as i64);
+                    }
+                }
+            }
+        }
+
+        if self.normalize_snake_case {
+            for member in ["params", "result"] {
+                if let Some(Value::Object(object)) = message.get_mut(member) {
+                    normalize_keys(object, warnings);
+                }
+            }
+        }
+    }
+}
+
+/// The camelCase members the spec defines at the top level of `params` or
+/// `result`.
+const SPEC_MEMBERS: &[&str] = &[
+    "clientInfo",
+    "includeContext",
+    "inputSchema",
+    "isError",
+    "maxTokens",
+    "modelPreferences",
+    "nextCursor",
+    "progressToken",
+    "protocolVersion",
+    "requestId",
+    "requestedSchema",
+    "resourceTemplates",
+    "serverInfo",
+    "stopReason",
+    "stopSequences",
+    "structuredContent",
+    "systemPrompt",
+];
+
+fn normalize_keys(object: &mut Map<String, Value>, warnings: &mut Vec<DecodeWarning>) {
+    let snake_keys: Vec<String> = object
+        .keys()
+        .filter(|key| key.contains('_') && !key.starts_with('_'))
+        .cloned()
+        .collect();
+    for key in snake_keys {
+        let camel = to_camel_case(&key);
+        if !SPEC_MEMBERS.contains(&camel.as_str()) || object.contains_key(&camel) {
+            continue;
+        }
+        if let Some(value) = object.remove(&key) {
+            object.insert(camel.clone(), value);
+            warnings.push(DecodeWarning::SnakeCaseKey {
+                from: key,
+                to: camel,
+            });
+        }
+    }
+}
+
+fn to_camel_case(key: &str) -> String {
+    let mut out = String::with_capacity(key.len());
+    let mut upper = false;
+    for c in key.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Why [`parse_strict`] rejected a message.
+#[derive(Debug)]
+pub enum StrictError {
+    /// The `jsonrpc` member was missing (`None`) or not `"2.0"`.
+    JsonRpcVersion(Option<Value>),
+    /// A response carried both `result` and `error`.
+    ResultAndError,
+    /// A notification (a `notifications/*` method) carried an `id`.
+    NotificationWithId(String),
+    /// The envelope was fine, but the message did not decode as the target
+    /// type.
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for StrictError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StrictError::JsonRpcVersion(None) => f.write_str("missing \"jsonrpc\" member"),
+            StrictError::JsonRpcVersion(Some(version)) => {
+                write!(f, "\"jsonrpc\" must be \"2.0\", found {}", version)
+            }
+            StrictError::ResultAndError => f.write_str("message has both \"result\" and \"error\""),
+            StrictError::NotificationWithId(method) => {
+                write!(f, "notification {} must not have an \"id\"", method)
+            }
+            StrictError::Json(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for StrictError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StrictError::Json(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for StrictError {
+    fn from(error: serde_json::Error) -> Self {
+        StrictError::Json(error)
+    }
+}
+
+/// Parses a message from JSON text, first checking its envelope: `jsonrpc`
+/// must be `"2.0"`, a response may not carry both `result` and `error`, and
+/// a notification may not carry an `id`. The plain serde implementations
+/// ignore the last two, since unknown members are skipped. Batches are
+/// checked entry by entry.
+pub fn parse_strict<T: DeserializeOwned>(input: &str) -> Result<T, StrictError> {
+    from_value_strict(serde_json::from_str(input)?)
+}
+
+/// Decodes a message from a JSON value; see [`parse_strict`].
+pub fn from_value_strict<T: DeserializeOwned>(value: Value) -> Result<T, StrictError> {
+    match &value {
+        Value::Array(entries) => entries.iter().try_for_each(check_envelope)?,
+        message => check_envelope(message)?,
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+fn check_envelope(message: &Value) -> Result<(), StrictError> {
+    let Value::Object(message) = message else {
+        // Not an envelope at all; let serde report the type mismatch.
+        return Ok(());
+    };
+    match message.get("jsonrpc") {
+        Some(version) if version == crate::JSONRPC_VERSION => {}
+        version => return Err(StrictError::JsonRpcVersion(version.cloned())),
+    }
+    if message.contains_key("result") && message.contains_key("error") {
+        return Err(StrictError::ResultAndError);
+    }
+    if let Some(method) = message.get("method").and_then(Value::as_str) {
+        if method.starts_with("notifications/") && message.contains_key("id") {
+            return Err(StrictError::NotificationWithId(method.to_string()));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,87 @@
+//! Deprecation notices for tools and prompts.
+
+use crate::extras::HasMeta;
+use crate::meta_keys;
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Announces that a tool or prompt will be removed, stored under
+/// [`meta_keys::DEPRECATION`] in its `_meta`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Deprecation {
+    /// The server version or date since which the entry is deprecated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// The name of the entry to use instead.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<String>,
+}
+
+impl Deprecation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn since(mut self, since: impl Into<String>) -> Self {
+        self.since = Some(since.into());
+        self
+    }
+
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    pub fn replacement(mut self, replacement: impl Into<String>) -> Self {
+        self.replacement = Some(replacement.into());
+        self
+    }
+}
+
+impl fmt::Display for Deprecation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("deprecated")?;
+        if let Some(since) = &self.since {
+            write!(f, " since {}", since)?;
+        }
+        if let Some(message) = &self.message {
+            write!(f, ": {}", message)?;
+        }
+        if let Some(replacement) = &self.replacement {
+            write!(f, " (use {} instead)", replacement)?;
+        }
+        Ok(())
+    }
+}
+
+impl Tool {
+    /// The tool's deprecation notice, if it has a well-formed one.
+    pub fn deprecation(&self) -> Option<Deprecation> {
+        self.get_meta_key(meta_keys::DEPRECATION)
+    }
+
+    /// Marks the tool as deprecated.
+    pub fn with_deprecation(mut self, deprecation: Deprecation) -> Self {
+        self.set_meta_key(meta_keys::DEPRECATION, deprecation)
+            .expect("deprecation serializes");
+        self
+    }
+}
+
+impl Prompt {
+    /// The prompt's deprecation notice, if it has a well-formed one.
+    pub fn deprecation(&self) -> Option<Deprecation> {
+        self.get_meta_key(meta_keys::DEPRECATION)
+    }
+
+    /// Marks the prompt as deprecated.
+    pub fn with_deprecation(mut self, deprecation: Deprecation) -> Self {
+        self.set_meta_key(meta_keys::DEPRECATION, deprecation)
+            .expect("deprecation serializes");
+        self
+    }
+}
@@ -0,0 +1,232 @@
+//! Documents served at well-known URLs so clients can discover a server and
+//! how to authorize against it before connecting.
+//!
+//! - [`DiscoveryDocument`] at [`MCP_WELL_KNOWN_PATH`] describes the server
+//!   and the transports it offers.
+//! - [`ProtectedResourceMetadata`] (RFC 9728) at
+//!   [`PROTECTED_RESOURCE_WELL_KNOWN_PATH`] names the authorization servers.
+//! - [`AuthorizationServerMetadata`] (RFC 8414) at
+//!   [`AUTHORIZATION_SERVER_WELL_KNOWN_PATH`] lists their endpoints.
+
+use crate::config::is_http_url;
+use crate::types::ServerCapabilities;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+
+pub const MCP_WELL_KNOWN_PATH: &str = "/.well-known/mcp";
+pub const PROTECTED_RESOURCE_WELL_KNOWN_PATH: &str = "/.well-known/oauth-protected-resource";
+pub const AUTHORIZATION_SERVER_WELL_KNOWN_PATH: &str = "/.well-known/oauth-authorization-server";
+
+/// A problem found while validating a discovery document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiscoveryError {
+    /// A required member is missing or empty.
+    MissingField(&'static str),
+    /// A member that must be an `http(s)` URL is not one.
+    InvalidUrl { field: &'static str, url: String },
+    /// The authorization server does not support PKCE with `S256`, which
+    /// MCP clients are required to use.
+    PkceUnsupported,
+}
+
+impl fmt::Display for DiscoveryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiscoveryError::MissingField(field) => write!(f, "missing {}", field),
+            DiscoveryError::InvalidUrl { field, url } => {
+                write!(f, "{} is not an http(s) URL: {:?}", field, url)
+            }
+            DiscoveryError::PkceUnsupported => {
+                f.write_str("authorization server does not support PKCE with S256")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DiscoveryError {}
+
+fn require_url(field: &'static str, url: &str) -> Result<(), DiscoveryError> {
+    if url.is_empty() {
+        return Err(DiscoveryError::MissingField(field));
+    }
+    if !is_http_url(url) {
+        return Err(DiscoveryError::InvalidUrl {
+            field,
+            url: url.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// A transport a server can be reached over.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransportEndpoint {
+    /// `"streamable-http"` or `"sse"`.
+    #[serde(rename = "type")]
+    pub r#type: String,
+    pub url: String,
+}
+
+/// Describes an MCP server before a client connects to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoveryDocument {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Protocol revisions the server speaks, e.g. `"2025-06-18"`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub protocol_versions: Vec<String>,
+    pub transports: Vec<TransportEndpoint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<ServerCapabilities>,
+    /// URL of the server's protected resource metadata, when it requires
+    /// authorization.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protected_resource_metadata: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub documentation_url: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl DiscoveryDocument {
+    pub fn new(name: impl Into<String>) -> Self {
+        DiscoveryDocument {
+            name: name.into(),
+            version: None,
+            description: None,
+            protocol_versions: Vec::new(),
+            transports: Vec::new(),
+            capabilities: None,
+            protected_resource_metadata: None,
+            documentation_url: None,
+            extra: HashMap::new(),
+        }
+    }
+
+    pub fn transport(mut self, r#type: impl Into<String>, url: impl Into<String>) -> Self {
+        self.transports.push(TransportEndpoint {
+            r#type: r#type.into(),
+            url: url.into(),
+        });
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), DiscoveryError> {
+        if self.name.is_empty() {
+            return Err(DiscoveryError::MissingField("name"));
+        }
+        if self.transports.is_empty() {
+            return Err(DiscoveryError::MissingField("transports"));
+        }
+        for transport in &self.transports {
+            if transport.r#type.is_empty() {
+                return Err(DiscoveryError::MissingField("transports[].type"));
+            }
+            require_url("transports[].url", &transport.url)?;
+        }
+        if let Some(url) = &self.protected_resource_metadata {
+            require_url("protectedResourceMetadata", url)?;
+        }
+        Ok(())
+    }
+}
+
+/// OAuth 2.0 protected resource metadata (RFC 9728). Member names are
+/// snake_case, as in the RFC.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProtectedResourceMetadata {
+    /// The resource identifier: the server's canonical URL.
+    pub resource: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authorization_servers: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes_supported: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bearer_methods_supported: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_documentation: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl ProtectedResourceMetadata {
+    pub fn new(resource: impl Into<String>) -> Self {
+        ProtectedResourceMetadata {
+            resource: resource.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn authorization_server(mut self, issuer: impl Into<String>) -> Self {
+        self.authorization_servers.push(issuer.into());
+        self
+    }
+
+    /// MCP requires at least one authorization server to be listed.
+    pub fn validate(&self) -> Result<(), DiscoveryError> {
+        require_url("resource", &self.resource)?;
+        if self.authorization_servers.is_empty() {
+            return Err(DiscoveryError::MissingField("authorization_servers"));
+        }
+        for issuer in &self.authorization_servers {
+            require_url("authorization_servers[]", issuer)?;
+        }
+        Ok(())
+    }
+}
+
+/// OAuth 2.0 authorization server metadata (RFC 8414).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AuthorizationServerMetadata {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    /// Dynamic client registration endpoint (RFC 7591).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registration_endpoint: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes_supported: Vec<String>,
+    #[serde(default)]
+    pub response_types_supported: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub grant_types_supported: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub code_challenge_methods_supported: Vec<String>,
+
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl AuthorizationServerMetadata {
+    pub fn validate(&self) -> Result<(), DiscoveryError> {
+        require_url("issuer", &self.issuer)?;
+        require_url("authorization_endpoint", &self.authorization_endpoint)?;
+        require_url("token_endpoint", &self.token_endpoint)?;
+        if let Some(url) = &self.registration_endpoint {
+            require_url("registration_endpoint", url)?;
+        }
+        if self.response_types_supported.is_empty() {
+            return Err(DiscoveryError::MissingField("response_types_supported"));
+        }
+        if !self.supports_pkce() {
+            return Err(DiscoveryError::PkceUnsupported);
+        }
+        Ok(())
+    }
+
+    /// Whether PKCE with the `S256` method is advertised.
+    pub fn supports_pkce(&self) -> bool {
+        self.code_challenge_methods_supported
+            .iter()
+            .any(|method| method == "S256")
+    }
+}
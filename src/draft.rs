@@ -0,0 +1,105 @@
+//! Types from the in-progress draft revision of the specification.
+//!
+//! These are separate types rather than changes to the crate root's, so
+//! turning on the `draft` feature never changes the shape of existing
+//! types.
+
+use crate::types::{MessageConversionError, PromptMessage, Role, SamplingContent};
+use serde::{Deserialize, Serialize};
+
+/// A sampling message that carries one or more content blocks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
+pub struct SamplingMessage<C = SamplingContent> {
+    pub role: Role,
+    pub content: OneOrMany<C>,
+}
+
+impl SamplingMessage {
+    /// A message with a single content block.
+    pub fn new(role: Role, content: SamplingContent) -> Self {
+        SamplingMessage {
+            role,
+            content: OneOrMany::One(content),
+        }
+    }
+
+    /// The message's content blocks, whether it carries one or several.
+    pub fn contents(&self) -> &[SamplingContent] {
+        self.content.as_slice()
+    }
+}
+
+impl From<crate::types::SamplingMessage> for SamplingMessage {
+    fn from(message: crate::types::SamplingMessage) -> Self {
+        SamplingMessage {
+            role: message.role,
+            content: OneOrMany::One(message.content),
+        }
+    }
+}
+
+/// Fails if the message carries more than one content block.
+impl TryFrom<SamplingMessage> for crate::types::SamplingMessage {
+    type Error = MessageConversionError;
+
+    fn try_from(message: SamplingMessage) -> Result<Self, Self::Error> {
+        let mut contents = message.content.into_vec();
+        if contents.len() != 1 {
+            return Err(MessageConversionError::MultipleContents(contents.len()));
+        }
+        Ok(crate::types::SamplingMessage::new(
+            message.role,
+            contents.remove(0),
+        ))
+    }
+}
+
+/// Fails if the message carries more than one content block.
+impl TryFrom<SamplingMessage> for PromptMessage {
+    type Error = MessageConversionError;
+
+    fn try_from(message: SamplingMessage) -> Result<Self, Self::Error> {
+        crate::types::SamplingMessage::try_from(message).map(PromptMessage::from)
+    }
+}
+
+/// Either a single value or an array of values. Serializes a single value as
+/// a bare object and several values as an array, and accepts both forms.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T> OneOrMany<T> {
+    /// A uniform slice view of the value(s).
+    pub fn as_slice(&self) -> &[T] {
+        match self {
+            OneOrMany::One(value) => std::slice::from_ref(value),
+            OneOrMany::Many(values) => values,
+        }
+    }
+
+    /// Converts into a vector of the value(s).
+    pub fn into_vec(self) -> Vec<T> {
+        match self {
+            OneOrMany::One(value) => vec![value],
+            OneOrMany::Many(values) => values,
+        }
+    }
+}
+
+impl<T> From<T> for OneOrMany<T> {
+    fn from(value: T) -> Self {
+        OneOrMany::One(value)
+    }
+}
+
+impl<T> From<Vec<T>> for OneOrMany<T> {
+    fn from(values: Vec<T>) -> Self {
+        OneOrMany::Many(values)
+    }
+}
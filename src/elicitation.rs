@@ -0,0 +1,497 @@
+//! Helpers for building `elicitation/create` requests.
+
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+
+/// serde treats the `type` tag of a tagged struct as an unknown field under
+/// `deny_unknown_fields`, so with `strict-fields` the tagged schema structs
+/// derive as `remote = "Self"` and these impls check and strip the tag.
+macro_rules! strict_tagged {
+    ($($ty:ident => $tag:literal),* $(,)?) => {
+        $(
+            #[cfg(feature = "strict-fields")]
+            impl Serialize for $ty {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: serde::Serializer,
+                {
+                    $ty::serialize(self, serializer)
+                }
+            }
+
+            #[cfg(feature = "strict-fields")]
+            impl<'de> Deserialize<'de> for $ty {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    use serde::de::Error;
+
+                    let mut object = serde_json::Map::deserialize(deserializer)?;
+                    match object.remove("type") {
+                        Some(Value::String(tag)) if tag == $tag => {}
+                        _ => {
+                            return Err(D::Error::custom(concat!(
+                                "expected \"type\": \"",
+                                $tag,
+                                "\""
+                            )))
+                        }
+                    }
+                    $ty::deserialize(Value::Object(object)).map_err(D::Error::custom)
+                }
+            }
+        )*
+    };
+}
+
+strict_tagged!(
+    EnumSchema => "string",
+    StringSchema => "string",
+    BooleanSchema => "boolean",
+    RequestedSchema => "object",
+);
+
+/// A string property restricted to a fixed set of values, optionally with a
+/// display name for each value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "string", rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(remote = "Self", deny_unknown_fields))]
+pub struct EnumSchema {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(rename = "enum")]
+    pub values: Vec<String>,
+    /// Display names, parallel to `values`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enum_names: Option<Vec<String>>,
+}
+
+impl EnumSchema {
+    pub fn new<I, S>(values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        EnumSchema {
+            title: None,
+            description: None,
+            values: values.into_iter().map(Into::into).collect(),
+            enum_names: None,
+        }
+    }
+
+    /// Sets the display names, one per value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is not exactly one name per value.
+    pub fn with_names<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let names: Vec<String> = names.into_iter().map(Into::into).collect();
+        assert_eq!(
+            names.len(),
+            self.values.len(),
+            "enum schema needs one display name per value"
+        );
+        self.enum_names = Some(names);
+        self
+    }
+
+    /// The label to show for `value`: its display name, or the value itself
+    /// when there are no names. `None` if `value` is not allowed.
+    pub fn label_for(&self, value: &str) -> Option<&str> {
+        let index = self.values.iter().position(|allowed| allowed == value)?;
+        let names = self.enum_names.as_ref().unwrap_or(&self.values);
+        names
+            .get(index)
+            .or(self.values.get(index))
+            .map(String::as_str)
+    }
+}
+
+/// A free-text string property.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "string", rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(remote = "Self", deny_unknown_fields))]
+pub struct StringSchema {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<StringFormat>,
+}
+
+impl StringSchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_format(mut self, format: StringFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+}
+
+/// The formats a [`StringSchema`] can ask for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StringFormat {
+    Email,
+    Uri,
+    Date,
+    DateTime,
+}
+
+/// Whether a [`NumberSchema`] allows fractions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NumberType {
+    Number,
+    Integer,
+}
+
+/// A numeric property.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
+pub struct NumberSchema {
+    pub r#type: NumberType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<f64>,
+}
+
+impl NumberSchema {
+    /// Any number, fractions included.
+    pub fn number() -> Self {
+        NumberSchema {
+            r#type: NumberType::Number,
+            title: None,
+            description: None,
+            minimum: None,
+            maximum: None,
+        }
+    }
+
+    pub fn integer() -> Self {
+        NumberSchema {
+            r#type: NumberType::Integer,
+            ..Self::number()
+        }
+    }
+}
+
+/// A yes/no property.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "boolean", rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(remote = "Self", deny_unknown_fields))]
+pub struct BooleanSchema {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<bool>,
+}
+
+impl BooleanSchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// The schema of one property of a [`RequestedSchema`]. Elicitation only
+/// allows flat primitives, so nested objects and arrays fail to
+/// deserialize.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum PrimitiveSchemaDefinition {
+    String(StringSchema),
+    Number(NumberSchema),
+    Boolean(BooleanSchema),
+    Enum(EnumSchema),
+}
+
+impl<'de> Deserialize<'de> for PrimitiveSchemaDefinition {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let object = serde_json::Map::deserialize(deserializer)?;
+        let is_enum = object.contains_key("enum");
+        let value =
+            match object.get("type").and_then(Value::as_str) {
+                Some("string") if is_enum => serde_json::from_value(Value::Object(object))
+                    .map(PrimitiveSchemaDefinition::Enum),
+                Some("string") => serde_json::from_value(Value::Object(object))
+                    .map(PrimitiveSchemaDefinition::String),
+                Some("number" | "integer") => serde_json::from_value(Value::Object(object))
+                    .map(PrimitiveSchemaDefinition::Number),
+                Some("boolean") => serde_json::from_value(Value::Object(object))
+                    .map(PrimitiveSchemaDefinition::Boolean),
+                Some(other) => {
+                    return Err(D::Error::custom(format!(
+                        "elicitation properties cannot have type {:?}",
+                        other
+                    )))
+                }
+                None => {
+                    return Err(D::Error::custom(
+                        "elicitation properties need a string \"type\"",
+                    ))
+                }
+            };
+        value.map_err(D::Error::custom)
+    }
+}
+
+impl PrimitiveSchemaDefinition {
+    pub fn title(&self) -> Option<&str> {
+        match self {
+            PrimitiveSchemaDefinition::String(schema) => schema.title.as_deref(),
+            PrimitiveSchemaDefinition::Number(schema) => schema.title.as_deref(),
+            PrimitiveSchemaDefinition::Boolean(schema) => schema.title.as_deref(),
+            PrimitiveSchemaDefinition::Enum(schema) => schema.title.as_deref(),
+        }
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            PrimitiveSchemaDefinition::String(schema) => schema.description.as_deref(),
+            PrimitiveSchemaDefinition::Number(schema) => schema.description.as_deref(),
+            PrimitiveSchemaDefinition::Boolean(schema) => schema.description.as_deref(),
+            PrimitiveSchemaDefinition::Enum(schema) => schema.description.as_deref(),
+        }
+    }
+
+    /// Whether `value` has this schema's type and, for enums, is one of the
+    /// allowed values. Lengths, bounds, and formats are not checked.
+    pub fn accepts(&self, value: &Value) -> bool {
+        match self {
+            PrimitiveSchemaDefinition::String(_) => value.is_string(),
+            PrimitiveSchemaDefinition::Number(schema) => match schema.r#type {
+                NumberType::Number => value.is_number(),
+                NumberType::Integer => value.is_i64() || value.is_u64(),
+            },
+            PrimitiveSchemaDefinition::Boolean(_) => value.is_boolean(),
+            PrimitiveSchemaDefinition::Enum(schema) => value
+                .as_str()
+                .is_some_and(|value| schema.values.iter().any(|allowed| allowed == value)),
+        }
+    }
+}
+
+impl From<StringSchema> for PrimitiveSchemaDefinition {
+    fn from(schema: StringSchema) -> Self {
+        PrimitiveSchemaDefinition::String(schema)
+    }
+}
+
+impl From<NumberSchema> for PrimitiveSchemaDefinition {
+    fn from(schema: NumberSchema) -> Self {
+        PrimitiveSchemaDefinition::Number(schema)
+    }
+}
+
+impl From<BooleanSchema> for PrimitiveSchemaDefinition {
+    fn from(schema: BooleanSchema) -> Self {
+        PrimitiveSchemaDefinition::Boolean(schema)
+    }
+}
+
+impl From<EnumSchema> for PrimitiveSchemaDefinition {
+    fn from(schema: EnumSchema) -> Self {
+        PrimitiveSchemaDefinition::Enum(schema)
+    }
+}
+
+/// The `requestedSchema` of an elicitation: an object whose properties are
+/// all primitives.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "object")]
+#[cfg_attr(feature = "strict-fields", serde(remote = "Self", deny_unknown_fields))]
+pub struct RequestedSchema {
+    pub properties: HashMap<String, PrimitiveSchemaDefinition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<Vec<String>>,
+}
+
+/// Builds the [`RequestedSchema`] of an elicitation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ElicitationSchema {
+    properties: HashMap<String, PrimitiveSchemaDefinition>,
+    required: Vec<String>,
+}
+
+impl ElicitationSchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn property(
+        mut self,
+        name: impl Into<String>,
+        schema: impl Into<PrimitiveSchemaDefinition>,
+    ) -> Self {
+        self.properties.insert(name.into(), schema.into());
+        self
+    }
+
+    /// Adds an enum property whose values are presented by display name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is not exactly one name per value.
+    pub fn enum_field<V, N, S, T>(self, name: impl Into<String>, values: V, names: N) -> Self
+    where
+        V: IntoIterator<Item = S>,
+        S: Into<String>,
+        N: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.property(name, EnumSchema::new(values).with_names(names))
+    }
+
+    /// Marks a property as required.
+    pub fn required(mut self, name: impl Into<String>) -> Self {
+        self.required.push(name.into());
+        self
+    }
+
+    pub fn build(self) -> RequestedSchema {
+        RequestedSchema {
+            properties: self.properties,
+            required: (!self.required.is_empty()).then_some(self.required),
+        }
+    }
+
+    /// Request parameters asking the user `message` for data of this shape.
+    pub fn into_params(self, message: impl Into<String>) -> ElicitationCreateParams {
+        ElicitationCreateParams {
+            message: message.into(),
+            requested_schema: self.build(),
+            extra: HashMap::new(),
+        }
+    }
+}
+
+impl ElicitationCreateResult {
+    /// The user submitted `content`.
+    pub fn accepted(content: Value) -> Self {
+        ElicitationCreateResult::new(ElicitationCreatePayload {
+            action: ElicitationAction::Accept,
+            content: Some(content),
+        })
+    }
+
+    /// The user explicitly declined to provide the data.
+    pub fn declined() -> Self {
+        ElicitationCreateResult::new(ElicitationCreatePayload {
+            action: ElicitationAction::Decline,
+            content: None,
+        })
+    }
+
+    /// The user dismissed the request without choosing.
+    pub fn cancelled() -> Self {
+        ElicitationCreateResult::new(ElicitationCreatePayload {
+            action: ElicitationAction::Cancel,
+            content: None,
+        })
+    }
+}
+
+/// An elicitation result that breaks the protocol's invariants or does not
+/// match the requested schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ElicitationError {
+    /// Content was sent with an action other than accept.
+    UnexpectedContent(ElicitationAction),
+    /// The user accepted but no content was sent.
+    MissingContent,
+    /// The content is not a JSON object.
+    NotAnObject,
+    /// A required property is missing.
+    MissingProperty(String),
+    /// A property's value does not match its schema.
+    InvalidProperty(String),
+}
+
+impl fmt::Display for ElicitationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ElicitationError::UnexpectedContent(action) => {
+                write!(f, "content sent with {:?} action", action)
+            }
+            ElicitationError::MissingContent => f.write_str("accepted without content"),
+            ElicitationError::NotAnObject => f.write_str("content is not an object"),
+            ElicitationError::MissingProperty(name) => {
+                write!(f, "required property {:?} is missing", name)
+            }
+            ElicitationError::InvalidProperty(name) => {
+                write!(f, "property {:?} does not match its schema", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ElicitationError {}
+
+impl ElicitationCreateResult {
+    /// Checks that content is present exactly when the user accepted.
+    pub fn validate(&self) -> Result<(), ElicitationError> {
+        match (self.action, &self.content) {
+            (ElicitationAction::Accept, None) => Err(ElicitationError::MissingContent),
+            (ElicitationAction::Accept, Some(_)) | (_, None) => Ok(()),
+            (action, Some(_)) => Err(ElicitationError::UnexpectedContent(action)),
+        }
+    }
+
+    /// Like [`validate`](Self::validate), and also checks accepted content
+    /// against the `requestedSchema` it answers: required properties must
+    /// be present, and each property must have its schema's primitive
+    /// `type` and, for enums, one of the allowed values (see
+    /// [`PrimitiveSchemaDefinition::accepts`]). Properties the schema does
+    /// not describe are not checked.
+    pub fn validate_against(&self, schema: &RequestedSchema) -> Result<(), ElicitationError> {
+        self.validate()?;
+        let Some(content) = &self.content else {
+            return Ok(());
+        };
+        let content = content.as_object().ok_or(ElicitationError::NotAnObject)?;
+        for name in schema.required.iter().flatten() {
+            if !content.contains_key(name) {
+                return Err(ElicitationError::MissingProperty(name.clone()));
+            }
+        }
+        for (name, property) in &schema.properties {
+            if let Some(value) = content.get(name) {
+                if !property.accepts(value) {
+                    return Err(ElicitationError::InvalidProperty(name.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,478 @@
+//! Error helpers for building MCP error results and JSON-RPC errors.
+
+use crate::types::*;
+use crate::version::ProtocolVersion;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::time::Duration;
+
+/// Error code for requests refused by rate limiting, from the range the
+/// JSON-RPC spec leaves to implementations (`-32000` to `-32099`).
+pub const RATE_LIMITED: i32 = -32029;
+
+/// Error code MCP uses for a `resources/read` of an unknown URI.
+pub const RESOURCE_NOT_FOUND: i32 = -32002;
+
+/// A JSON-RPC error code: the codes JSON-RPC reserves, those MCP defines,
+/// and [`RATE_LIMITED`]; any other code is kept as `Other`.
+///
+/// Codes compare by their numeric value, so `Other(-32601)` equals
+/// `MethodNotFound`.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum ErrorCode {
+    ParseError,
+    InvalidRequest,
+    MethodNotFound,
+    InvalidParams,
+    InternalError,
+    ResourceNotFound,
+    RateLimited,
+    Other(i32),
+}
+
+impl ErrorCode {
+    /// Every named code, in declaration order.
+    pub const ALL: &'static [ErrorCode] = &[
+        ErrorCode::ParseError,
+        ErrorCode::InvalidRequest,
+        ErrorCode::MethodNotFound,
+        ErrorCode::InvalidParams,
+        ErrorCode::InternalError,
+        ErrorCode::ResourceNotFound,
+        ErrorCode::RateLimited,
+    ];
+
+    pub fn code(self) -> i32 {
+        match self {
+            ErrorCode::ParseError => PARSE_ERROR,
+            ErrorCode::InvalidRequest => INVALID_REQUEST,
+            ErrorCode::MethodNotFound => METHOD_NOT_FOUND,
+            ErrorCode::InvalidParams => INVALID_PARAMS,
+            ErrorCode::InternalError => INTERNAL_ERROR,
+            ErrorCode::ResourceNotFound => RESOURCE_NOT_FOUND,
+            ErrorCode::RateLimited => RATE_LIMITED,
+            ErrorCode::Other(code) => code,
+        }
+    }
+
+    /// The conventional message for the code, e.g. `"Method not found"`.
+    pub fn message(self) -> &'static str {
+        match self {
+            ErrorCode::ParseError => "Parse error",
+            ErrorCode::InvalidRequest => "Invalid request",
+            ErrorCode::MethodNotFound => "Method not found",
+            ErrorCode::InvalidParams => "Invalid params",
+            ErrorCode::InternalError => "Internal error",
+            ErrorCode::ResourceNotFound => "Resource not found",
+            ErrorCode::RateLimited => "Rate limited",
+            ErrorCode::Other(_) => "Error",
+        }
+    }
+}
+
+impl PartialEq for ErrorCode {
+    fn eq(&self, other: &Self) -> bool {
+        self.code() == other.code()
+    }
+}
+
+impl Eq for ErrorCode {}
+
+impl std::hash::Hash for ErrorCode {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.code().hash(state);
+    }
+}
+
+impl From<ErrorCode> for i32 {
+    fn from(code: ErrorCode) -> Self {
+        code.code()
+    }
+}
+
+impl From<i32> for ErrorCode {
+    fn from(code: i32) -> Self {
+        ErrorCode::ALL
+            .iter()
+            .copied()
+            .find(|known| known.code() == code)
+            .unwrap_or(ErrorCode::Other(code))
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.message(), self.code())
+    }
+}
+
+/// A failure while executing a tool.
+///
+/// MCP distinguishes between two ways of reporting such failures:
+///
+/// - **Tool-level errors** are reported inside a successful `tools/call`
+///   response as a [`CallToolResult`] with `isError: true`, so the model can
+///   see the problem and possibly recover. Use `CallToolResult::from(err)`.
+/// - **Protocol-level errors** are reported as a JSON-RPC error response,
+///   for problems the model cannot act upon. Use
+///   [`ToolError::into_jsonrpc_error`] or `RPCErrorDetail::from(err)`.
+#[derive(Debug, Clone)]
+pub enum ToolError {
+    /// The arguments did not match the tool's input schema.
+    InvalidArguments { message: String, data: Option<Value> },
+    /// The tool ran but failed.
+    ExecutionFailed { message: String, data: Option<Value> },
+    /// The caller is not allowed to use the tool.
+    Unauthorized { message: String, data: Option<Value> },
+    /// The tool did not finish in time.
+    Timeout { message: String, data: Option<Value> },
+    /// Any other failure, with the JSON-RPC error code to use on the
+    /// protocol path.
+    Custom {
+        code: i32,
+        message: String,
+        data: Option<Value>,
+    },
+}
+
+impl ToolError {
+    pub fn invalid_arguments(message: impl Into<String>) -> Self {
+        ToolError::InvalidArguments {
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    pub fn execution_failed(message: impl Into<String>) -> Self {
+        ToolError::ExecutionFailed {
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        ToolError::Unauthorized {
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    pub fn timeout(message: impl Into<String>) -> Self {
+        ToolError::Timeout {
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    pub fn custom(code: i32, message: impl Into<String>) -> Self {
+        ToolError::Custom {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    /// Attaches structured data describing the failure.
+    pub fn with_data(mut self, value: Value) -> Self {
+        match &mut self {
+            ToolError::InvalidArguments { data, .. }
+            | ToolError::ExecutionFailed { data, .. }
+            | ToolError::Unauthorized { data, .. }
+            | ToolError::Timeout { data, .. }
+            | ToolError::Custom { data, .. } => *data = Some(value),
+        }
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            ToolError::InvalidArguments { message, .. }
+            | ToolError::ExecutionFailed { message, .. }
+            | ToolError::Unauthorized { message, .. }
+            | ToolError::Timeout { message, .. }
+            | ToolError::Custom { message, .. } => message,
+        }
+    }
+
+    pub fn data(&self) -> Option<&Value> {
+        match self {
+            ToolError::InvalidArguments { data, .. }
+            | ToolError::ExecutionFailed { data, .. }
+            | ToolError::Unauthorized { data, .. }
+            | ToolError::Timeout { data, .. }
+            | ToolError::Custom { data, .. } => data.as_ref(),
+        }
+    }
+
+    /// The JSON-RPC error code used on the protocol-level path.
+    pub fn code(&self) -> i32 {
+        match self {
+            ToolError::InvalidArguments { .. } => INVALID_PARAMS,
+            ToolError::Custom { code, .. } => *code,
+            _ => INTERNAL_ERROR,
+        }
+    }
+
+    /// Converts into a protocol-level JSON-RPC error response for `id`.
+    pub fn into_jsonrpc_error(self, id: RequestId) -> JSONRPCError {
+        JSONRPCError::new(id, self.into())
+    }
+}
+
+impl fmt::Display for ToolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            ToolError::InvalidArguments { .. } => "invalid arguments",
+            ToolError::ExecutionFailed { .. } => "execution failed",
+            ToolError::Unauthorized { .. } => "unauthorized",
+            ToolError::Timeout { .. } => "timed out",
+            ToolError::Custom { .. } => return f.write_str(self.message()),
+        };
+        write!(f, "{}: {}", kind, self.message())
+    }
+}
+
+impl std::error::Error for ToolError {}
+
+/// Tool-level error: an `isError` result with the error as text and any
+/// data as a second text block of JSON.
+///
+/// The data is kept out of `structuredContent`, which clients validate
+/// against the tool's `outputSchema` even on errors.
+impl From<ToolError> for CallToolResult {
+    fn from(err: ToolError) -> Self {
+        let mut result = CallToolResult::error(err.to_string());
+        if let Some(data) = err.data() {
+            result
+                .content
+                .push(PromptContent::Text(TextContent::new(data.to_string())));
+        }
+        result
+    }
+}
+
+/// Protocol-level error details.
+impl From<ToolError> for RPCErrorDetail {
+    fn from(err: ToolError) -> Self {
+        RPCErrorDetail {
+            code: err.code(),
+            message: err.to_string(),
+            data: err.data().cloned(),
+        }
+    }
+}
+
+impl RPCErrorDetail {
+    /// An error with `code` and `message` and no `data`.
+    pub fn new(code: impl Into<i32>, message: impl Into<String>) -> Self {
+        RPCErrorDetail {
+            code: code.into(),
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    /// The code as an [`ErrorCode`].
+    pub fn error_code(&self) -> ErrorCode {
+        ErrorCode::from(self.code)
+    }
+
+    /// Sets `data` to `data` serialized, e.g. one of the payloads such as
+    /// [`ResourceNotFoundData`], failing if it does not serialize to JSON.
+    pub fn with_data<T: Serialize>(mut self, data: T) -> Result<Self, serde_json::Error> {
+        self.data = Some(serde_json::to_value(data)?);
+        Ok(self)
+    }
+
+    /// [`with_data`](Self::with_data) for the payloads of this module, which
+    /// hold only strings and integers and so always serialize.
+    fn with_builtin_data<T: Serialize>(self, data: T) -> Self {
+        self.with_data(data)
+            .expect("built-in error payloads serialize")
+    }
+
+    /// `data` as a `T`, or `None` if it is absent or has another shape.
+    pub fn data_as<T: DeserializeOwned>(&self) -> Option<T> {
+        T::deserialize(self.data.as_ref()?).ok()
+    }
+
+    /// A parse error (`-32700`): the message was not valid JSON.
+    pub fn parse_error(message: impl Into<String>) -> Self {
+        RPCErrorDetail::new(ErrorCode::ParseError, message)
+    }
+
+    /// An invalid-request error (`-32600`): the JSON is not a valid request.
+    pub fn invalid_request(message: impl Into<String>) -> Self {
+        RPCErrorDetail::new(ErrorCode::InvalidRequest, message)
+    }
+
+    /// A method-not-found error (`-32601`) for `method`.
+    pub fn method_not_found(method: &str) -> Self {
+        RPCErrorDetail::new(
+            ErrorCode::MethodNotFound,
+            format!("method not found: {}", method),
+        )
+    }
+
+    /// An invalid-params error (`-32602`).
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        RPCErrorDetail::new(ErrorCode::InvalidParams, message)
+    }
+
+    /// An internal error (`-32603`).
+    pub fn internal_error(message: impl Into<String>) -> Self {
+        RPCErrorDetail::new(ErrorCode::InternalError, message)
+    }
+
+    /// A resource-not-found error (`-32002`) for `uri`, with the URI in
+    /// `data` as the spec suggests.
+    pub fn resource_not_found(uri: &str) -> Self {
+        RPCErrorDetail::new(ErrorCode::ResourceNotFound, "Resource not found").with_builtin_data(
+            ResourceNotFoundData {
+                uri: uri.to_string(),
+            },
+        )
+    }
+
+    /// The invalid-params error (`-32602`) the spec prescribes when a
+    /// client asks for the protocol revision `requested`, listing the
+    /// revisions this crate supports.
+    pub fn unsupported_protocol_version(requested: impl Into<String>) -> Self {
+        RPCErrorDetail::new(ErrorCode::InvalidParams, "Unsupported protocol version")
+            .with_builtin_data(UnsupportedProtocolVersionData {
+                supported: ProtocolVersion::ALL
+                    .iter()
+                    .map(|version| version.as_str().to_string())
+                    .collect(),
+                requested: requested.into(),
+            })
+    }
+
+    /// An internal error (`-32603`) describing `err`. The message is the
+    /// error's own message; the full chain of sources, outermost first, is
+    /// captured as `{"chain": [...]}` in `data`.
+    pub fn internal_from(err: &dyn std::error::Error) -> Self {
+        let mut chain = Vec::new();
+        let mut current = Some(err);
+        while let Some(err) = current {
+            chain.push(err.to_string());
+            current = err.source();
+        }
+        RPCErrorDetail::internal_error(err.to_string())
+            .with_builtin_data(InternalErrorData { chain })
+    }
+}
+
+/// The `data` of a [`RESOURCE_NOT_FOUND`] error.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceNotFoundData {
+    pub uri: String,
+}
+
+/// The `data` of an `initialize` error for an unsupported protocol
+/// revision.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnsupportedProtocolVersionData {
+    pub supported: Vec<String>,
+    pub requested: String,
+}
+
+/// The `data` of an error built by [`RPCErrorDetail::internal_from`]: the
+/// messages of the error and its sources, outermost first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InternalErrorData {
+    pub chain: Vec<String>,
+}
+
+/// The `data` of a [`RATE_LIMITED`] error, telling the client when to retry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitedData {
+    pub retry_after_ms: u64,
+    /// Requests allowed per window.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+    /// Requests left in the current window.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining: Option<u64>,
+}
+
+impl RateLimitedData {
+    pub fn new(retry_after: Duration) -> Self {
+        RateLimitedData {
+            retry_after_ms: u64::try_from(retry_after.as_millis()).unwrap_or(u64::MAX),
+            limit: None,
+            remaining: None,
+        }
+    }
+
+    pub fn with_limit(mut self, limit: u64, remaining: u64) -> Self {
+        self.limit = Some(limit);
+        self.remaining = Some(remaining);
+        self
+    }
+
+    pub fn retry_after(&self) -> Duration {
+        Duration::from_millis(self.retry_after_ms)
+    }
+}
+
+impl From<RateLimitedData> for RPCErrorDetail {
+    fn from(data: RateLimitedData) -> Self {
+        RPCErrorDetail::new(
+            ErrorCode::RateLimited,
+            format!("rate limited; retry after {} ms", data.retry_after_ms),
+        )
+        .with_builtin_data(data)
+    }
+}
+
+impl RPCErrorDetail {
+    /// The rate limit details of a [`RATE_LIMITED`] error, if its `data`
+    /// has the expected shape.
+    pub fn rate_limited_data(&self) -> Option<RateLimitedData> {
+        if self.code != RATE_LIMITED {
+            return None;
+        }
+        self.data_as()
+    }
+}
+
+impl JSONRPCError {
+    /// An error response to the request `id`.
+    pub fn new(id: impl Into<RequestId>, error: RPCErrorDetail) -> Self {
+        JSONRPCError {
+            json_rpc: JsonRpcVersion,
+            id: Some(id.into()),
+            error,
+        }
+    }
+
+    /// An error response with `"id": null`, for requests whose id could not
+    /// be read, such as a parse error.
+    pub fn without_id(error: RPCErrorDetail) -> Self {
+        JSONRPCError {
+            json_rpc: JsonRpcVersion,
+            id: None,
+            error,
+        }
+    }
+
+    /// A [`RATE_LIMITED`] error response for `id`, asking the client to
+    /// wait `retry_after` before retrying.
+    pub fn rate_limited(id: impl Into<RequestId>, retry_after: Duration) -> Self {
+        JSONRPCError::new(id, RateLimitedData::new(retry_after).into())
+    }
+}
+
+#[cfg(feature = "anyhow")]
+impl JSONRPCError {
+    /// An internal error response for `id`, capturing the error chain of
+    /// `err` as in [`RPCErrorDetail::internal_from`].
+    pub fn from_anyhow(id: impl Into<RequestId>, err: &anyhow::Error) -> Self {
+        JSONRPCError::new(id, RPCErrorDetail::internal_from(&**err))
+    }
+}
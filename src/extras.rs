@@ -0,0 +1,175 @@
+//! Uniform access to the `_meta` object and passthrough fields of MCP types.
+//!
+//! Types declared with [`mcp_extras`](crate::mcp_extras) implement these
+//! traits automatically; [`Extras`] is also implemented by every other type
+//! in this crate that keeps unknown fields.
+
+use crate::config::*;
+use crate::discovery::*;
+use crate::types::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// A type carrying an MCP `_meta` object.
+pub trait HasMeta {
+    fn meta(&self) -> Option<&HashMap<String, Value>>;
+
+    /// The `_meta` object, created empty if absent.
+    fn meta_mut(&mut self) -> &mut HashMap<String, Value>;
+
+    /// The `_meta` entry `key` as a `T`, or `None` if it is absent or does
+    /// not have the shape of a `T`.
+    fn get_meta_key<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        T::deserialize(self.meta()?.get(key)?).ok()
+    }
+
+    /// Sets the `_meta` entry `key`, replacing any previous value.
+    fn set_meta_key(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Serialize,
+    ) -> Result<(), serde_json::Error> {
+        let value = serde_json::to_value(value)?;
+        self.meta_mut().insert(key.into(), value);
+        Ok(())
+    }
+}
+
+/// A type keeping fields it does not model in a flattened `extra` map,
+/// such as vendor extensions.
+pub trait Extras {
+    fn extra(&self) -> &HashMap<String, Value>;
+
+    fn extra_mut(&mut self) -> &mut HashMap<String, Value>;
+
+    /// The passthrough field `key` as a `T`, or `None` if it is absent or
+    /// does not have the shape of a `T`.
+    fn get_extra<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        T::deserialize(self.extra().get(key)?).ok()
+    }
+
+    /// Sets the passthrough field `key`, replacing any previous value.
+    fn set_extra(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Serialize,
+    ) -> Result<(), serde_json::Error> {
+        let value = serde_json::to_value(value)?;
+        self.extra_mut().insert(key.into(), value);
+        Ok(())
+    }
+
+    fn remove_extra(&mut self, key: &str) -> Option<Value> {
+        self.extra_mut().remove(key)
+    }
+
+    /// The names of the passthrough fields, sorted.
+    fn extra_keys(&self) -> Vec<&str> {
+        let mut keys: Vec<&str> = self.extra().keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        keys
+    }
+}
+
+/// Deserializes a flattened `extra` map. With the `strict-fields` feature
+/// any field left over for it is an error, so that misspelled members such
+/// as `"protocolversion"` are caught instead of kept as passthrough fields.
+/// Used by [`mcp_extras`](crate::mcp_extras); not part of the public API.
+#[doc(hidden)]
+pub fn deserialize_extra<'de, D>(deserializer: D) -> Result<HashMap<String, Value>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let extra = HashMap::<String, Value>::deserialize(deserializer)?;
+    #[cfg(feature = "strict-fields")]
+    if let Some(field) = extra.keys().min() {
+        return Err(serde::de::Error::custom(format_args!(
+            "unknown field `{}`",
+            field
+        )));
+    }
+    Ok(extra)
+}
+
+macro_rules! impl_extras {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Extras for $ty {
+                fn extra(&self) -> &HashMap<String, Value> {
+                    &self.extra
+                }
+
+                fn extra_mut(&mut self) -> &mut HashMap<String, Value> {
+                    &mut self.extra
+                }
+            }
+        )*
+    };
+}
+
+impl_extras!(
+    RequestMeta,
+    SamplingCapability,
+    ElicitationCapability,
+    McpConfig,
+    StdioServerConfig,
+    RemoteServerConfig,
+    DiscoveryDocument,
+    ProtectedResourceMetadata,
+    AuthorizationServerMetadata,
+);
+
+#[cfg(feature = "provider-metadata")]
+impl_extras!(
+    crate::provider_meta::AnthropicSamplingMeta,
+    crate::provider_meta::OpenAiSamplingMeta,
+);
+
+impl<T> HasMeta for WithMeta<T> {
+    fn meta(&self) -> Option<&HashMap<String, Value>> {
+        self.meta.as_ref()
+    }
+
+    fn meta_mut(&mut self) -> &mut HashMap<String, Value> {
+        self.meta.get_or_insert_with(HashMap::new)
+    }
+}
+
+impl HasMeta for ResourceContents {
+    fn meta(&self) -> Option<&HashMap<String, Value>> {
+        match self {
+            ResourceContents::Text(text) => text.meta.as_ref(),
+            ResourceContents::Blob(blob) => blob.meta.as_ref(),
+        }
+    }
+
+    fn meta_mut(&mut self) -> &mut HashMap<String, Value> {
+        let meta = match self {
+            ResourceContents::Text(text) => &mut text.meta,
+            ResourceContents::Blob(blob) => &mut blob.meta,
+        };
+        meta.get_or_insert_with(HashMap::new)
+    }
+}
+
+impl<T> Extras for WithMeta<T> {
+    fn extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut HashMap<String, Value> {
+        &mut self.extra
+    }
+}
+
+impl<T> Extras for Paginated<T> {
+    fn extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
+
+    fn extra_mut(&mut self) -> &mut HashMap<String, Value> {
+        &mut self.extra
+    }
+}
@@ -0,0 +1,157 @@
+//! Incremental parsing of JSON messages from a byte stream.
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::marker::PhantomData;
+
+/// The default limit on the size of a single message, in bytes.
+pub const DEFAULT_MAX_FRAME_LEN: usize = 4 * 1024 * 1024;
+
+/// A push-based parser that splits a byte stream into JSON messages.
+///
+/// Unlike line-delimited framing, message boundaries are found by tracking
+/// JSON nesting, so a message may be split across several reads and one read
+/// may carry several concatenated messages (with or without whitespace in
+/// between). Each top-level value must be an object or an array (a batch).
+///
+/// `T` is the type each message is decoded into; it defaults to
+/// [`serde_json::Value`].
+///
+/// A message longer than the frame limit ([`DEFAULT_MAX_FRAME_LEN`] unless
+/// set with [`FrameParser::with_max_frame_len`]) yields an error and is
+/// skipped without being buffered.
+#[derive(Debug)]
+pub struct FrameParser<T = Value> {
+    buffer: Vec<u8>,
+    /// Start of the frame currently being scanned.
+    start: usize,
+    /// Scan position inside the buffer.
+    pos: usize,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    max_frame_len: usize,
+    /// Set while skipping the rest of a message over the frame limit.
+    oversized: bool,
+    _message: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for FrameParser<T> {
+    fn default() -> Self {
+        FrameParser {
+            buffer: Vec::new(),
+            start: 0,
+            pos: 0,
+            depth: 0,
+            in_string: false,
+            escaped: false,
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+            oversized: false,
+            _message: PhantomData,
+        }
+    }
+}
+
+impl<T: DeserializeOwned> FrameParser<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the largest message, in bytes, the parser will buffer.
+    pub fn with_max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.max_frame_len = max_frame_len;
+        self
+    }
+
+    /// Feeds bytes read from the transport and returns every message they
+    /// complete, in order. A complete frame that fails to decode yields an
+    /// error in its place; stray bytes between frames are reported the same
+    /// way and skipped, as is a message over the frame limit.
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<Result<T, serde_json::Error>> {
+        self.buffer.extend_from_slice(bytes);
+        let mut messages = Vec::new();
+
+        while self.pos < self.buffer.len() {
+            let byte = self.buffer[self.pos];
+            if self.depth == 0 {
+                match byte {
+                    b' ' | b'\t' | b'\r' | b'\n' => {
+                        self.pos += 1;
+                        self.start = self.pos;
+                        continue;
+                    }
+                    b'{' | b'[' => {}
+                    _ => {
+                        let end = self.buffer[self.pos..]
+                            .iter()
+                            .position(|b| matches!(b, b'{' | b'['))
+                            .map_or(self.buffer.len(), |offset| self.pos + offset);
+                        let garbage = &self.buffer[self.start..end];
+                        messages.push(Err(serde_json::from_slice::<T>(garbage)
+                            .err()
+                            .unwrap_or_else(|| {
+                                serde::de::Error::custom("unexpected bytes between messages")
+                            })));
+                        self.pos = end;
+                        self.start = end;
+                        continue;
+                    }
+                }
+            }
+
+            self.pos += 1;
+            if self.depth > 0 && !self.oversized && self.pos - self.start > self.max_frame_len {
+                messages.push(Err(serde::de::Error::custom(format!(
+                    "message exceeds the {} byte frame limit",
+                    self.max_frame_len
+                ))));
+                self.oversized = true;
+            }
+            if self.in_string {
+                match byte {
+                    _ if self.escaped => self.escaped = false,
+                    b'\\' => self.escaped = true,
+                    b'"' => self.in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match byte {
+                b'"' => self.in_string = true,
+                b'{' | b'[' => self.depth += 1,
+                b'}' | b']' => {
+                    self.depth -= 1;
+                    if self.depth == 0 {
+                        if self.oversized {
+                            self.oversized = false;
+                        } else {
+                            messages
+                                .push(serde_json::from_slice(&self.buffer[self.start..self.pos]));
+                        }
+                        self.start = self.pos;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if self.oversized {
+            self.start = self.pos;
+        }
+        self.compact();
+        messages
+    }
+
+    /// The number of buffered bytes not yet part of a complete message.
+    pub fn pending(&self) -> usize {
+        self.buffer.len() - self.start
+    }
+
+    fn compact(&mut self) {
+        if self.start > 0 {
+            self.buffer.drain(..self.start);
+            self.pos -= self.start;
+            self.start = 0;
+        }
+    }
+}
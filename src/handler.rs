@@ -0,0 +1,233 @@
+//! Transport-agnostic request handling.
+
+use crate::types::*;
+
+/// Handles the requests a client sends to a server.
+///
+/// Every method defaults to a method-not-found error except `ping`, which
+/// answers with an empty result, so a server implements only what it
+/// advertises in its capabilities. Use [`dispatch_client_request`] to route
+/// a decoded [`ClientRequest`] to these methods.
+pub trait McpServerHandler {
+    fn ping(&self, params: PingParams) -> Result<EmptyResult, RPCErrorDetail> {
+        let _ = params;
+        Ok(EmptyResult::default())
+    }
+
+    fn initialize(&self, params: InitializeParams) -> Result<InitializeResult, RPCErrorDetail> {
+        let _ = params;
+        Err(RPCErrorDetail::method_not_found("initialize"))
+    }
+
+    fn complete(&self, params: CompleteParams) -> Result<CompleteResult, RPCErrorDetail> {
+        let _ = params;
+        Err(RPCErrorDetail::method_not_found("completion/complete"))
+    }
+
+    fn set_level(&self, params: SetLevelParams) -> Result<EmptyResult, RPCErrorDetail> {
+        let _ = params;
+        Err(RPCErrorDetail::method_not_found("logging/setLevel"))
+    }
+
+    fn get_prompt(&self, params: GetPromptParams) -> Result<GetPromptResult, RPCErrorDetail> {
+        let _ = params;
+        Err(RPCErrorDetail::method_not_found("prompts/get"))
+    }
+
+    fn list_prompts(&self, params: ListPromptsParams) -> Result<ListPromptsResult, RPCErrorDetail> {
+        let _ = params;
+        Err(RPCErrorDetail::method_not_found("prompts/list"))
+    }
+
+    fn list_resources(
+        &self,
+        params: ListResourcesParams,
+    ) -> Result<ListResourcesResult, RPCErrorDetail> {
+        let _ = params;
+        Err(RPCErrorDetail::method_not_found("resources/list"))
+    }
+
+    fn list_resource_templates(
+        &self,
+        params: ListResourceTemplatesParams,
+    ) -> Result<ListResourceTemplatesResult, RPCErrorDetail> {
+        let _ = params;
+        Err(RPCErrorDetail::method_not_found("resources/templates/list"))
+    }
+
+    fn read_resource(
+        &self,
+        params: ReadResourceParams,
+    ) -> Result<ReadResourceResult, RPCErrorDetail> {
+        let _ = params;
+        Err(RPCErrorDetail::method_not_found("resources/read"))
+    }
+
+    fn subscribe(&self, params: SubscribeParams) -> Result<EmptyResult, RPCErrorDetail> {
+        let _ = params;
+        Err(RPCErrorDetail::method_not_found("resources/subscribe"))
+    }
+
+    fn unsubscribe(&self, params: UnsubscribeParams) -> Result<EmptyResult, RPCErrorDetail> {
+        let _ = params;
+        Err(RPCErrorDetail::method_not_found("resources/unsubscribe"))
+    }
+
+    fn call_tool(&self, params: CallToolParams) -> Result<CallToolResult, RPCErrorDetail> {
+        let _ = params;
+        Err(RPCErrorDetail::method_not_found("tools/call"))
+    }
+
+    fn list_tools(&self, params: ListToolsParams) -> Result<ListToolsResult, RPCErrorDetail> {
+        let _ = params;
+        Err(RPCErrorDetail::method_not_found("tools/list"))
+    }
+
+    /// Handles the deprecated `ClientRequest::ElicitationCreate`; clients
+    /// answer `elicitation/create` through [`McpClientHandler::elicit`].
+    fn create_elicitation(
+        &self,
+        params: ElicitationCreateParams,
+    ) -> Result<ElicitationCreateResult, RPCErrorDetail> {
+        let _ = params;
+        Err(RPCErrorDetail::method_not_found("elicitation/create"))
+    }
+}
+
+/// Routes `request` to the matching [`McpServerHandler`] method and wraps
+/// the outcome in a JSON-RPC response or error carrying the request's id.
+pub fn dispatch_client_request<H: McpServerHandler + ?Sized>(
+    handler: &H,
+    request: ClientRequest,
+) -> Result<JSONRPCResponse<ServerResult>, JSONRPCError> {
+    let (id, result) = match request {
+        ClientRequest::Ping { id, params, .. } => {
+            (id, handler.ping(params).map(ServerResult::Empty))
+        }
+        ClientRequest::Initialize { id, params, .. } => {
+            (id, handler.initialize(params).map(ServerResult::Initialize))
+        }
+        ClientRequest::Complete { id, params, .. } => {
+            (id, handler.complete(params).map(ServerResult::Complete))
+        }
+        ClientRequest::SetLevel { id, params, .. } => {
+            (id, handler.set_level(params).map(ServerResult::Empty))
+        }
+        ClientRequest::GetPrompt { id, params, .. } => {
+            (id, handler.get_prompt(params).map(ServerResult::GetPrompt))
+        }
+        ClientRequest::ListPrompts { id, params, .. } => (
+            id,
+            handler.list_prompts(params).map(ServerResult::ListPrompts),
+        ),
+        ClientRequest::ListResources { id, params, .. } => (
+            id,
+            handler
+                .list_resources(params)
+                .map(ServerResult::ListResources),
+        ),
+        ClientRequest::ListResourceTemplates { id, params, .. } => (
+            id,
+            handler
+                .list_resource_templates(params)
+                .map(ServerResult::ListResourceTemplates),
+        ),
+        ClientRequest::ReadResource { id, params, .. } => (
+            id,
+            handler
+                .read_resource(params)
+                .map(ServerResult::ReadResource),
+        ),
+        ClientRequest::Subscribe { id, params, .. } => {
+            (id, handler.subscribe(params).map(ServerResult::Empty))
+        }
+        ClientRequest::Unsubscribe { id, params, .. } => {
+            (id, handler.unsubscribe(params).map(ServerResult::Empty))
+        }
+        ClientRequest::CallTool { id, params, .. } => {
+            (id, handler.call_tool(params).map(ServerResult::CallTool))
+        }
+        ClientRequest::ListTools { id, params, .. } => {
+            (id, handler.list_tools(params).map(ServerResult::ListTools))
+        }
+        #[allow(deprecated)]
+        ClientRequest::ElicitationCreate { id, params, .. } => (
+            id,
+            handler
+                .create_elicitation(params)
+                .map(ServerResult::ElicitationCreate),
+        ),
+    };
+    respond(id, result)
+}
+
+/// Handles the requests a server sends to a client.
+///
+/// As with [`McpServerHandler`], everything except `ping` defaults to a
+/// method-not-found error, so a client host implements only the
+/// capabilities it advertises. Use [`dispatch_server_request`] to route a
+/// decoded [`ServerRequest`] to these methods.
+pub trait McpClientHandler {
+    fn ping(&self, params: PingParams) -> Result<EmptyResult, RPCErrorDetail> {
+        let _ = params;
+        Ok(EmptyResult::default())
+    }
+
+    fn create_message(
+        &self,
+        params: CreateMessageParams,
+    ) -> Result<CreateMessageResult, RPCErrorDetail> {
+        let _ = params;
+        Err(RPCErrorDetail::method_not_found("sampling/createMessage"))
+    }
+
+    fn list_roots(&self, params: ListRootsParams) -> Result<ListRootsResult, RPCErrorDetail> {
+        let _ = params;
+        Err(RPCErrorDetail::method_not_found("roots/list"))
+    }
+
+    fn elicit(
+        &self,
+        params: ElicitationCreateParams,
+    ) -> Result<ElicitationCreateResult, RPCErrorDetail> {
+        let _ = params;
+        Err(RPCErrorDetail::method_not_found("elicitation/create"))
+    }
+}
+
+/// Routes `request` to the matching [`McpClientHandler`] method and wraps
+/// the outcome in a JSON-RPC response or error carrying the request's id.
+pub fn dispatch_server_request<H: McpClientHandler + ?Sized>(
+    handler: &H,
+    request: ServerRequest,
+) -> Result<JSONRPCResponse<ClientResult>, JSONRPCError> {
+    let (id, result) = match request {
+        ServerRequest::Ping { id, params, .. } => {
+            (id, handler.ping(params).map(ClientResult::Empty))
+        }
+        ServerRequest::CreateMessage { id, params, .. } => (
+            id,
+            handler
+                .create_message(params)
+                .map(ClientResult::CreateMessage),
+        ),
+        ServerRequest::ListRoots { id, params, .. } => {
+            (id, handler.list_roots(params).map(ClientResult::ListRoots))
+        }
+        ServerRequest::ElicitationCreate { id, params, .. } => (
+            id,
+            handler.elicit(params).map(ClientResult::ElicitationCreate),
+        ),
+    };
+    respond(id, result)
+}
+
+fn respond<T>(
+    id: RequestId,
+    result: Result<T, RPCErrorDetail>,
+) -> Result<JSONRPCResponse<T>, JSONRPCError> {
+    match result {
+        Ok(result) => Ok(JSONRPCResponse::new(id, result)),
+        Err(error) => Err(JSONRPCError::new(id, error)),
+    }
+}
@@ -0,0 +1,104 @@
+//! Adapters for existing JSON-RPC crates, so MCP methods can be served and
+//! called through `jsonrpsee` or `jsonrpc-core` without re-modeling messages.
+//!
+//! Incoming params need no adapter: both crates parse params into any
+//! `DeserializeOwned` type, and return any `Serialize` type as a result.
+
+use crate::types::*;
+
+/// Implements the outgoing-params conversions for MCP params types.
+macro_rules! params_interop {
+    ($($params:ty),* $(,)?) => {$(
+        #[cfg(feature = "jsonrpsee")]
+        impl jsonrpsee_core::traits::ToRpcParams for $params {
+            fn to_rpc_params(
+                self,
+            ) -> Result<Option<Box<serde_json::value::RawValue>>, serde_json::Error> {
+                serde_json::value::to_raw_value(&self).map(Some)
+            }
+        }
+
+        #[cfg(feature = "jsonrpc-core")]
+        impl TryFrom<$params> for jsonrpc_core::Params {
+            type Error = serde_json::Error;
+
+            fn try_from(params: $params) -> Result<Self, Self::Error> {
+                match serde_json::to_value(params)? {
+                    serde_json::Value::Object(map) => Ok(jsonrpc_core::Params::Map(map)),
+                    _ => Ok(jsonrpc_core::Params::None),
+                }
+            }
+        }
+    )*};
+}
+
+params_interop!(
+    MCPRequestParams,
+    MCPNotificationParams,
+    CancelledNotificationParams,
+    InitializeParams,
+    PingParams,
+    ProgressNotificationParams,
+    PaginatedParams,
+    ListPromptsParams,
+    ListResourcesParams,
+    ListResourceTemplatesParams,
+    ListToolsParams,
+    ReadResourceParams,
+    SubscribeParams,
+    UnsubscribeParams,
+    ResourceUpdatedParams,
+    GetPromptParams,
+    CallToolParams,
+    SetLevelParams,
+    LoggingMessageParams,
+    CreateMessageParams,
+    CompleteParams,
+    ListRootsParams,
+    ElicitationCreateParams,
+);
+
+#[cfg(feature = "jsonrpsee")]
+impl From<RPCErrorDetail> for jsonrpsee_types::ErrorObjectOwned {
+    fn from(detail: RPCErrorDetail) -> Self {
+        jsonrpsee_types::ErrorObject::owned(detail.code, detail.message, detail.data)
+    }
+}
+
+#[cfg(feature = "jsonrpsee")]
+impl From<jsonrpsee_types::ErrorObject<'_>> for RPCErrorDetail {
+    /// Error data that is not valid JSON is dropped.
+    fn from(error: jsonrpsee_types::ErrorObject<'_>) -> Self {
+        RPCErrorDetail {
+            code: error.code(),
+            message: error.message().to_string(),
+            data: error
+                .data()
+                .and_then(|data| serde_json::from_str(data.get()).ok()),
+        }
+    }
+}
+
+#[cfg(feature = "jsonrpc-core")]
+impl From<RPCErrorDetail> for jsonrpc_core::Error {
+    fn from(detail: RPCErrorDetail) -> Self {
+        jsonrpc_core::Error {
+            code: jsonrpc_core::ErrorCode::from(i64::from(detail.code)),
+            message: detail.message,
+            data: detail.data,
+        }
+    }
+}
+
+#[cfg(feature = "jsonrpc-core")]
+impl From<jsonrpc_core::Error> for RPCErrorDetail {
+    /// Codes outside the `i32` range, which JSON-RPC does not allow, become
+    /// [`INTERNAL_ERROR`].
+    fn from(error: jsonrpc_core::Error) -> Self {
+        RPCErrorDetail {
+            code: i32::try_from(error.code.code()).unwrap_or(INTERNAL_ERROR),
+            message: error.message,
+            data: error.data,
+        }
+    }
+}
@@ -1,3 +1,109 @@
+// Lets `#[mcp_extras]` refer to `::mcp_schema` from inside this crate.
+extern crate self as mcp_schema;
+
+mod auth;
+mod batch;
+mod builder;
+mod capabilities;
+mod catalog;
+mod chunk;
+mod completion;
+mod config;
+#[cfg(feature = "hmac")]
+mod cursor;
+mod decode;
+mod deprecation;
+mod discovery;
+#[cfg(feature = "draft")]
+pub mod draft;
+mod elicitation;
+mod error;
+mod extras;
+mod framing;
+mod handler;
+#[cfg(any(feature = "jsonrpsee", feature = "jsonrpc-core"))]
+mod interop;
+mod logging;
+mod markdown;
+mod meta;
+pub mod meta_keys;
+mod metadata;
+mod method;
+mod middleware;
+mod origin;
+#[cfg(feature = "otel")]
+pub mod otel;
+mod progress;
+mod prompts;
+#[cfg(feature = "provider-metadata")]
+mod provider_meta;
+pub mod registry;
+mod request;
+mod request_id;
+mod resources;
+mod results;
+mod resume;
+mod roots;
+mod router;
+mod sampling;
+pub mod sanitize;
+mod scheme;
+mod scopes;
+#[cfg(feature = "axum")]
+mod streamable_http;
+mod timestamp;
+mod tools;
+mod trace;
 mod types;
+pub mod v2024_11_05;
+pub mod v2025_03_26;
+pub mod v2025_06_18;
+mod validate;
+mod version;
 
+pub use auth::*;
+pub use batch::*;
+pub use builder::*;
+pub use catalog::{ListDiff, DEFAULT_PAGE_SIZE};
+pub use chunk::*;
+pub use completion::*;
+pub use config::*;
+#[cfg(feature = "hmac")]
+pub use cursor::*;
+pub use decode::*;
+pub use deprecation::*;
+pub use discovery::*;
+pub use elicitation::*;
+pub use error::*;
+pub use extras::*;
+pub use framing::*;
+pub use handler::*;
+pub use logging::*;
+pub use markdown::*;
+pub use mcp_schema_derive::mcp_extras;
+pub use metadata::*;
+pub use method::*;
+pub use middleware::*;
+pub use origin::*;
+pub use progress::*;
+pub use prompts::*;
+#[cfg(feature = "provider-metadata")]
+pub use provider_meta::*;
+pub use request::*;
+pub use request_id::*;
+pub use resources::*;
+pub use results::*;
+pub use resume::*;
+pub use roots::*;
+pub use router::*;
+pub use sampling::*;
+pub use scheme::*;
+pub use scopes::*;
+#[cfg(feature = "axum")]
+pub use streamable_http::*;
+pub use timestamp::*;
+pub use tools::*;
+pub use trace::*;
 pub use types::*;
+pub use validate::*;
+pub use version::*;
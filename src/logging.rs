@@ -0,0 +1,321 @@
+//! Helpers for servers that emit `notifications/message` log records.
+
+use crate::timestamp::Timestamp;
+use crate::types::*;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// A structured log record, the recommended shape for the `data` payload of
+/// a logging notification.
+///
+/// Converted into [`LoggingMessageParams`], the record becomes:
+///
+/// ```json
+/// {
+///   "message": "request failed",
+///   "fields": { "tool": "search" },
+///   "error": ["connection reset", "broken pipe"],
+///   "timestamp": "2025-01-01T12:00:00Z"
+/// }
+/// ```
+///
+/// with empty `fields`, `error`, and a missing `timestamp` omitted.
+#[derive(Debug, Clone)]
+pub struct LogRecord {
+    pub level: LoggingLevel,
+    pub logger: Option<String>,
+    pub message: String,
+    pub fields: BTreeMap<String, Value>,
+    /// Error messages, outermost first.
+    pub error_chain: Vec<String>,
+    pub timestamp: Option<Timestamp>,
+}
+
+impl LogRecord {
+    pub fn new(level: LoggingLevel, message: impl Into<String>) -> Self {
+        LogRecord {
+            level,
+            logger: None,
+            message: message.into(),
+            fields: BTreeMap::new(),
+            error_chain: Vec::new(),
+            timestamp: None,
+        }
+    }
+
+    pub fn logger(mut self, logger: impl Into<String>) -> Self {
+        self.logger = Some(logger.into());
+        self
+    }
+
+    /// Adds a structured field, failing if `value` does not serialize to
+    /// JSON.
+    pub fn field(
+        mut self,
+        key: impl Into<String>,
+        value: impl Serialize,
+    ) -> Result<Self, serde_json::Error> {
+        let value = serde_json::to_value(value)?;
+        self.fields.insert(key.into(), value);
+        Ok(self)
+    }
+
+    /// Records `err` and its chain of sources.
+    pub fn error(mut self, err: &dyn std::error::Error) -> Self {
+        self.error_chain.clear();
+        let mut current = Some(err);
+        while let Some(err) = current {
+            self.error_chain.push(err.to_string());
+            current = err.source();
+        }
+        self
+    }
+
+    pub fn timestamp(mut self, timestamp: Timestamp) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Stamps the record with the current system time.
+    pub fn timestamp_now(self) -> Self {
+        self.timestamp(Timestamp::now())
+    }
+}
+
+impl From<LogRecord> for LoggingMessageParams {
+    fn from(record: LogRecord) -> Self {
+        let mut data = Map::new();
+        data.insert("message".into(), Value::String(record.message));
+        if !record.fields.is_empty() {
+            data.insert(
+                "fields".into(),
+                Value::Object(record.fields.into_iter().collect()),
+            );
+        }
+        if !record.error_chain.is_empty() {
+            data.insert(
+                "error".into(),
+                Value::Array(record.error_chain.into_iter().map(Value::String).collect()),
+            );
+        }
+        if let Some(timestamp) = record.timestamp {
+            data.insert("timestamp".into(), Value::String(timestamp.to_string()));
+        }
+        LoggingMessageParams {
+            level: record.level,
+            logger: record.logger,
+            data: Value::Object(data),
+            extra: Default::default(),
+        }
+    }
+}
+
+/// Applies the level requested through `logging/setLevel` to outgoing log
+/// notifications.
+///
+/// Wraps a sink that sends `notifications/message` and drops messages less
+/// severe than the current level.
+pub struct LogLevelFilter<F> {
+    level: LoggingLevel,
+    sink: F,
+}
+
+impl<F: FnMut(LoggingMessageParams)> LogLevelFilter<F> {
+    /// A filter starting at `level`, used until the client sets another.
+    pub fn new(level: LoggingLevel, sink: F) -> Self {
+        LogLevelFilter { level, sink }
+    }
+
+    /// The current minimum level.
+    pub fn level(&self) -> LoggingLevel {
+        self.level
+    }
+
+    /// Applies a `logging/setLevel` request.
+    pub fn set_level(&mut self, params: &SetLevelParams) {
+        self.level = params.level;
+    }
+
+    /// Whether messages at `level` are currently emitted.
+    pub fn enabled(&self, level: LoggingLevel) -> bool {
+        level >= self.level
+    }
+
+    /// Passes `params` to the sink if its level is enabled, returning
+    /// whether it was emitted.
+    pub fn maybe_emit(&mut self, params: LoggingMessageParams) -> bool {
+        if !self.enabled(params.level) {
+            return false;
+        }
+        (self.sink)(params);
+        true
+    }
+}
+
+impl<F> std::fmt::Debug for LogLevelFilter<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LogLevelFilter")
+            .field("level", &self.level)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A token bucket refilled continuously up to `burst` tokens.
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    tokens: f64,
+    refilled: Instant,
+}
+
+/// Rate-limits outgoing log notifications so that a chatty server does not
+/// flood a slow transport.
+///
+/// Each level has its own token bucket, so a burst of debug output cannot
+/// starve errors. A message identical to the last one emitted (same level,
+/// logger, and data) is suppressed without using a token for one refill
+/// interval of its level after that emission; once a different message goes
+/// out, the interval has passed, or on [`flush`](LogThrottle::flush), the
+/// sink first receives a record saying `suppressed N similar messages`.
+pub struct LogThrottle<F> {
+    burst: u32,
+    refill: Duration,
+    levels: BTreeMap<LoggingLevel, (u32, Duration)>,
+    buckets: BTreeMap<LoggingLevel, TokenBucket>,
+    last: Option<LoggingMessageParams>,
+    /// When `last` was emitted.
+    last_emitted: Option<Instant>,
+    suppressed: u64,
+    dropped: u64,
+    sink: F,
+}
+
+impl<F: FnMut(LoggingMessageParams)> LogThrottle<F> {
+    /// A throttle allowing bursts of `burst` messages per level, refilled by
+    /// one message every `refill`.
+    pub fn new(burst: u32, refill: Duration, sink: F) -> Self {
+        LogThrottle {
+            burst,
+            refill,
+            levels: BTreeMap::new(),
+            buckets: BTreeMap::new(),
+            last: None,
+            last_emitted: None,
+            suppressed: 0,
+            dropped: 0,
+            sink,
+        }
+    }
+
+    /// Overrides the rate for messages at `level`.
+    pub fn level_rate(mut self, level: LoggingLevel, burst: u32, refill: Duration) -> Self {
+        self.levels.insert(level, (burst, refill));
+        self
+    }
+
+    /// Number of messages dropped because their level ran out of tokens.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Number of duplicates suppressed since the last summary.
+    pub fn suppressed(&self) -> u64 {
+        self.suppressed
+    }
+
+    /// Passes `params` to the sink unless it repeats the last message within
+    /// its level's refill interval or its level is over its rate, returning
+    /// whether it was emitted.
+    pub fn maybe_emit(&mut self, params: LoggingMessageParams) -> bool {
+        self.maybe_emit_at(params, Instant::now())
+    }
+
+    /// [`maybe_emit`](Self::maybe_emit) with an explicit clock, for tests
+    /// and replay.
+    pub fn maybe_emit_at(&mut self, params: LoggingMessageParams, now: Instant) -> bool {
+        let (_, refill) = self.rate(params.level);
+        let repeated = self
+            .last
+            .as_ref()
+            .is_some_and(|last| same_message(last, &params));
+        let within_window = self
+            .last_emitted
+            .is_some_and(|emitted| now.saturating_duration_since(emitted) < refill);
+        if repeated && within_window {
+            self.suppressed += 1;
+            return false;
+        }
+        if !self.take_token(params.level, now) {
+            self.dropped += 1;
+            return false;
+        }
+        self.flush();
+        (self.sink)(params.clone());
+        self.last = Some(params);
+        self.last_emitted = Some(now);
+        true
+    }
+
+    /// Emits the summary of suppressed duplicates, if there are any.
+    pub fn flush(&mut self) {
+        if self.suppressed == 0 {
+            return;
+        }
+        let Some(last) = &self.last else {
+            return;
+        };
+        let summary = LogRecord {
+            logger: last.logger.clone(),
+            ..LogRecord::new(
+                last.level,
+                format!("suppressed {} similar messages", self.suppressed),
+            )
+        };
+        self.suppressed = 0;
+        (self.sink)(summary.into());
+    }
+
+    /// The burst and refill interval of `level`.
+    fn rate(&self, level: LoggingLevel) -> (u32, Duration) {
+        self.levels
+            .get(&level)
+            .copied()
+            .unwrap_or((self.burst, self.refill))
+    }
+
+    fn take_token(&mut self, level: LoggingLevel, now: Instant) -> bool {
+        let (burst, refill) = self.rate(level);
+        let bucket = self.buckets.entry(level).or_insert(TokenBucket {
+            tokens: f64::from(burst),
+            refilled: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.refilled);
+        bucket.tokens = if refill.is_zero() {
+            f64::from(burst)
+        } else {
+            (bucket.tokens + elapsed.as_secs_f64() / refill.as_secs_f64()).min(f64::from(burst))
+        };
+        bucket.refilled = now;
+        if bucket.tokens < 1.0 {
+            return false;
+        }
+        bucket.tokens -= 1.0;
+        true
+    }
+}
+
+fn same_message(a: &LoggingMessageParams, b: &LoggingMessageParams) -> bool {
+    a.level == b.level && a.logger == b.logger && a.data == b.data
+}
+
+impl<F> std::fmt::Debug for LogThrottle<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LogThrottle")
+            .field("burst", &self.burst)
+            .field("refill", &self.refill)
+            .field("suppressed", &self.suppressed)
+            .field("dropped", &self.dropped)
+            .finish_non_exhaustive()
+    }
+}
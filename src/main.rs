@@ -1,41 +1,40 @@
 use mcp_schema::{
-    ClientCapabilities, Implementation, InitializeParams, InitializeResult, JSONRPCRequest,
-    JSONRPCResponse, RequestId, ServerCapabilities, JSONRPC_VERSION, LATEST_PROTOCOL_VERSION,
+    ClientCapabilities, Implementation, InitializeParams, InitializePayload, InitializeResult,
+    JSONRPCRequest, JSONRPCResponse, RequestId, ServerCapabilities, LATEST_PROTOCOL_VERSION,
 };
 use std::collections::HashMap;
 
 fn main() {
     // Create and serialize an InitializeRequest
-    let init_req = JSONRPCRequest {
-        json_rpc: JSONRPC_VERSION.to_string(),
-        method: "initialize".to_string(),
-        id: RequestId::Number(1),
-        params: InitializeParams {
+    let init_req = JSONRPCRequest::new(
+        RequestId::Number(1),
+        "initialize",
+        InitializeParams {
             protocol_version: LATEST_PROTOCOL_VERSION.to_string(),
             capabilities: ClientCapabilities {
                 experimental: None,
                 roots: None,
                 sampling: None,
+                elicitation: None,
                 extra: HashMap::new(),
             },
             client_info: Implementation {
                 name: "MyClient".into(),
+                title: None,
                 version: "1.0".into(),
                 extra: HashMap::new(),
             },
         },
-    };
+    );
 
     // Serialize to JSON
     let json_str = serde_json::to_string_pretty(&init_req).unwrap();
     println!("InitializeRequest (serialized):\n{}\n", json_str);
 
     // Create and serialize an InitializeResponse
-    let init_res = JSONRPCResponse {
-        json_rpc: JSONRPC_VERSION.to_string(),
-        id: RequestId::Number(1),
-        result: InitializeResult {
-            meta: None,
+    let init_res = JSONRPCResponse::new(
+        RequestId::Number(1),
+        InitializeResult::new(InitializePayload {
             protocol_version: LATEST_PROTOCOL_VERSION.to_string(),
             capabilities: ServerCapabilities {
                 experimental: None,
@@ -43,17 +42,18 @@ fn main() {
                 prompts: None,
                 resources: None,
                 tools: None,
+                completions: None,
                 extra: HashMap::new(),
             },
             server_info: Implementation {
                 name: "MyServer".into(),
+                title: None,
                 version: "1.2".into(),
                 extra: HashMap::new(),
             },
             instructions: Some("Welcome to MyServer!".into()),
-            extra: HashMap::new(),
-        },
-    };
+        }),
+    );
 
     // Serialize the response to JSON
     let res_json = serde_json::to_string_pretty(&init_res).unwrap();
@@ -0,0 +1,107 @@
+//! Rendering of content blocks as Markdown, for hosts that show tool
+//! results and prompts in a chat UI.
+
+use crate::metadata::BaseMetadata;
+use crate::types::*;
+
+/// How [`render_markdown_with`] renders content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownOptions {
+    /// Embed images as `data:` URIs. When unset, or when the image is larger
+    /// than `max_inline_image_bytes`, an image link without a URL is
+    /// rendered as a placeholder.
+    pub inline_images: bool,
+    /// Largest base64-encoded image to embed.
+    pub max_inline_image_bytes: usize,
+}
+
+impl Default for MarkdownOptions {
+    fn default() -> Self {
+        MarkdownOptions {
+            inline_images: true,
+            max_inline_image_bytes: 1024 * 1024,
+        }
+    }
+}
+
+/// Renders `content` with the default [`MarkdownOptions`].
+pub fn render_markdown(content: &[PromptContent]) -> String {
+    render_markdown_with(content, &MarkdownOptions::default())
+}
+
+/// Renders `content` as Markdown, one paragraph per block:
+///
+/// - text verbatim;
+/// - images as image links to a `data:` URI, or as placeholders;
+/// - audio as a note naming its MIME type;
+/// - resource links as links titled with the resource's display name;
+/// - embedded text resources as fenced code blocks captioned with their
+///   URI, and binary ones as the caption and a size note.
+pub fn render_markdown_with(content: &[PromptContent], options: &MarkdownOptions) -> String {
+    let blocks: Vec<String> = content
+        .iter()
+        .map(|block| match block {
+            PromptContent::Text(text) => text.text.clone(),
+            PromptContent::Image(image) => render_image(image, options),
+            PromptContent::Audio(audio) => format!("*audio ({})*", audio.mime_type),
+            PromptContent::ResourceLink(link) => {
+                format!("[{}]({})", link.resource.display_name(), link.resource.uri)
+            }
+            PromptContent::Resource(embedded) => render_resource(&embedded.resource),
+        })
+        .collect();
+    blocks.join("\n\n")
+}
+
+fn render_image(image: &ImageContent, options: &MarkdownOptions) -> String {
+    if options.inline_images && image.data.len() <= options.max_inline_image_bytes {
+        format!("![image](data:{};base64,{})", image.mime_type, image.data)
+    } else {
+        format!("![image ({})]()", image.mime_type)
+    }
+}
+
+/// The info string for a fenced block of `mime_type`, e.g. `rust` for
+/// `text/x-rust` or `json` for `application/json`.
+fn fence_language(mime_type: Option<&str>) -> &str {
+    let Some(mime_type) = mime_type else {
+        return "";
+    };
+    let subtype = mime_type.split(';').next().unwrap_or_default().trim();
+    let subtype = subtype.rsplit('/').next().unwrap_or_default();
+    let subtype = subtype.strip_prefix("x-").unwrap_or(subtype);
+    match subtype {
+        "plain" => "",
+        _ => subtype.rsplit('+').next().unwrap_or(subtype),
+    }
+}
+
+fn render_resource(resource: &ResourceContents) -> String {
+    match resource {
+        ResourceContents::Text(text) => {
+            // The fence must be longer than any run of backticks in the text.
+            let longest = text
+                .text
+                .split(|c| c != '`')
+                .map(str::len)
+                .max()
+                .unwrap_or(0);
+            let fence = "`".repeat(longest.max(2) + 1);
+            let body = text.text.strip_suffix('\n').unwrap_or(&text.text);
+            format!(
+                "`{}`\n\n{}{}\n{}\n{}",
+                text.uri,
+                fence,
+                fence_language(text.mime_type.as_deref()),
+                body,
+                fence
+            )
+        }
+        ResourceContents::Blob(blob) => format!(
+            "`{}`\n\n*{} bytes of {}*",
+            blob.uri,
+            blob.blob.trim_end_matches('=').len() * 3 / 4,
+            blob.mime_type.as_deref().unwrap_or("binary data")
+        ),
+    }
+}
@@ -0,0 +1,51 @@
+//! Typed access to `_meta` conventions on requests.
+
+use crate::meta_keys;
+use crate::types::*;
+use serde_json::Value;
+
+impl RequestMeta {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the idempotency key, under [`meta_keys::IDEMPOTENCY_KEY`].
+    pub fn with_idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.extra.insert(
+            meta_keys::IDEMPOTENCY_KEY.to_string(),
+            Value::String(key.into()),
+        );
+        self
+    }
+
+    pub fn idempotency_key(&self) -> Option<&str> {
+        self.extra.get(meta_keys::IDEMPOTENCY_KEY)?.as_str()
+    }
+
+    /// Sets the retry count, under [`meta_keys::RETRY_COUNT`].
+    pub fn with_retry_count(mut self, count: u32) -> Self {
+        self.extra
+            .insert(meta_keys::RETRY_COUNT.to_string(), Value::from(count));
+        self
+    }
+
+    /// The retry count; `0` when absent, meaning a first attempt.
+    pub fn retry_count(&self) -> u32 {
+        self.extra
+            .get(meta_keys::RETRY_COUNT)
+            .and_then(Value::as_u64)
+            .map_or(0, |count| count.try_into().unwrap_or(u32::MAX))
+    }
+
+    /// Whether this request is a retry of an earlier attempt.
+    pub fn is_retry(&self) -> bool {
+        self.retry_count() > 0
+    }
+
+    /// The metadata for the next attempt of the same request: the same
+    /// idempotency key with the retry count incremented.
+    pub fn next_attempt(&self) -> Self {
+        let count = self.retry_count().saturating_add(1);
+        self.clone().with_retry_count(count)
+    }
+}
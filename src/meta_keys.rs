@@ -0,0 +1,147 @@
+//! Well-known `_meta` keys and validation of custom key names.
+//!
+//! The spec reserves every `_meta` key prefix whose labels (before the last
+//! one) include `modelcontextprotocol` or `mcp`, such as
+//! `modelcontextprotocol.io/` or `mcp.dev/`. Extensions should use their own
+//! reverse-DNS style prefix, e.g. `com.example/feature`.
+//!
+//! Key grammar:
+//!
+//! - An optional prefix: dot-separated labels followed by `/`. Each label
+//!   starts with a letter, ends with a letter or digit, and may contain
+//!   hyphens in between.
+//! - A name: empty, or starting and ending with an alphanumeric character,
+//!   with hyphens, underscores, dots, and alphanumerics in between.
+
+use std::fmt;
+
+/// Key of the progress token in a request's `_meta`.
+pub const PROGRESS_TOKEN: &str = "progressToken";
+
+/// Key under which this crate stores a tool's version in `Tool::_meta`.
+pub const TOOL_VERSION: &str = "io.github.yonaka15/toolVersion";
+
+/// Key under which this crate stores deprecation notices in the `_meta` of
+/// tools and prompts.
+pub const DEPRECATION: &str = "io.github.yonaka15/deprecation";
+
+/// Key under which this crate stores the OAuth scopes a tool requires in
+/// `Tool::_meta`.
+pub const REQUIRED_SCOPES: &str = "io.github.yonaka15/requiredScopes";
+
+/// Key under which this crate stores the position of a chunk in the
+/// `_meta` of resource contents split by [`crate::split_contents`].
+pub const RESOURCE_CHUNK: &str = "io.github.yonaka15/chunk";
+
+/// Key of a client-chosen idempotency key in a request's `_meta`. Requests
+/// retried with the same key are duplicates of one logical request.
+pub const IDEMPOTENCY_KEY: &str = "io.github.yonaka15/idempotencyKey";
+
+/// Key of the number of times a request has been retried, in its `_meta`.
+pub const RETRY_COUNT: &str = "io.github.yonaka15/retryCount";
+
+/// Key of the W3C Trace Context `traceparent` in a request's `_meta`.
+pub const TRACEPARENT: &str = "traceparent";
+
+/// Key of the W3C Trace Context `tracestate` in a request's `_meta`.
+pub const TRACESTATE: &str = "tracestate";
+
+/// Prefix reserved for keys defined by the MCP specification itself.
+pub const RESERVED_PREFIX: &str = "modelcontextprotocol.io/";
+
+/// Labels that mark a prefix as reserved when they appear before its last label.
+const RESERVED_LABELS: [&str; 2] = ["modelcontextprotocol", "mcp"];
+
+/// Reasons a `_meta` key can be rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MetaKeyError {
+    /// The prefix (before `/`) does not follow the label grammar.
+    InvalidPrefix(String),
+    /// The name (after the prefix) does not follow the name grammar.
+    InvalidName(String),
+    /// The prefix is reserved for the MCP specification.
+    Reserved(String),
+}
+
+impl fmt::Display for MetaKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetaKeyError::InvalidPrefix(key) => write!(f, "invalid _meta key prefix in {:?}", key),
+            MetaKeyError::InvalidName(key) => write!(f, "invalid _meta key name in {:?}", key),
+            MetaKeyError::Reserved(key) => write!(f, "_meta key {:?} uses a reserved prefix", key),
+        }
+    }
+}
+
+impl std::error::Error for MetaKeyError {}
+
+/// Splits a key into its optional prefix (without the trailing `/`) and name.
+fn split_key(key: &str) -> (Option<&str>, &str) {
+    match key.rfind('/') {
+        Some(pos) => (Some(&key[..pos]), &key[pos + 1..]),
+        None => (None, key),
+    }
+}
+
+fn is_valid_label(label: &str) -> bool {
+    let bytes = label.as_bytes();
+    match (bytes.first(), bytes.last()) {
+        (Some(first), Some(last)) => {
+            first.is_ascii_alphabetic()
+                && last.is_ascii_alphanumeric()
+                && bytes.iter().all(|b| b.is_ascii_alphanumeric() || *b == b'-')
+        }
+        _ => false,
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let bytes = name.as_bytes();
+    match (bytes.first(), bytes.last()) {
+        (Some(first), Some(last)) => {
+            first.is_ascii_alphanumeric()
+                && last.is_ascii_alphanumeric()
+                && bytes
+                    .iter()
+                    .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'))
+        }
+        _ => true,
+    }
+}
+
+/// Returns `true` if the key's prefix is reserved for the MCP specification.
+pub fn is_reserved(key: &str) -> bool {
+    match split_key(key) {
+        (Some(prefix), _) => {
+            let labels: Vec<&str> = prefix.split('.').collect();
+            labels[..labels.len() - 1]
+                .iter()
+                .any(|label| RESERVED_LABELS.contains(label))
+        }
+        (None, _) => false,
+    }
+}
+
+/// Checks that a key follows the `_meta` key grammar.
+pub fn validate_key(key: &str) -> Result<(), MetaKeyError> {
+    let (prefix, name) = split_key(key);
+    if let Some(prefix) = prefix {
+        if !prefix.split('.').all(is_valid_label) {
+            return Err(MetaKeyError::InvalidPrefix(key.to_string()));
+        }
+    }
+    if !is_valid_name(name) {
+        return Err(MetaKeyError::InvalidName(key.to_string()));
+    }
+    Ok(())
+}
+
+/// Checks that a key is suitable for an extension: it must follow the key
+/// grammar and must not use a reserved prefix.
+pub fn validate_custom_key(key: &str) -> Result<(), MetaKeyError> {
+    validate_key(key)?;
+    if is_reserved(key) {
+        return Err(MetaKeyError::Reserved(key.to_string()));
+    }
+    Ok(())
+}
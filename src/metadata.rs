@@ -0,0 +1,84 @@
+//! Labels for things shown to users: implementations, tools, prompts and
+//! their arguments, resources, and resource templates.
+
+use crate::types::*;
+
+/// A named item that may also carry a human-readable `title`.
+pub trait BaseMetadata {
+    /// The programmatic name, used as an identifier.
+    fn name(&self) -> &str;
+
+    /// The human-readable name, if one was given.
+    fn title(&self) -> Option<&str>;
+
+    /// The label to show in a UI: the title if there is one, else the name.
+    fn display_name(&self) -> &str {
+        self.title().unwrap_or_else(|| self.name())
+    }
+}
+
+impl BaseMetadata for Implementation {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+}
+
+impl BaseMetadata for Prompt {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+}
+
+impl BaseMetadata for Resource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+}
+
+impl BaseMetadata for PromptArgument {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+}
+
+impl BaseMetadata for ResourceTemplate {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+}
+
+/// Tools were titled through `annotations.title` before they had a `title`
+/// of their own; that is used when `title` is absent.
+impl BaseMetadata for Tool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn title(&self) -> Option<&str> {
+        self.title.as_deref().or_else(|| {
+            self.annotations
+                .as_ref()
+                .and_then(|annotations| annotations.title.as_deref())
+        })
+    }
+}
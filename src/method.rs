@@ -0,0 +1,197 @@
+//! The methods of MCP requests and notifications.
+
+use crate::types::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+macro_rules! methods {
+    ($($(#[$doc:meta])* $variant:ident => $name:literal,)*) => {
+        /// A request or notification method, sent by either side.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum Method {
+            $($(#[$doc])* $variant,)*
+        }
+
+        impl Method {
+            /// Every known method.
+            pub const ALL: &'static [Method] = &[$(Method::$variant,)*];
+
+            /// The method name sent on the wire, e.g. `"tools/call"`.
+            pub fn as_str(self) -> &'static str {
+                match self {
+                    $(Method::$variant => $name,)*
+                }
+            }
+        }
+    };
+}
+
+methods! {
+    Ping => "ping",
+    Initialize => "initialize",
+    Complete => "completion/complete",
+    SetLevel => "logging/setLevel",
+    GetPrompt => "prompts/get",
+    ListPrompts => "prompts/list",
+    ListResources => "resources/list",
+    ListResourceTemplates => "resources/templates/list",
+    ReadResource => "resources/read",
+    Subscribe => "resources/subscribe",
+    Unsubscribe => "resources/unsubscribe",
+    CallTool => "tools/call",
+    ListTools => "tools/list",
+    CreateMessage => "sampling/createMessage",
+    ListRoots => "roots/list",
+    ElicitationCreate => "elicitation/create",
+    Cancelled => "notifications/cancelled",
+    Progress => "notifications/progress",
+    Initialized => "notifications/initialized",
+    RootsListChanged => "notifications/roots/list_changed",
+    LoggingMessage => "notifications/message",
+    ResourceUpdated => "notifications/resources/updated",
+    ResourceListChanged => "notifications/resources/list_changed",
+    ToolListChanged => "notifications/tools/list_changed",
+    PromptListChanged => "notifications/prompts/list_changed",
+}
+
+impl Method {
+    /// Whether the method is a notification, which has no `id` and gets no
+    /// response.
+    pub fn is_notification(self) -> bool {
+        self.as_str().starts_with("notifications/")
+    }
+
+    /// Whether the method is a request that a client sends to a server.
+    /// `ping` goes both ways, so it counts.
+    pub fn is_client_request(self) -> bool {
+        !self.is_notification()
+            && !matches!(
+                self,
+                Method::CreateMessage | Method::ListRoots | Method::ElicitationCreate
+            )
+    }
+
+    /// Whether the method is a request that a server sends to a client.
+    /// `ping` goes both ways, so it counts.
+    pub fn is_server_request(self) -> bool {
+        matches!(
+            self,
+            Method::Ping | Method::CreateMessage | Method::ListRoots | Method::ElicitationCreate
+        )
+    }
+
+    /// Whether the method is a notification that a client sends to a server.
+    pub fn is_client_notification(self) -> bool {
+        matches!(
+            self,
+            Method::Cancelled | Method::Progress | Method::Initialized | Method::RootsListChanged
+        )
+    }
+
+    /// Whether the method is a notification that a server sends to a client.
+    pub fn is_server_notification(self) -> bool {
+        self.is_notification() && !matches!(self, Method::Initialized | Method::RootsListChanged)
+    }
+}
+
+/// A method name that names no known [`Method`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownMethod(pub String);
+
+impl fmt::Display for UnknownMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown method {:?}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownMethod {}
+
+impl FromStr for Method {
+    type Err = UnknownMethod;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Method::ALL
+            .iter()
+            .copied()
+            .find(|method| method.as_str() == s)
+            .ok_or_else(|| UnknownMethod(s.to_string()))
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Method {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Method {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl ClientRequest {
+    pub fn method(&self) -> Method {
+        match self {
+            ClientRequest::Ping { .. } => Method::Ping,
+            ClientRequest::Initialize { .. } => Method::Initialize,
+            ClientRequest::Complete { .. } => Method::Complete,
+            ClientRequest::SetLevel { .. } => Method::SetLevel,
+            ClientRequest::GetPrompt { .. } => Method::GetPrompt,
+            ClientRequest::ListPrompts { .. } => Method::ListPrompts,
+            ClientRequest::ListResources { .. } => Method::ListResources,
+            ClientRequest::ListResourceTemplates { .. } => Method::ListResourceTemplates,
+            ClientRequest::ReadResource { .. } => Method::ReadResource,
+            ClientRequest::Subscribe { .. } => Method::Subscribe,
+            ClientRequest::Unsubscribe { .. } => Method::Unsubscribe,
+            ClientRequest::CallTool { .. } => Method::CallTool,
+            ClientRequest::ListTools { .. } => Method::ListTools,
+            #[allow(deprecated)]
+            ClientRequest::ElicitationCreate { .. } => Method::ElicitationCreate,
+        }
+    }
+}
+
+impl ServerRequest {
+    pub fn method(&self) -> Method {
+        match self {
+            ServerRequest::Ping { .. } => Method::Ping,
+            ServerRequest::CreateMessage { .. } => Method::CreateMessage,
+            ServerRequest::ListRoots { .. } => Method::ListRoots,
+            ServerRequest::ElicitationCreate { .. } => Method::ElicitationCreate,
+        }
+    }
+}
+
+impl ClientNotification {
+    pub fn method(&self) -> Method {
+        match self {
+            ClientNotification::Cancelled { .. } => Method::Cancelled,
+            ClientNotification::Progress { .. } => Method::Progress,
+            ClientNotification::Initialized { .. } => Method::Initialized,
+            ClientNotification::RootsListChanged { .. } => Method::RootsListChanged,
+        }
+    }
+}
+
+impl ServerNotification {
+    pub fn method(&self) -> Method {
+        match self {
+            ServerNotification::Cancelled { .. } => Method::Cancelled,
+            ServerNotification::Progress { .. } => Method::Progress,
+            ServerNotification::LoggingMessage { .. } => Method::LoggingMessage,
+            ServerNotification::ResourceUpdated { .. } => Method::ResourceUpdated,
+            ServerNotification::ResourceListChanged { .. } => Method::ResourceListChanged,
+            ServerNotification::ToolListChanged { .. } => Method::ToolListChanged,
+            ServerNotification::PromptListChanged { .. } => Method::PromptListChanged,
+        }
+    }
+}
@@ -0,0 +1,269 @@
+//! Composable middleware around message handlers.
+//!
+//! Messages travel as raw JSON-RPC values, so layers can inspect and
+//! rewrite any message, including ones the typed enums would reject. A
+//! [`MessageHandler`] takes an incoming message and returns an optional
+//! reply; an [`Interceptor`] sees messages on the way in and replies on the
+//! way out, and becomes a [`Layer`] wrapping any handler.
+
+use crate::decode::DecodeLimits;
+use crate::handler::{dispatch_client_request, McpServerHandler};
+use crate::method::Method;
+use crate::types::*;
+use crate::version::ProtocolVersion;
+use serde_json::{Map, Value};
+
+/// Processes one incoming message, returning the reply to send, if any.
+pub trait MessageHandler {
+    fn handle(&mut self, message: Value) -> Option<Value>;
+}
+
+impl<F: FnMut(Value) -> Option<Value>> MessageHandler for F {
+    fn handle(&mut self, message: Value) -> Option<Value> {
+        self(message)
+    }
+}
+
+/// Which way a message is travelling through a layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Incoming,
+    Outgoing,
+}
+
+/// What an [`Interceptor`] decided about a message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Flow {
+    /// Pass the (possibly rewritten) message on.
+    Continue(Value),
+    /// Stop an incoming message and answer it with this reply instead.
+    Reply(Value),
+    /// Discard the message.
+    Drop,
+}
+
+/// A hook run on every message passing through a layer.
+pub trait Interceptor {
+    fn intercept(&mut self, direction: Direction, message: Value) -> Flow;
+}
+
+/// Wraps a handler in additional behavior.
+pub trait Layer<H: MessageHandler> {
+    type Handler: MessageHandler;
+
+    fn layer(self, inner: H) -> Self::Handler;
+}
+
+impl<H: MessageHandler, I: Interceptor> Layer<H> for I {
+    type Handler = Intercepted<I, H>;
+
+    fn layer(self, inner: H) -> Self::Handler {
+        Intercepted {
+            interceptor: self,
+            inner,
+        }
+    }
+}
+
+/// A handler wrapped by an [`Interceptor`].
+#[derive(Debug, Clone)]
+pub struct Intercepted<I, H> {
+    pub interceptor: I,
+    pub inner: H,
+}
+
+impl<I: Interceptor, H: MessageHandler> MessageHandler for Intercepted<I, H> {
+    fn handle(&mut self, message: Value) -> Option<Value> {
+        match self.interceptor.intercept(Direction::Incoming, message) {
+            Flow::Continue(message) => {
+                let reply = self.inner.handle(message)?;
+                match self.interceptor.intercept(Direction::Outgoing, reply) {
+                    Flow::Continue(reply) | Flow::Reply(reply) => Some(reply),
+                    Flow::Drop => None,
+                }
+            }
+            Flow::Reply(reply) => Some(reply),
+            Flow::Drop => None,
+        }
+    }
+}
+
+/// Adapts an [`McpServerHandler`] into a [`MessageHandler`].
+///
+/// Requests are decoded as [`ClientRequest`] and dispatched. A request for a
+/// method servers do not handle is answered with a method-not-found error,
+/// one with a malformed envelope (such as a wrong `jsonrpc` value) with an
+/// invalid-request error, and one whose params fail to decode with an
+/// invalid-params error. Messages without an `id` get no reply.
+#[derive(Debug, Clone)]
+pub struct ServerService<H>(pub H);
+
+impl<H: McpServerHandler> MessageHandler for ServerService<H> {
+    fn handle(&mut self, message: Value) -> Option<Value> {
+        let id = request_id(&message)?;
+        let method = message.get("method").and_then(Value::as_str);
+        let known = method
+            .and_then(|method| method.parse::<Method>().ok())
+            .is_some_and(Method::is_client_request);
+        let error = match method {
+            Some(method) if !known => Some(RPCErrorDetail::method_not_found(method)),
+            _ => serde_json::from_value::<JSONRPCRequest<Option<Value>>>(message.clone())
+                .err()
+                .map(|err| RPCErrorDetail::invalid_request(err.to_string())),
+        };
+        let reply = match error {
+            Some(error) => serde_json::to_value(JSONRPCError::new(id, error)),
+            None => match serde_json::from_value::<ClientRequest>(message) {
+                Ok(request) => match dispatch_client_request(&self.0, request) {
+                    Ok(response) => serde_json::to_value(response),
+                    Err(error) => serde_json::to_value(error),
+                },
+                Err(err) => serde_json::to_value(JSONRPCError::new(
+                    id,
+                    RPCErrorDetail::invalid_params(err.to_string()),
+                )),
+            },
+        };
+        reply.ok()
+    }
+}
+
+fn request_id(message: &Value) -> Option<RequestId> {
+    message
+        .get("method")
+        .and(message.get("id"))
+        .and_then(|id| serde_json::from_value(id.clone()).ok())
+}
+
+fn reject(message: &Value, error: RPCErrorDetail) -> Flow {
+    match request_id(message) {
+        Some(id) => serde_json::to_value(JSONRPCError::new(id, error))
+            .map(Flow::Reply)
+            .unwrap_or(Flow::Drop),
+        None => Flow::Drop,
+    }
+}
+
+/// Reports every message to a sink, without changing it.
+#[derive(Debug, Clone)]
+pub struct Logging<F>(pub F);
+
+impl<F: FnMut(Direction, &Value)> Interceptor for Logging<F> {
+    fn intercept(&mut self, direction: Direction, message: Value) -> Flow {
+        (self.0)(direction, &message);
+        Flow::Continue(message)
+    }
+}
+
+/// Rejects incoming requests for features the server did not advertise,
+/// answering them with a method-not-found error.
+#[derive(Debug, Clone)]
+pub struct EnforceCapabilities {
+    pub capabilities: ServerCapabilities,
+    /// The negotiated protocol version. The `completions` capability only
+    /// exists from 2025-03-26 on, so completion requests are checked against
+    /// it only when the version is known to be that recent.
+    pub version: Option<ProtocolVersion>,
+}
+
+impl EnforceCapabilities {
+    pub fn new(capabilities: ServerCapabilities) -> Self {
+        EnforceCapabilities {
+            capabilities,
+            version: None,
+        }
+    }
+
+    pub fn with_version(mut self, version: ProtocolVersion) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Whether the capabilities allow a client to call `method`.
+    pub fn allows(&self, method: &str) -> bool {
+        let capabilities = &self.capabilities;
+        match method {
+            "logging/setLevel" => capabilities.logging.is_some(),
+            "completion/complete" => match self.version {
+                Some(version) if version >= ProtocolVersion::V2025_03_26 => {
+                    capabilities.completions.is_some()
+                }
+                _ => true,
+            },
+            "resources/subscribe" | "resources/unsubscribe" => capabilities
+                .resources
+                .as_ref()
+                .is_some_and(|resources| resources.subscribe == Some(true)),
+            _ if method.starts_with("prompts/") => capabilities.prompts.is_some(),
+            _ if method.starts_with("resources/") => capabilities.resources.is_some(),
+            _ if method.starts_with("tools/") => capabilities.tools.is_some(),
+            _ => true,
+        }
+    }
+}
+
+impl Interceptor for EnforceCapabilities {
+    fn intercept(&mut self, direction: Direction, message: Value) -> Flow {
+        let method = message.get("method").and_then(Value::as_str);
+        match method {
+            Some(method) if direction == Direction::Incoming && !self.allows(method) => {
+                reject(&message, RPCErrorDetail::method_not_found(method))
+            }
+            _ => Flow::Continue(message),
+        }
+    }
+}
+
+/// Rejects incoming messages whose text content exceeds [`DecodeLimits`],
+/// answering requests with an invalid-request error and dropping the rest.
+#[derive(Debug, Clone, Default)]
+pub struct EnforceLimits(pub DecodeLimits);
+
+impl Interceptor for EnforceLimits {
+    fn intercept(&mut self, direction: Direction, message: Value) -> Flow {
+        if direction == Direction::Outgoing {
+            return Flow::Continue(message);
+        }
+        match self.0.check_value(&message) {
+            Ok(()) => Flow::Continue(message),
+            Err(err) => reject(&message, RPCErrorDetail::invalid_request(err.to_string())),
+        }
+    }
+}
+
+/// Adds `_meta` entries to outgoing messages: to `result` for responses and
+/// to `params` for requests and notifications. Keys already present are
+/// left alone.
+#[derive(Debug, Clone, Default)]
+pub struct InjectMeta(pub Map<String, Value>);
+
+impl Interceptor for InjectMeta {
+    fn intercept(&mut self, direction: Direction, mut message: Value) -> Flow {
+        if direction == Direction::Incoming {
+            return Flow::Continue(message);
+        }
+        let field = if message.get("result").is_some() {
+            "result"
+        } else if message.get("method").is_some() {
+            "params"
+        } else {
+            return Flow::Continue(message);
+        };
+        if let Some(object) = message.as_object_mut() {
+            let target = object
+                .entry(field)
+                .or_insert_with(|| Value::Object(Map::new()));
+            if let Some(target) = target.as_object_mut() {
+                let meta = target
+                    .entry("_meta")
+                    .or_insert_with(|| Value::Object(Map::new()));
+                if let Some(meta) = meta.as_object_mut() {
+                    for (key, value) in &self.0 {
+                        meta.entry(key.clone()).or_insert_with(|| value.clone());
+                    }
+                }
+            }
+        }
+        Flow::Continue(message)
+    }
+}
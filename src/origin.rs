@@ -0,0 +1,170 @@
+//! Origin validation for HTTP transports.
+//!
+//! The spec requires servers to validate the `Origin` header of incoming
+//! connections. Servers bound to localhost must also check `Host`: in a DNS
+//! rebinding attack a page on `evil.example` resolves its own name to
+//! `127.0.0.1`, so the browser sends a same-origin request carrying
+//! `Host: evil.example` to the local server.
+
+use std::fmt;
+use std::net::IpAddr;
+
+/// Why a request was refused by an [`OriginPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OriginRejection {
+    /// The `Origin` header is not a valid serialized origin.
+    MalformedOrigin(String),
+    /// The origin is not allowed by the policy.
+    OriginNotAllowed(String),
+    /// The request has no `Host` header but the policy needs one.
+    MissingHost,
+    /// The `Host` header names a host the server does not answer for.
+    HostNotAllowed(String),
+}
+
+impl OriginRejection {
+    /// The HTTP status to answer with: `403 Forbidden`, as the spec
+    /// requires for invalid origins, or `400` for an unusable `Host`.
+    pub fn http_status(&self) -> u16 {
+        match self {
+            OriginRejection::MissingHost => 400,
+            _ => 403,
+        }
+    }
+}
+
+impl fmt::Display for OriginRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OriginRejection::MalformedOrigin(origin) => write!(f, "malformed origin {:?}", origin),
+            OriginRejection::OriginNotAllowed(origin) => {
+                write!(f, "origin {:?} is not allowed", origin)
+            }
+            OriginRejection::MissingHost => f.write_str("missing Host header"),
+            OriginRejection::HostNotAllowed(host) => write!(f, "host {:?} is not allowed", host),
+        }
+    }
+}
+
+impl std::error::Error for OriginRejection {}
+
+/// Which origins and hosts an HTTP transport accepts.
+///
+/// Requests without an `Origin` header come from non-browser clients and
+/// pass the origin check; browsers always send it on cross-origin requests.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OriginPolicy {
+    /// Origins to accept, e.g. `https://app.example.com`. When empty and
+    /// `localhost_only` is unset, any origin is accepted.
+    pub allowed_origins: Vec<String>,
+    /// Hosts to accept in the `Host` header. When empty and
+    /// `localhost_only` is unset, `Host` is not checked.
+    pub allowed_hosts: Vec<String>,
+    /// Only accept local origins (and the allowed ones) and local hosts.
+    pub localhost_only: bool,
+}
+
+/// Splits `host[:port]` (with IPv6 literals in brackets) into the host.
+fn host_name(authority: &str) -> &str {
+    if let Some(rest) = authority.strip_prefix('[') {
+        return rest.split(']').next().unwrap_or(rest);
+    }
+    match authority.rsplit_once(':') {
+        Some((host, port)) if port.bytes().all(|b| b.is_ascii_digit()) => host,
+        _ => authority,
+    }
+}
+
+/// Whether `host` is a loopback address or a `localhost` name. Addresses
+/// are parsed, so DNS names that merely look like one (`127.a.evil.com`)
+/// are not local.
+fn is_local(host: &str) -> bool {
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return ip.is_loopback();
+    }
+    let host = host.to_ascii_lowercase();
+    host == "localhost" || host.ends_with(".localhost")
+}
+
+/// The host of a serialized origin (`scheme://host[:port]`).
+fn origin_host(origin: &str) -> Option<&str> {
+    let (scheme, authority) = origin.split_once("://")?;
+    if scheme.is_empty() || authority.is_empty() || authority.contains('/') {
+        return None;
+    }
+    Some(host_name(authority))
+}
+
+impl OriginPolicy {
+    /// Accepts any origin and host.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts only local origins and hosts, the right default for servers
+    /// bound to a loopback address.
+    pub fn localhost() -> Self {
+        OriginPolicy {
+            localhost_only: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn allow_origin(mut self, origin: impl Into<String>) -> Self {
+        self.allowed_origins.push(origin.into());
+        self
+    }
+
+    pub fn allow_host(mut self, host: impl Into<String>) -> Self {
+        self.allowed_hosts.push(host.into());
+        self
+    }
+
+    /// Whether any check applies; a policy that accepts everything is
+    /// usually a misconfiguration outside of tests.
+    pub fn is_restrictive(&self) -> bool {
+        self.localhost_only || !self.allowed_origins.is_empty() || !self.allowed_hosts.is_empty()
+    }
+
+    /// Checks the `Origin` and `Host` headers of a request.
+    pub fn check(&self, origin: Option<&str>, host: Option<&str>) -> Result<(), OriginRejection> {
+        if let Some(origin) = origin {
+            self.check_origin(origin.trim())?;
+        }
+        if self.localhost_only || !self.allowed_hosts.is_empty() {
+            let host = host.map(str::trim).ok_or(OriginRejection::MissingHost)?;
+            let name = host_name(host);
+            let allowed = (self.localhost_only && is_local(name))
+                || self.allowed_hosts.iter().any(|allowed| {
+                    allowed.eq_ignore_ascii_case(host) || allowed.eq_ignore_ascii_case(name)
+                });
+            if !allowed {
+                return Err(OriginRejection::HostNotAllowed(host.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_origin(&self, origin: &str) -> Result<(), OriginRejection> {
+        if self
+            .allowed_origins
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(origin))
+        {
+            return Ok(());
+        }
+        if !self.localhost_only && self.allowed_origins.is_empty() {
+            return Ok(());
+        }
+        // Sandboxed documents and `file:` pages send the opaque origin "null".
+        if origin == "null" {
+            return Err(OriginRejection::OriginNotAllowed(origin.to_string()));
+        }
+        let host = origin_host(origin)
+            .ok_or_else(|| OriginRejection::MalformedOrigin(origin.to_string()))?;
+        if self.localhost_only && is_local(host) {
+            return Ok(());
+        }
+        Err(OriginRejection::OriginNotAllowed(origin.to_string()))
+    }
+}
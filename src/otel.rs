@@ -0,0 +1,139 @@
+//! Conversion of MCP traffic into OpenTelemetry span attributes and events.
+//!
+//! Attribute names follow the OpenTelemetry semantic conventions for MCP
+//! and JSON-RPC where they exist.
+
+use crate::types::*;
+use opentelemetry::trace::Event;
+use opentelemetry::KeyValue;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Name of the span events produced by [`logging_event`].
+pub const LOG_EVENT_NAME: &str = "mcp.log";
+
+fn id_attribute(id: &RequestId) -> KeyValue {
+    KeyValue::new("jsonrpc.request.id", id.to_string())
+}
+
+/// Attributes describing an outgoing or incoming request: the method, the
+/// id, and for `tools/call` the tool name.
+pub fn request_attributes<T: Serialize>(request: &JSONRPCRequest<T>) -> Vec<KeyValue> {
+    let mut attributes = vec![
+        KeyValue::new("rpc.system", "jsonrpc"),
+        KeyValue::new("mcp.method.name", request.method.to_string()),
+        id_attribute(&request.id),
+    ];
+    if request.method == "tools/call" {
+        let name = serde_json::to_value(&request.params)
+            .ok()
+            .and_then(|params| params.get("name")?.as_str().map(str::to_string));
+        if let Some(name) = name {
+            attributes.push(KeyValue::new("gen_ai.tool.name", name));
+        }
+    }
+    attributes
+}
+
+/// Attributes describing a successful response. A `tools/call` result with
+/// `isError: true` is marked with `error.type = "tool_error"`.
+pub fn response_attributes<U: Serialize>(response: &JSONRPCResponse<U>) -> Vec<KeyValue> {
+    let mut attributes = vec![id_attribute(&response.id)];
+    let is_error = serde_json::to_value(&response.result)
+        .ok()
+        .and_then(|result| result.get("isError").and_then(Value::as_bool))
+        .unwrap_or(false);
+    if is_error {
+        attributes.push(KeyValue::new("error.type", "tool_error"));
+    }
+    attributes
+}
+
+/// Attributes describing an error response. Errors with a null id get no
+/// id attribute.
+pub fn error_attributes(error: &JSONRPCError) -> Vec<KeyValue> {
+    error
+        .id
+        .iter()
+        .map(id_attribute)
+        .chain([
+            KeyValue::new("rpc.jsonrpc.error_code", i64::from(error.error.code)),
+            KeyValue::new("rpc.jsonrpc.error_message", error.error.message.clone()),
+            KeyValue::new("error.type", error.error.code.to_string()),
+        ])
+        .collect()
+}
+
+/// A span event for a `notifications/message` log record, stamped with the
+/// current time. String data is recorded as-is, anything else as JSON.
+pub fn logging_event(params: &LoggingMessageParams) -> Event {
+    let mut attributes = vec![KeyValue::new("mcp.log.level", params.level.to_string())];
+    if let Some(logger) = &params.logger {
+        attributes.push(KeyValue::new("mcp.log.logger", logger.clone()));
+    }
+    let data = match &params.data {
+        Value::String(data) => data.clone(),
+        data => data.to_string(),
+    };
+    attributes.push(KeyValue::new("mcp.log.data", data));
+    Event::new(LOG_EVENT_NAME, SystemTime::now(), attributes, 0)
+}
+
+/// Start times of outstanding requests, so the response can be annotated
+/// with how long the request took.
+///
+/// Requests are tracked when sent and forgotten when their response or
+/// error is recorded.
+#[derive(Debug, Clone, Default)]
+pub struct RequestTimings {
+    started: HashMap<RequestId, (String, Instant)>,
+}
+
+impl RequestTimings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that `request` was sent now.
+    pub fn start<T>(&mut self, request: &JSONRPCRequest<T>) {
+        self.start_at(&request.id, &request.method, Instant::now());
+    }
+
+    pub fn start_at(&mut self, id: &RequestId, method: &str, at: Instant) {
+        self.started.insert(id.clone(), (method.to_string(), at));
+    }
+
+    /// Forgets the request with `id`, returning its method and how long ago
+    /// it started.
+    pub fn finish(&mut self, id: &RequestId) -> Option<(String, Duration)> {
+        self.finish_at(id, Instant::now())
+    }
+
+    pub fn finish_at(&mut self, id: &RequestId, at: Instant) -> Option<(String, Duration)> {
+        let (method, started) = self.started.remove(id)?;
+        Some((method, at.saturating_duration_since(started)))
+    }
+
+    /// Like [`RequestTimings::finish`], as span attributes: the method and
+    /// the duration in seconds.
+    pub fn finish_attributes(&mut self, id: &RequestId) -> Vec<KeyValue> {
+        match self.finish(id) {
+            Some((method, duration)) => vec![
+                KeyValue::new("mcp.method.name", method),
+                KeyValue::new("mcp.request.duration", duration.as_secs_f64()),
+            ],
+            None => Vec::new(),
+        }
+    }
+
+    /// The number of outstanding requests.
+    pub fn len(&self) -> usize {
+        self.started.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.started.is_empty()
+    }
+}
@@ -0,0 +1,166 @@
+//! Correlation of progress notifications with outstanding requests.
+
+use crate::types::*;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+
+/// Where a progress notification should go.
+#[derive(Debug, Clone)]
+pub enum ProgressRoute {
+    /// The notification belongs to this outstanding request.
+    Active(RequestId),
+    /// The notification belongs to a request that was cancelled; it is
+    /// stale and should be ignored.
+    Cancelled(RequestId),
+    /// No tracked request uses this progress token.
+    Unknown,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    id: RequestId,
+    cancelled: bool,
+}
+
+/// Routes `notifications/progress` to the request whose `_meta` carried the
+/// progress token.
+///
+/// Requests are tracked when sent and forgotten when their response
+/// arrives. A cancelled request gets no response, so cancelled requests are
+/// kept only up to a limit (oldest dropped first); until then, late
+/// progress for them is recognized as stale rather than unknown.
+#[derive(Debug, Clone)]
+pub struct ProgressRouter {
+    by_token: HashMap<ProgressToken, Entry>,
+    tokens_by_id: HashMap<RequestId, ProgressToken>,
+    cancelled: VecDeque<RequestId>,
+    cancelled_limit: usize,
+}
+
+impl Default for ProgressRouter {
+    fn default() -> Self {
+        ProgressRouter {
+            by_token: HashMap::new(),
+            tokens_by_id: HashMap::new(),
+            cancelled: VecDeque::new(),
+            cancelled_limit: Self::DEFAULT_CANCELLED_LIMIT,
+        }
+    }
+}
+
+impl ProgressRouter {
+    /// How many cancelled requests are remembered by default.
+    pub const DEFAULT_CANCELLED_LIMIT: usize = 256;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how many cancelled requests are remembered before the oldest
+    /// is forgotten.
+    pub fn with_cancelled_limit(mut self, limit: usize) -> Self {
+        self.cancelled_limit = limit;
+        self.expire_cancelled();
+        self
+    }
+
+    /// Tracks a request that asked for progress with `token`. A request
+    /// previously tracked with the same token, or the same id, is
+    /// forgotten.
+    pub fn track(&mut self, id: RequestId, token: &ProgressToken) {
+        self.forget(&id);
+        if let Some(displaced) = self.by_token.get(token).map(|entry| entry.id.clone()) {
+            self.forget(&displaced);
+        }
+        self.tokens_by_id.insert(id.clone(), token.clone());
+        self.by_token.insert(
+            token.clone(),
+            Entry {
+                id,
+                cancelled: false,
+            },
+        );
+    }
+
+    /// Tracks a request if it carries `params._meta.progressToken`,
+    /// returning the token found.
+    pub fn track_request<R: Serialize>(&mut self, request: &R) -> Option<ProgressToken> {
+        let value = serde_json::to_value(request).ok()?;
+        let id: RequestId = serde_json::from_value(value.get("id")?.clone()).ok()?;
+        let token: ProgressToken = value
+            .get("params")
+            .and_then(|params| params.get("_meta"))
+            .and_then(|meta| meta.get(crate::meta_keys::PROGRESS_TOKEN))
+            .cloned()
+            .and_then(|token: Value| serde_json::from_value(token).ok())?;
+        self.track(id, &token);
+        Some(token)
+    }
+
+    /// Finds the request a progress notification belongs to.
+    pub fn route(&self, params: &ProgressNotificationParams) -> ProgressRoute {
+        match self.by_token.get(&params.progress_token) {
+            Some(entry) if entry.cancelled => ProgressRoute::Cancelled(entry.id.clone()),
+            Some(entry) => ProgressRoute::Active(entry.id.clone()),
+            None => ProgressRoute::Unknown,
+        }
+    }
+
+    /// Marks a request as cancelled; its progress is reported as stale.
+    pub fn cancel(&mut self, id: &RequestId) {
+        let Some(entry) = self.entry_mut(id) else {
+            return;
+        };
+        if !entry.cancelled {
+            entry.cancelled = true;
+            self.cancelled.push_back(id.clone());
+            self.expire_cancelled();
+        }
+    }
+
+    /// Forgets a request once its response (or error) has arrived.
+    pub fn complete(&mut self, id: &RequestId) {
+        self.forget(id);
+    }
+
+    /// Forgets a request, whether or not it was cancelled.
+    pub fn forget(&mut self, id: &RequestId) {
+        let Some(token) = self.tokens_by_id.remove(id) else {
+            return;
+        };
+        if self
+            .by_token
+            .get(&token)
+            .is_some_and(|entry| entry.id == *id)
+        {
+            let entry = self.by_token.remove(&token);
+            if entry.is_some_and(|entry| entry.cancelled) {
+                self.cancelled.retain(|cancelled| cancelled != id);
+            }
+        }
+    }
+
+    /// The entry of `id`, if its token still routes to it.
+    fn entry_mut(&mut self, id: &RequestId) -> Option<&mut Entry> {
+        let token = self.tokens_by_id.get(id)?;
+        self.by_token.get_mut(token).filter(|entry| entry.id == *id)
+    }
+
+    fn expire_cancelled(&mut self) {
+        while self.cancelled.len() > self.cancelled_limit {
+            if let Some(id) = self.cancelled.pop_front() {
+                self.forget(&id);
+            }
+        }
+    }
+
+    /// The number of tracked requests, including cancelled ones.
+    pub fn len(&self) -> usize {
+        self.by_token.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_token.is_empty()
+    }
+}
@@ -0,0 +1,173 @@
+//! Helpers for servers that offer prompts.
+
+use crate::catalog::{Catalog, ListDiff};
+use crate::types::*;
+use std::collections::HashMap;
+use std::fmt;
+
+/// A `{{placeholder}}` referred to an argument that was not supplied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingArg {
+    pub name: String,
+}
+
+impl fmt::Display for MissingArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "missing prompt argument {:?}", self.name)
+    }
+}
+
+impl std::error::Error for MissingArg {}
+
+/// Substitutes `{{argument}}` placeholders in `template` with values from
+/// `args`. Whitespace inside the braces is ignored (`{{ name }}`), and an
+/// unterminated `{{` is kept as literal text.
+pub fn interpolate(template: &str, args: &HashMap<String, String>) -> Result<String, MissingArg> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let name = rest[start + 2..start + 2 + len].trim();
+        match args.get(name) {
+            Some(value) => out.push_str(value),
+            None => {
+                return Err(MissingArg {
+                    name: name.to_string(),
+                })
+            }
+        }
+        rest = &rest[start + 2 + len + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+impl Prompt {
+    /// Renders message templates into a `prompts/get` result.
+    ///
+    /// Every argument the prompt declares as required must be present in
+    /// `args`. Placeholders are substituted in text content and in embedded
+    /// text resources; images and blobs are copied unchanged. The prompt's
+    /// description becomes the result's description.
+    pub fn render(
+        &self,
+        messages_template: &[PromptMessage],
+        args: &HashMap<String, String>,
+    ) -> Result<GetPromptResult, MissingArg> {
+        for argument in self.arguments.iter().flatten() {
+            if argument.required == Some(true) && !args.contains_key(argument.name.as_str()) {
+                return Err(MissingArg {
+                    name: argument.name.to_string(),
+                });
+            }
+        }
+
+        let messages = messages_template
+            .iter()
+            .map(|message| {
+                let content = match &message.content {
+                    PromptContent::Text(text) => PromptContent::Text(TextContent {
+                        text: interpolate(&text.text, args)?,
+                        ..text.clone()
+                    }),
+                    PromptContent::Resource(embedded) => match &embedded.resource {
+                        ResourceContents::Text(resource) => {
+                            PromptContent::Resource(EmbeddedResource {
+                                resource: ResourceContents::Text(TextResourceContents {
+                                    text: interpolate(&resource.text, args)?,
+                                    ..resource.clone()
+                                }),
+                                ..embedded.clone()
+                            })
+                        }
+                        ResourceContents::Blob(_) => message.content.clone(),
+                    },
+                    PromptContent::Image(_) => message.content.clone(),
+                    PromptContent::Audio(_) => message.content.clone(),
+                    PromptContent::ResourceLink(_) => message.content.clone(),
+                };
+                Ok(PromptMessage {
+                    role: message.role.clone(),
+                    content,
+                })
+            })
+            .collect::<Result<_, MissingArg>>()?;
+
+        Ok(GetPromptResult::new(GetPromptPayload {
+            description: self.description.clone(),
+            messages,
+        }))
+    }
+}
+
+/// An in-memory catalog of prompts, keyed by name.
+///
+/// It only holds prompt metadata; rendering is left to the server (e.g.
+/// with [`Prompt::render`]). Lists are ordered by name and paginated with
+/// cursors that survive concurrent changes to the library.
+#[derive(Debug, Clone, Default)]
+pub struct PromptLibrary {
+    prompts: Catalog<Prompt>,
+}
+
+impl PromptLibrary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of prompts per `prompts/list` page.
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.prompts.page_size = page_size;
+        self
+    }
+
+    /// Adds or replaces a prompt, returning the previous definition.
+    pub fn register(&mut self, prompt: Prompt) -> Option<Prompt> {
+        self.prompts.insert(prompt.name.to_string(), prompt)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Prompt> {
+        self.prompts.remove(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Prompt> {
+        self.prompts.get(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.prompts.contains(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.prompts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prompts.len() == 0
+    }
+
+    /// All prompts, ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = &Prompt> {
+        self.prompts.values()
+    }
+
+    /// The `prompts/list` page following `cursor`.
+    pub fn list(&self, cursor: Option<&str>) -> ListPromptsResult {
+        let (items, next_cursor) = self.prompts.page(cursor);
+        Paginated {
+            meta: None,
+            next_cursor,
+            items,
+            extra: HashMap::new(),
+        }
+    }
+
+    /// What changed relative to an earlier snapshot of the library. A
+    /// non-empty diff warrants `notifications/prompts/list_changed`.
+    pub fn diff(&self, previous: &PromptLibrary) -> ListDiff {
+        self.prompts.diff(&previous.prompts)
+    }
+}
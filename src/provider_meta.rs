@@ -0,0 +1,76 @@
+//! Typed views over provider-specific `sampling/createMessage` metadata.
+//!
+//! The spec leaves `CreateMessageParams::metadata` free-form. Clients that
+//! bridge sampling requests to a model provider commonly read provider
+//! options from a key named after the provider; these types give those
+//! entries a shape.
+
+use crate::types::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Provider options stored under [`ProviderSamplingMeta::KEY`] in the
+/// sampling metadata.
+pub trait ProviderSamplingMeta: Serialize + DeserializeOwned {
+    const KEY: &'static str;
+}
+
+/// Options for Anthropic models, under `metadata.anthropic`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnthropicSamplingMeta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl ProviderSamplingMeta for AnthropicSamplingMeta {
+    const KEY: &'static str = "anthropic";
+}
+
+/// Options for OpenAI models, under `metadata.openai`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OpenAiSamplingMeta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<i64>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl ProviderSamplingMeta for OpenAiSamplingMeta {
+    const KEY: &'static str = "openai";
+}
+
+impl CreateMessageParams {
+    /// Reads the options for provider `T`, or `None` if the metadata has no
+    /// entry for it.
+    pub fn metadata_for<T: ProviderSamplingMeta>(&self) -> Result<Option<T>, serde_json::Error> {
+        self.metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get(T::KEY))
+            .map(|value| T::deserialize(value))
+            .transpose()
+    }
+
+    /// Stores the options for provider `T`, replacing any existing entry.
+    pub fn set_metadata_for<T: ProviderSamplingMeta>(
+        &mut self,
+        meta: &T,
+    ) -> Result<&mut Self, serde_json::Error> {
+        let value = serde_json::to_value(meta)?;
+        self.metadata
+            .get_or_insert_with(HashMap::new)
+            .insert(T::KEY.to_string(), value);
+        Ok(self)
+    }
+}
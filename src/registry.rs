@@ -0,0 +1,290 @@
+//! The `server.json` metadata format of the MCP server registry.
+
+use crate::config::is_http_url;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+
+/// `_meta` key under which a publisher adds its own metadata.
+pub const REGISTRY_PUBLISHER_META: &str = "io.modelcontextprotocol.registry/publisher-provided";
+
+/// `_meta` key under which the registry records what it assigned on publish.
+pub const REGISTRY_OFFICIAL_META: &str = "io.modelcontextprotocol.registry/official";
+
+/// A registry entry describing one version of a server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerJson {
+    #[serde(rename = "$schema", skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    /// Reverse-DNS name whose namespace identifies the publisher, e.g.
+    /// `io.github.alice/weather`.
+    pub name: String,
+    pub description: String,
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<Repository>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub website_url: Option<String>,
+    /// Installable packages that run the server locally.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub packages: Vec<Package>,
+    /// Hosted endpoints of the server.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remotes: Vec<Transport>,
+    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
+    pub meta: Option<HashMap<String, Value>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Repository {
+    pub url: String,
+    /// The hosting service, e.g. `"github"`.
+    pub source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Path of the server within a monorepo.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subfolder: Option<String>,
+}
+
+/// A package in a package registry (npm, PyPI, OCI, ...) that runs the server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Package {
+    /// `"npm"`, `"pypi"`, `"oci"`, `"nuget"`, `"mcpb"`, ...
+    pub registry_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registry_base_url: Option<String>,
+    pub identifier: String,
+    /// An exact version; ranges are rejected by [`ServerJson::validate`].
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_sha256: Option<String>,
+    /// The launcher to use, e.g. `"npx"` or `"uvx"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runtime_hint: Option<String>,
+    pub transport: Transport,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub runtime_arguments: Vec<Argument>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub package_arguments: Vec<Argument>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub environment_variables: Vec<KeyValueInput>,
+}
+
+/// How to talk to a package or remote: `stdio`, `streamable-http`, or `sse`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transport {
+    #[serde(rename = "type")]
+    pub r#type: String,
+    /// Required for the HTTP transports.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<KeyValueInput>,
+}
+
+/// A value the user or launcher supplies, such as an argument or an
+/// environment variable.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// A fixed value, which may reference `{variables}`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_required: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_secret: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub choices: Vec<String>,
+}
+
+/// A command-line argument of a package or its runtime.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Argument {
+    #[serde(rename_all = "camelCase")]
+    Positional {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        value_hint: Option<String>,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        is_repeated: bool,
+        #[serde(flatten)]
+        input: Input,
+    },
+    #[serde(rename_all = "camelCase")]
+    Named {
+        /// The flag, e.g. `--port`.
+        name: String,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        is_repeated: bool,
+        #[serde(flatten)]
+        input: Input,
+    },
+}
+
+/// A named input, such as an environment variable or an HTTP header.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyValueInput {
+    pub name: String,
+    #[serde(flatten)]
+    pub input: Input,
+}
+
+/// A problem found by [`ServerJson::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryError {
+    /// The name is not `namespace/name` with a reverse-DNS namespace.
+    InvalidName(String),
+    MissingField(&'static str),
+    /// A version is a range or tag rather than an exact version.
+    VersionRange {
+        field: &'static str,
+        version: String,
+    },
+    /// A transport type is unknown, or an HTTP transport has no URL.
+    InvalidTransport(String),
+    InvalidUrl {
+        field: &'static str,
+        url: String,
+    },
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::InvalidName(name) => write!(f, "invalid server name {:?}", name),
+            RegistryError::MissingField(field) => write!(f, "missing {}", field),
+            RegistryError::VersionRange { field, version } => {
+                write!(f, "{} must be an exact version, not {:?}", field, version)
+            }
+            RegistryError::InvalidTransport(r#type) => {
+                write!(f, "invalid transport {:?}", r#type)
+            }
+            RegistryError::InvalidUrl { field, url } => {
+                write!(f, "{} is not an http(s) URL: {:?}", field, url)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+/// Whether `version` is a range, wildcard, or tag rather than one version.
+pub fn is_version_range(version: &str) -> bool {
+    let version = version.trim();
+    version.is_empty()
+        || version == "latest"
+        || version.contains(['^', '~', '>', '<', '*', '|', ' ', ','])
+        || version
+            .split(['.', '-', '+'])
+            .any(|part| part.eq_ignore_ascii_case("x"))
+}
+
+fn check_version(field: &'static str, version: &str) -> Result<(), RegistryError> {
+    if is_version_range(version) {
+        return Err(RegistryError::VersionRange {
+            field,
+            version: version.to_string(),
+        });
+    }
+    Ok(())
+}
+
+impl Transport {
+    fn validate(&self, field: &'static str) -> Result<(), RegistryError> {
+        match (self.r#type.as_str(), &self.url) {
+            ("stdio", None) => Ok(()),
+            ("streamable-http" | "sse", Some(url)) if is_http_url(url) => Ok(()),
+            ("streamable-http" | "sse", Some(url)) => Err(RegistryError::InvalidUrl {
+                field,
+                url: url.clone(),
+            }),
+            _ => Err(RegistryError::InvalidTransport(self.r#type.clone())),
+        }
+    }
+}
+
+impl ServerJson {
+    /// The publisher namespace, the part of the name before `/`.
+    pub fn namespace(&self) -> Option<&str> {
+        self.name.split_once('/').map(|(namespace, _)| namespace)
+    }
+
+    /// Metadata the publisher attached under [`REGISTRY_PUBLISHER_META`].
+    pub fn publisher_meta(&self) -> Option<&Value> {
+        self.meta.as_ref()?.get(REGISTRY_PUBLISHER_META)
+    }
+
+    /// Metadata the registry assigned under [`REGISTRY_OFFICIAL_META`].
+    pub fn official_meta(&self) -> Option<&Value> {
+        self.meta.as_ref()?.get(REGISTRY_OFFICIAL_META)
+    }
+
+    /// Checks the entry against the registry's publishing rules, returning
+    /// the first problem found.
+    pub fn validate(&self) -> Result<(), RegistryError> {
+        let valid_name = match self.name.split_once('/') {
+            Some((namespace, name)) => {
+                namespace.contains('.')
+                    && namespace.split('.').all(|label| {
+                        !label.is_empty()
+                            && label
+                                .bytes()
+                                .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+                    })
+                    && !name.is_empty()
+                    && name
+                        .bytes()
+                        .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'))
+            }
+            None => false,
+        };
+        if !valid_name {
+            return Err(RegistryError::InvalidName(self.name.clone()));
+        }
+        if self.description.trim().is_empty() {
+            return Err(RegistryError::MissingField("description"));
+        }
+        check_version("version", &self.version)?;
+        if self.packages.is_empty() && self.remotes.is_empty() {
+            return Err(RegistryError::MissingField("packages or remotes"));
+        }
+        for package in &self.packages {
+            if package.identifier.is_empty() {
+                return Err(RegistryError::MissingField("packages[].identifier"));
+            }
+            check_version("packages[].version", &package.version)?;
+            package.transport.validate("packages[].transport.url")?;
+        }
+        for remote in &self.remotes {
+            if remote.r#type == "stdio" {
+                return Err(RegistryError::InvalidTransport(remote.r#type.clone()));
+            }
+            remote.validate("remotes[].url")?;
+        }
+        if let Some(url) = &self.website_url {
+            if !is_http_url(url) {
+                return Err(RegistryError::InvalidUrl {
+                    field: "websiteUrl",
+                    url: url.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
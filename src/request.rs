@@ -0,0 +1,121 @@
+//! The link between request params, their method, and their result.
+
+use crate::method::Method;
+use crate::types::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// A params type bound to the method it is sent with and the result type
+/// that answers it.
+///
+/// Lets a client send any request through one generic function:
+///
+/// ```
+/// use mcp_schema::*;
+/// use serde_json::{json, Value};
+///
+/// fn send<R: McpRequest>(
+///     params: R,
+///     transport: impl FnOnce(Value) -> Value,
+/// ) -> serde_json::Result<R::Result> {
+///     let request = serde_json::to_value(params.into_request(1))?;
+///     let response: JSONRPCResponse<R::Result> = serde_json::from_value(transport(request))?;
+///     Ok(response.result)
+/// }
+///
+/// let tools = send(ListToolsParams::default(), |request| {
+///     assert_eq!(request["method"], "tools/list");
+///     json!({ "jsonrpc": "2.0", "id": 1, "result": { "tools": [] } })
+/// })
+/// .unwrap();
+/// assert!(tools.items.is_empty());
+/// ```
+pub trait McpRequest: Serialize + DeserializeOwned {
+    const METHOD: Method;
+    type Result: Serialize + DeserializeOwned;
+
+    /// Wraps the params in a JSON-RPC request for [`Self::METHOD`].
+    fn into_request(self, id: impl Into<RequestId>) -> JSONRPCRequest<Self> {
+        JSONRPCRequest::new(id, Self::METHOD.as_str(), self)
+    }
+}
+
+impl McpRequest for PingParams {
+    const METHOD: Method = Method::Ping;
+    type Result = EmptyResult;
+}
+
+impl McpRequest for InitializeParams {
+    const METHOD: Method = Method::Initialize;
+    type Result = InitializeResult;
+}
+
+impl McpRequest for CompleteParams {
+    const METHOD: Method = Method::Complete;
+    type Result = CompleteResult;
+}
+
+impl McpRequest for SetLevelParams {
+    const METHOD: Method = Method::SetLevel;
+    type Result = EmptyResult;
+}
+
+impl McpRequest for GetPromptParams {
+    const METHOD: Method = Method::GetPrompt;
+    type Result = GetPromptResult;
+}
+
+impl McpRequest for ListPromptsParams {
+    const METHOD: Method = Method::ListPrompts;
+    type Result = ListPromptsResult;
+}
+
+impl McpRequest for ListResourcesParams {
+    const METHOD: Method = Method::ListResources;
+    type Result = ListResourcesResult;
+}
+
+impl McpRequest for ListResourceTemplatesParams {
+    const METHOD: Method = Method::ListResourceTemplates;
+    type Result = ListResourceTemplatesResult;
+}
+
+impl McpRequest for ReadResourceParams {
+    const METHOD: Method = Method::ReadResource;
+    type Result = ReadResourceResult;
+}
+
+impl McpRequest for SubscribeParams {
+    const METHOD: Method = Method::Subscribe;
+    type Result = EmptyResult;
+}
+
+impl McpRequest for UnsubscribeParams {
+    const METHOD: Method = Method::Unsubscribe;
+    type Result = EmptyResult;
+}
+
+impl McpRequest for CallToolParams {
+    const METHOD: Method = Method::CallTool;
+    type Result = CallToolResult;
+}
+
+impl McpRequest for ListToolsParams {
+    const METHOD: Method = Method::ListTools;
+    type Result = ListToolsResult;
+}
+
+impl McpRequest for CreateMessageParams {
+    const METHOD: Method = Method::CreateMessage;
+    type Result = CreateMessageResult;
+}
+
+impl McpRequest for ListRootsParams {
+    const METHOD: Method = Method::ListRoots;
+    type Result = ListRootsResult;
+}
+
+impl McpRequest for ElicitationCreateParams {
+    const METHOD: Method = Method::ElicitationCreate;
+    type Result = ElicitationCreateResult;
+}
@@ -0,0 +1,106 @@
+//! Allocation of unique request ids and progress tokens.
+
+#[cfg(feature = "uuid")]
+use crate::types::ProgressToken;
+use crate::types::RequestId;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+/// Hands out request ids from an atomic counter, so tasks sharing one
+/// generator never reuse an id. Ids are numbers starting at 1, or strings
+/// such as `"cli-1"` when a prefix is set.
+///
+/// ```
+/// use mcp_schema::{RequestId, RequestIdGenerator};
+///
+/// let ids = RequestIdGenerator::with_prefix("cli-");
+/// assert_eq!(ids.next_id(), RequestId::from("cli-1"));
+/// assert_eq!(ids.next_id(), RequestId::from("cli-2"));
+/// ```
+#[derive(Debug)]
+pub struct RequestIdGenerator {
+    next: AtomicI64,
+    prefix: Option<String>,
+}
+
+impl RequestIdGenerator {
+    pub fn new() -> Self {
+        Self::starting_at(1)
+    }
+
+    /// A generator whose first id is `first`.
+    pub fn starting_at(first: i64) -> Self {
+        RequestIdGenerator {
+            next: AtomicI64::new(first),
+            prefix: None,
+        }
+    }
+
+    /// A generator of string ids: `prefix` followed by the counter.
+    pub fn with_prefix(prefix: impl Into<String>) -> Self {
+        RequestIdGenerator {
+            prefix: Some(prefix.into()),
+            ..Self::new()
+        }
+    }
+
+    pub fn next_id(&self) -> RequestId {
+        let n = self.next.fetch_add(1, Ordering::Relaxed);
+        match &self.prefix {
+            Some(prefix) => RequestId::String(format!("{}{}", prefix, n)),
+            None => RequestId::Number(n),
+        }
+    }
+}
+
+impl Default for RequestIdGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl RequestId {
+    /// A string id holding a random (version 4) UUID, for gateways that
+    /// multiplex several connections and need ids unique across all of them.
+    pub fn new_uuid() -> Self {
+        RequestId::String(uuid::Uuid::new_v4().to_string())
+    }
+
+    /// The id as a UUID, if it is a string in UUID form.
+    pub fn as_uuid(&self) -> Option<uuid::Uuid> {
+        match self {
+            RequestId::String(id) => uuid::Uuid::parse_str(id).ok(),
+            RequestId::Number(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl From<uuid::Uuid> for RequestId {
+    fn from(id: uuid::Uuid) -> Self {
+        RequestId::String(id.to_string())
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl ProgressToken {
+    /// A string token holding a random (version 4) UUID.
+    pub fn new_uuid() -> Self {
+        ProgressToken::String(uuid::Uuid::new_v4().to_string())
+    }
+
+    /// The token as a UUID, if it is a string in UUID form.
+    pub fn as_uuid(&self) -> Option<uuid::Uuid> {
+        match self {
+            ProgressToken::String(token) => uuid::Uuid::parse_str(token).ok(),
+            ProgressToken::Number(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl From<uuid::Uuid> for ProgressToken {
+    fn from(token: uuid::Uuid) -> Self {
+        ProgressToken::String(token.to_string())
+    }
+}
@@ -0,0 +1,240 @@
+//! Helpers for servers that offer resources.
+
+use crate::catalog::Catalog;
+use crate::types::*;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+/// A URI could not be resolved to a resource or resource template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceNotFound {
+    pub uri: String,
+}
+
+impl fmt::Display for ResourceNotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "resource not found: {}", self.uri)
+    }
+}
+
+impl std::error::Error for ResourceNotFound {}
+
+impl From<ResourceNotFound> for RPCErrorDetail {
+    fn from(err: ResourceNotFound) -> Self {
+        RPCErrorDetail::resource_not_found(&err.uri)
+    }
+}
+
+/// The result of resolving a URI against a [`ResourceIndex`].
+#[derive(Debug, Clone)]
+pub enum Resolved<'a> {
+    /// The URI names a listed resource.
+    Resource(&'a Resource),
+    /// The URI matches a resource template; `variables` holds the values
+    /// extracted for the template's placeholders.
+    Template {
+        template: &'a ResourceTemplate,
+        variables: HashMap<String, String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment<'a> {
+    Literal(&'a str),
+    /// A placeholder; `reserved` (`{+name}`) values may contain `/`.
+    Variable {
+        name: &'a str,
+        reserved: bool,
+    },
+}
+
+fn parse_template(template: &str) -> Option<Vec<Segment<'_>>> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        if start > 0 {
+            segments.push(Segment::Literal(&rest[..start]));
+        }
+        let len = rest[start..].find('}')?;
+        let expression = &rest[start + 1..start + len];
+        let (name, reserved) = match expression.strip_prefix('+') {
+            Some(name) => (name, true),
+            None => (expression, false),
+        };
+        if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return None;
+        }
+        segments.push(Segment::Variable { name, reserved });
+        rest = &rest[start + len + 1..];
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Literal(rest));
+    }
+    Some(segments)
+}
+
+fn match_segments(
+    segments: &[Segment<'_>],
+    uri: &str,
+    variables: &mut HashMap<String, String>,
+) -> bool {
+    match segments.split_first() {
+        None => uri.is_empty(),
+        Some((Segment::Literal(literal), rest)) => uri
+            .strip_prefix(literal)
+            .is_some_and(|uri| match_segments(rest, uri, variables)),
+        Some((Segment::Variable { name, reserved }, rest)) => {
+            let limit = if *reserved {
+                uri.len()
+            } else {
+                uri.find('/').unwrap_or(uri.len())
+            };
+            if limit == 0 {
+                return false;
+            }
+            // Prefer the longest value that lets the rest of the template match.
+            let ends = uri[..limit]
+                .char_indices()
+                .map(|(index, _)| index)
+                .skip(1)
+                .chain(std::iter::once(limit))
+                .collect::<Vec<_>>();
+            for end in ends.into_iter().rev() {
+                if match_segments(rest, &uri[end..], variables) {
+                    variables.insert(name.to_string(), uri[..end].to_string());
+                    return true;
+                }
+            }
+            false
+        }
+    }
+}
+
+impl ResourceTemplate {
+    /// Matches `uri` against this template, returning the extracted
+    /// variables.
+    ///
+    /// Supports RFC 6570 simple expansion (`{name}`, which does not match
+    /// `/`) and reserved expansion (`{+name}`, which does). Variables must
+    /// match at least one character. Templates using other operators never
+    /// match.
+    pub fn match_uri(&self, uri: &str) -> Option<HashMap<String, String>> {
+        let segments = parse_template(&self.uri_template)?;
+        let mut variables = HashMap::new();
+        match_segments(&segments, uri, &mut variables).then_some(variables)
+    }
+}
+
+impl ListResourcesResult {
+    /// The combined size in bytes of the resources on this page, for
+    /// budgeting context. Resources that report no size count as zero.
+    pub fn known_size(&self) -> u64 {
+        self.items.iter().filter_map(|resource| resource.size).sum()
+    }
+}
+
+/// An in-memory index of a server's resources and resource templates, with
+/// the client's subscriptions.
+#[derive(Debug, Clone, Default)]
+pub struct ResourceIndex {
+    resources: Catalog<Resource>,
+    templates: Catalog<ResourceTemplate>,
+    subscriptions: BTreeSet<String>,
+}
+
+impl ResourceIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of entries per `resources/list` and
+    /// `resources/templates/list` page.
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.resources.page_size = page_size;
+        self.templates.page_size = page_size;
+        self
+    }
+
+    /// Adds or replaces a resource, keyed by URI.
+    pub fn add_resource(&mut self, resource: Resource) -> Option<Resource> {
+        self.resources.insert(resource.uri.to_string(), resource)
+    }
+
+    pub fn remove_resource(&mut self, uri: &str) -> Option<Resource> {
+        self.resources.remove(uri)
+    }
+
+    /// Adds or replaces a resource template, keyed by its URI template.
+    pub fn add_template(&mut self, template: ResourceTemplate) -> Option<ResourceTemplate> {
+        self.templates
+            .insert(template.uri_template.to_string(), template)
+    }
+
+    pub fn remove_template(&mut self, uri_template: &str) -> Option<ResourceTemplate> {
+        self.templates.remove(uri_template)
+    }
+
+    /// Resolves `uri` to a listed resource or, failing that, to the first
+    /// matching template (in URI template order).
+    pub fn resolve(&self, uri: &str) -> Result<Resolved<'_>, ResourceNotFound> {
+        if let Some(resource) = self.resources.get(uri) {
+            return Ok(Resolved::Resource(resource));
+        }
+        self.templates
+            .values()
+            .find_map(|template| {
+                template.match_uri(uri).map(|variables| Resolved::Template {
+                    template,
+                    variables,
+                })
+            })
+            .ok_or_else(|| ResourceNotFound {
+                uri: uri.to_string(),
+            })
+    }
+
+    /// The `resources/list` page following `cursor`.
+    pub fn list_resources(&self, cursor: Option<&str>) -> ListResourcesResult {
+        let (items, next_cursor) = self.resources.page(cursor);
+        Paginated {
+            meta: None,
+            next_cursor,
+            items,
+            extra: HashMap::new(),
+        }
+    }
+
+    /// The `resources/templates/list` page following `cursor`.
+    pub fn list_templates(&self, cursor: Option<&str>) -> ListResourceTemplatesResult {
+        let (items, next_cursor) = self.templates.page(cursor);
+        Paginated {
+            meta: None,
+            next_cursor,
+            items,
+            extra: HashMap::new(),
+        }
+    }
+
+    /// Handles `resources/subscribe`: the URI must resolve.
+    pub fn subscribe(&mut self, uri: &str) -> Result<(), ResourceNotFound> {
+        self.resolve(uri)?;
+        self.subscriptions.insert(uri.to_string());
+        Ok(())
+    }
+
+    /// Handles `resources/unsubscribe`, returning whether the URI was
+    /// subscribed.
+    pub fn unsubscribe(&mut self, uri: &str) -> bool {
+        self.subscriptions.remove(uri)
+    }
+
+    /// Whether a change to `uri` warrants `notifications/resources/updated`.
+    pub fn is_subscribed(&self, uri: &str) -> bool {
+        self.subscriptions.contains(uri)
+    }
+
+    /// The subscribed URIs, in order.
+    pub fn subscriptions(&self) -> impl Iterator<Item = &str> {
+        self.subscriptions.iter().map(String::as_str)
+    }
+}
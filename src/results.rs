@@ -0,0 +1,164 @@
+//! Conversions between [`ServerResult`] / [`ClientResult`] and the concrete
+//! result types.
+
+use crate::types::*;
+use std::fmt;
+
+/// A response to any client request, as decoded before the method is known.
+pub type AnyServerResponse = JSONRPCResponse<ServerResult>;
+
+/// A response to any server request, as decoded before the method is known.
+pub type AnyClientResponse = JSONRPCResponse<ClientResult>;
+
+/// A [`ServerResult`] was not of the kind the caller expected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnexpectedResult {
+    pub expected: ServerResultKind,
+    pub found: ServerResultKind,
+}
+
+impl fmt::Display for UnexpectedResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected a {} result, got a {} result",
+            self.expected.methods().join("/"),
+            self.found.methods().join("/")
+        )
+    }
+}
+
+impl std::error::Error for UnexpectedResult {}
+
+/// A [`ClientResult`] was not of the kind the caller expected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnexpectedClientResult {
+    pub expected: ClientResultKind,
+    pub found: ClientResultKind,
+}
+
+impl fmt::Display for UnexpectedClientResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected a {} result, got a {} result",
+            self.expected.methods().join("/"),
+            self.found.methods().join("/")
+        )
+    }
+}
+
+impl std::error::Error for UnexpectedClientResult {}
+
+/// Re-decodes a result that holds another variant than the one expected.
+/// Untagged decoding picks the first variant whose shape matches, so the
+/// JSON may still be a valid `T`. An empty result matches any object, so
+/// nothing is re-decoded as one.
+fn redecode<R, T>(result: &R, expected_empty: bool) -> Option<T>
+where
+    R: serde::Serialize,
+    T: serde::de::DeserializeOwned,
+{
+    if expected_empty {
+        return None;
+    }
+    serde_json::to_value(result)
+        .and_then(serde_json::from_value)
+        .ok()
+}
+
+macro_rules! result_conversions {
+    (
+        $union:ident, $kind:ident, $error:ident;
+        $($(#[$attr:meta])* $variant:ident($ty:ty) => $expect:ident;)*
+    ) => {
+        $(
+            #[allow(deprecated)]
+            impl From<$ty> for $union {
+                fn from(result: $ty) -> Self {
+                    $union::$variant(result)
+                }
+            }
+
+            #[allow(deprecated)]
+            impl TryFrom<$union> for $ty {
+                type Error = $error;
+
+                fn try_from(result: $union) -> Result<Self, Self::Error> {
+                    match result {
+                        $union::$variant(typed) => Ok(typed),
+                        other => {
+                            let expected = $kind::$variant;
+                            redecode(&other, expected == $kind::Empty).ok_or($error {
+                                expected,
+                                found: other.kind(),
+                            })
+                        }
+                    }
+                }
+            }
+        )*
+
+        impl $union {
+            $(
+                #[doc = concat!("Converts into a [`", stringify!($ty), "`], or describes what the result was instead.")]
+                $(#[$attr])*
+                pub fn $expect(self) -> Result<$ty, $error> {
+                    self.try_into()
+                }
+            )*
+        }
+    };
+}
+
+result_conversions! {
+    ServerResult, ServerResultKind, UnexpectedResult;
+    Initialize(InitializeResult) => expect_initialize;
+    Complete(CompleteResult) => expect_complete;
+    GetPrompt(GetPromptResult) => expect_get_prompt;
+    ListPrompts(ListPromptsResult) => expect_list_prompts;
+    ListResources(ListResourcesResult) => expect_list_resources;
+    ListResourceTemplates(ListResourceTemplatesResult) => expect_list_resource_templates;
+    ReadResource(ReadResourceResult) => expect_read_resource;
+    CallTool(CallToolResult) => expect_call_tool;
+    ListTools(ListToolsResult) => expect_list_tools;
+    #[deprecated(note = "elicitation/create is answered by clients; use `ClientResult::expect_elicitation_create`")]
+    ElicitationCreate(ElicitationCreateResult) => expect_elicitation_create;
+    Empty(EmptyResult) => expect_empty;
+}
+
+result_conversions! {
+    ClientResult, ClientResultKind, UnexpectedClientResult;
+    CreateMessage(CreateMessageResult) => expect_create_message;
+    ListRoots(ListRootsResult) => expect_list_roots;
+    ElicitationCreate(ElicitationCreateResult) => expect_elicitation_create;
+    Empty(EmptyResult) => expect_empty;
+}
+
+impl AnyServerResponse {
+    /// Converts the result into `T`, keeping the response envelope.
+    pub fn into_typed<T>(self) -> Result<JSONRPCResponse<T>, UnexpectedResult>
+    where
+        T: TryFrom<ServerResult, Error = UnexpectedResult>,
+    {
+        Ok(JSONRPCResponse {
+            json_rpc: self.json_rpc,
+            id: self.id,
+            result: self.result.try_into()?,
+        })
+    }
+}
+
+impl AnyClientResponse {
+    /// Converts the result into `T`, keeping the response envelope.
+    pub fn into_typed<T>(self) -> Result<JSONRPCResponse<T>, UnexpectedClientResult>
+    where
+        T: TryFrom<ClientResult, Error = UnexpectedClientResult>,
+    {
+        Ok(JSONRPCResponse {
+            json_rpc: self.json_rpc,
+            id: self.id,
+            result: self.result.try_into()?,
+        })
+    }
+}
@@ -0,0 +1,212 @@
+//! Resumable SSE streams: event ids, the `Last-Event-ID` header, and a
+//! bounded buffer of sent messages to replay after a reconnect.
+//!
+//! Event ids produced here have the form `{stream}:{sequence}`, so an id
+//! identifies the stream it was sent on; the spec requires a resumed stream
+//! to replay only messages of the stream that was disconnected.
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+/// Header a reconnecting client sends with the id of the last event it saw.
+pub const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+/// The id of an SSE event.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EventId(String);
+
+impl EventId {
+    /// The id of the `sequence`th event on `stream`. SSE ids cannot contain
+    /// line breaks or NUL, so those bytes and `%` are percent-encoded in the
+    /// stream name; [`EventId::parts`] returns the encoded name.
+    pub fn new(stream: &str, sequence: u64) -> Self {
+        EventId(format!("{}:{}", escape_stream(stream), sequence))
+    }
+
+    /// Parses a `Last-Event-ID` header value. SSE ids cannot contain line
+    /// breaks or NUL, and an empty id means the client has none.
+    pub fn from_header(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.is_empty() || value.contains(['\0', '\n', '\r']) {
+            return None;
+        }
+        Some(EventId(value.to_string()))
+    }
+
+    /// The stream and sequence number, for ids made by [`EventId::new`].
+    pub fn parts(&self) -> Option<(&str, u64)> {
+        let (stream, sequence) = self.0.rsplit_once(':')?;
+        Some((stream, sequence.parse().ok()?))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+fn escape_stream(stream: &str) -> Cow<'_, str> {
+    if !stream.contains(['%', '\0', '\n', '\r']) {
+        return Cow::Borrowed(stream);
+    }
+    let mut escaped = String::with_capacity(stream.len() + 8);
+    for c in stream.chars() {
+        match c {
+            '%' | '\0' | '\n' | '\r' => escaped.push_str(&format!("%{:02X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+impl fmt::Display for EventId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Why a stream cannot be resumed from a `Last-Event-ID`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The id was not issued by this buffer.
+    UnknownEventId(EventId),
+    /// Events after the id have already been evicted, so replaying would
+    /// silently lose messages.
+    Evicted(EventId),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::UnknownEventId(id) => write!(f, "unknown event id {}", id),
+            ReplayError::Evicted(id) => write!(f, "events after {} are no longer buffered", id),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+#[derive(Debug, Clone)]
+struct StreamState {
+    next: u64,
+    /// The value of the buffer's push counter when the stream last sent.
+    last_push: u64,
+}
+
+/// Outbound messages of one or more SSE streams, kept so that a client that
+/// reconnects with `Last-Event-ID` receives what it missed.
+///
+/// The buffer holds at most `capacity` messages across all streams; the
+/// oldest are evicted first. A stream is forgotten once it has been idle for
+/// the idle limit, counted in messages pushed on any stream, and resuming it
+/// afterwards fails with [`ReplayError::UnknownEventId`]. A stream's numbering
+/// starts at the number of messages pushed before it, so an event id is never
+/// issued twice, even when a forgotten stream's name is reused.
+#[derive(Debug, Clone)]
+pub struct ReplayBuffer<T> {
+    capacity: usize,
+    idle_limit: u64,
+    pushed: u64,
+    next_sweep: u64,
+    events: VecDeque<(EventId, T)>,
+    streams: HashMap<String, StreamState>,
+}
+
+impl<T> ReplayBuffer<T> {
+    /// A buffer of `capacity` messages, with an idle limit of four times the
+    /// capacity. With a capacity of 0 nothing is kept, so no stream can be
+    /// resumed.
+    pub fn new(capacity: usize) -> Self {
+        ReplayBuffer {
+            capacity,
+            idle_limit: capacity as u64 * 4,
+            pushed: 0,
+            next_sweep: 0,
+            events: VecDeque::new(),
+            streams: HashMap::new(),
+        }
+    }
+
+    /// Sets how many messages may be pushed after a stream's last one before
+    /// the stream is forgotten. Limits below the capacity are raised to it,
+    /// so a stream with buffered messages is never forgotten.
+    pub fn with_idle_limit(mut self, pushes: usize) -> Self {
+        self.idle_limit = pushes.max(self.capacity) as u64;
+        self
+    }
+
+    /// Stores a message sent on `stream`, returning the id to send it with.
+    pub fn push(&mut self, stream: &str, message: T) -> EventId {
+        let clock = self.pushed;
+        self.pushed += 1;
+        let state = self
+            .streams
+            .entry(escape_stream(stream).into_owned())
+            .or_insert(StreamState {
+                next: clock,
+                last_push: clock,
+            });
+        let id = EventId::new(stream, state.next);
+        state.next += 1;
+        state.last_push = clock;
+        // Sweeping once per idle limit keeps at most twice the limit's worth
+        // of streams.
+        if clock == self.next_sweep {
+            let idle_limit = self.idle_limit;
+            self.next_sweep = clock + idle_limit.max(1);
+            self.streams
+                .retain(|_, state| clock - state.last_push < idle_limit);
+        }
+        if self.capacity == 0 {
+            return id;
+        }
+        if self.events.len() == self.capacity {
+            self.events.pop_front();
+        }
+        self.events.push_back((id.clone(), message));
+        id
+    }
+
+    /// The messages sent on the same stream after `last`, oldest first.
+    pub fn replay_after(
+        &self,
+        last: &EventId,
+    ) -> Result<impl Iterator<Item = (&EventId, &T)>, ReplayError> {
+        let unknown = || ReplayError::UnknownEventId(last.clone());
+        let (stream, sequence) = last.parts().ok_or_else(unknown)?;
+        let sent = match self.streams.get(stream) {
+            Some(state) if sequence < state.next => state.next,
+            _ => return Err(unknown()),
+        };
+        let stream = stream.to_string();
+        // The sequence number of `id`, if it was sent on the same stream.
+        let on_stream = move |id: &EventId| match id.parts() {
+            Some((s, n)) if s == stream => Some(n),
+            _ => None,
+        };
+        // Resuming is only lossless if the event right after `last` is still
+        // buffered, or if `last` was the newest event of its stream.
+        let oldest = self.events.iter().find_map(|(id, _)| on_stream(id));
+        let complete = match oldest {
+            Some(oldest) => oldest <= sequence + 1,
+            None => sent == sequence + 1,
+        };
+        if !complete {
+            return Err(ReplayError::Evicted(last.clone()));
+        }
+        Ok(self
+            .events
+            .iter()
+            .filter_map(move |(id, message)| (on_stream(id)? > sequence).then_some((id, message))))
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
@@ -0,0 +1,117 @@
+//! Tracking of the client's roots for servers that handle
+//! `notifications/roots/list_changed`.
+
+use crate::types::*;
+use std::collections::BTreeSet;
+
+/// Whether `uri` is `root` itself or lies underneath it.
+fn is_within(uri: &str, root: &str) -> bool {
+    let root = root.trim_end_matches('/');
+    uri == root
+        || uri
+            .strip_prefix(root)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// The difference between two successive root lists.
+#[derive(Debug, Clone, Default)]
+pub struct RootsChange {
+    /// Roots present now but not before.
+    pub added: Vec<Root>,
+    /// Roots present before but not now.
+    pub removed: Vec<Root>,
+    /// Subscribed URIs that no longer lie within any root.
+    pub invalidated: Vec<String>,
+}
+
+impl RootsChange {
+    /// Whether the server should re-scan its roots.
+    pub fn needs_rescan(&self) -> bool {
+        !self.added.is_empty() || !self.removed.is_empty()
+    }
+
+    /// The subscriptions affected by this change.
+    pub fn affected_subscriptions(&self) -> impl Iterator<Item = &str> {
+        self.invalidated.iter().map(String::as_str)
+    }
+}
+
+/// Keeps the latest `roots/list` result and the server's resource
+/// subscriptions, and works out what changed whenever the client sends a
+/// new root list.
+///
+/// A typical server calls `roots/list` after initialization and after every
+/// `notifications/roots/list_changed`, then feeds the result to
+/// [`RootsWatcher::update`].
+#[derive(Debug, Clone, Default)]
+pub struct RootsWatcher {
+    roots: Vec<Root>,
+    subscriptions: BTreeSet<String>,
+}
+
+impl RootsWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The current roots.
+    pub fn roots(&self) -> &[Root] {
+        &self.roots
+    }
+
+    /// Whether `uri` lies within any current root.
+    pub fn is_within_roots(&self, uri: &str) -> bool {
+        self.roots.iter().any(|root| is_within(uri, &root.uri))
+    }
+
+    /// Records a subscription to `uri`.
+    pub fn subscribe(&mut self, uri: impl Into<String>) {
+        self.subscriptions.insert(uri.into());
+    }
+
+    /// Forgets a subscription, returning whether it existed.
+    pub fn unsubscribe(&mut self, uri: &str) -> bool {
+        self.subscriptions.remove(uri)
+    }
+
+    /// The current subscriptions, in URI order.
+    pub fn subscriptions(&self) -> impl Iterator<Item = &str> {
+        self.subscriptions.iter().map(String::as_str)
+    }
+
+    /// Replaces the roots with those from `result` and reports the change.
+    ///
+    /// Subscriptions that fall outside the new roots are reported as
+    /// invalidated and dropped from the watcher.
+    pub fn update(&mut self, result: &ListRootsResult) -> RootsChange {
+        let added = result
+            .roots
+            .iter()
+            .filter(|root| !self.roots.iter().any(|old| old.uri == root.uri))
+            .cloned()
+            .collect();
+        let removed = self
+            .roots
+            .iter()
+            .filter(|old| !result.roots.iter().any(|root| root.uri == old.uri))
+            .cloned()
+            .collect();
+        self.roots = result.roots.clone();
+
+        let invalidated: Vec<String> = self
+            .subscriptions
+            .iter()
+            .filter(|uri| !self.is_within_roots(uri))
+            .cloned()
+            .collect();
+        for uri in &invalidated {
+            self.subscriptions.remove(uri);
+        }
+
+        RootsChange {
+            added,
+            removed,
+            invalidated,
+        }
+    }
+}
@@ -0,0 +1,83 @@
+//! Method routing with typed payloads.
+
+use crate::request::McpRequest;
+use crate::types::*;
+use serde_json::value::RawValue;
+use std::collections::HashMap;
+use std::fmt;
+
+type Route = Box<dyn Fn(&RawValue) -> Result<Box<RawValue>, RPCErrorDetail> + Send + Sync>;
+
+/// Dispatches raw JSON-RPC requests to handlers registered per method.
+///
+/// Each handler is registered for a [`McpRequest`] type, which fixes the
+/// method it serves, the type its params are decoded into and the type of
+/// result it must produce.
+///
+/// ```
+/// use mcp_schema::*;
+///
+/// let mut router = Router::new();
+/// router.on(|params: ListToolsParams| {
+///     Ok(ToolRegistry::new().list(params.0.cursor.as_deref()))
+/// });
+/// ```
+#[derive(Default)]
+pub struct Router {
+    routes: HashMap<&'static str, Route>,
+}
+
+impl fmt::Debug for Router {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut methods = self.routes.keys().collect::<Vec<_>>();
+        methods.sort();
+        f.debug_struct("Router").field("methods", &methods).finish()
+    }
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `handler` for `P::METHOD`, replacing any earlier handler.
+    pub fn on<P, F>(&mut self, handler: F) -> &mut Self
+    where
+        P: McpRequest,
+        F: Fn(P) -> Result<P::Result, RPCErrorDetail> + Send + Sync + 'static,
+    {
+        let route = move |params: &RawValue| {
+            let params = serde_json::from_str::<P>(params.get())
+                .map_err(|err| RPCErrorDetail::invalid_params(err.to_string()))?;
+            let result = handler(params)?;
+            serde_json::value::to_raw_value(&result)
+                .map_err(|err| RPCErrorDetail::internal_from(&err))
+        };
+        self.routes.insert(P::METHOD.as_str(), Box::new(route));
+        self
+    }
+
+    /// Whether a handler is registered for `method`.
+    pub fn handles(&self, method: &str) -> bool {
+        self.routes.contains_key(method)
+    }
+
+    /// Decodes the request's params for its method's handler, invokes it and
+    /// encodes the result. Missing or `null` params decode as `{}`.
+    pub fn route(
+        &self,
+        request: JSONRPCRequest<Option<Box<RawValue>>>,
+    ) -> Result<JSONRPCResponse<Box<RawValue>>, JSONRPCError> {
+        let result = match self.routes.get(request.method.as_str()) {
+            Some(route) => match request.params.as_deref() {
+                Some(params) => route(params),
+                None => route(&RawValue::from_string("{}".to_string()).expect("valid JSON")),
+            },
+            None => Err(RPCErrorDetail::method_not_found(&request.method)),
+        };
+        match result {
+            Ok(result) => Ok(JSONRPCResponse::new(request.id, result)),
+            Err(error) => Err(JSONRPCError::new(request.id, error)),
+        }
+    }
+}
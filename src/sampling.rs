@@ -0,0 +1,220 @@
+//! Helpers for clients that implement `sampling/createMessage`.
+
+use crate::types::*;
+use std::fmt;
+
+/// A model the client could use to fulfil a sampling request, described by
+/// the same dimensions as [`ModelPreferences`].
+///
+/// `cost`, `speed`, and `intelligence` are normalized to `0.0..=1.0`:
+/// `cost` 0 is the cheapest model, `speed` and `intelligence` 1 are the
+/// fastest and most capable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelCandidate {
+    pub name: String,
+    pub cost: f64,
+    pub speed: f64,
+    pub intelligence: f64,
+}
+
+impl ModelCandidate {
+    pub fn new(name: impl Into<String>, cost: f64, speed: f64, intelligence: f64) -> Self {
+        ModelCandidate {
+            name: name.into(),
+            cost,
+            speed,
+            intelligence,
+        }
+    }
+}
+
+/// The weight of a single hint position. Larger than the maximum priority
+/// score (3.0), so a matching hint always outranks the numeric priorities.
+const HINT_WEIGHT: f64 = 4.0;
+
+impl ModelPreferences {
+    /// Scores `model` against these preferences; higher is better.
+    ///
+    /// The spec asks clients to favour hints over numeric priorities, so the
+    /// score has two parts:
+    ///
+    /// - hints: if any hint matches the model name, the earliest matching
+    ///   hint contributes `4.0 * (hints.len() - index)`;
+    /// - priorities: `cost_priority * (1 - cost) + speed_priority * speed +
+    ///   intelligence_priority * intelligence`, with missing priorities
+    ///   treated as 0.
+    pub fn score(&self, model: &ModelCandidate) -> f64 {
+        let hints = self.hints.as_deref().unwrap_or_default();
+        let hint_score = hints
+            .iter()
+            .position(|hint| hint.matches(&model.name))
+            .map(|index| HINT_WEIGHT * (hints.len() - index) as f64)
+            .unwrap_or(0.0);
+
+        let priority = |value: Option<f64>| value.unwrap_or(0.0).clamp(0.0, 1.0);
+        let priority_score = priority(self.cost_priority) * (1.0 - model.cost.clamp(0.0, 1.0))
+            + priority(self.speed_priority) * model.speed.clamp(0.0, 1.0)
+            + priority(self.intelligence_priority) * model.intelligence.clamp(0.0, 1.0);
+
+        hint_score + priority_score
+    }
+}
+
+/// Picks the highest-scoring candidate under `prefs`, preferring earlier
+/// candidates on ties. Returns `None` if there are no candidates.
+pub fn select_model<'a>(
+    candidates: &'a [ModelCandidate],
+    prefs: &ModelPreferences,
+) -> Option<&'a ModelCandidate> {
+    let mut best: Option<(&ModelCandidate, f64)> = None;
+    for candidate in candidates {
+        let score = prefs.score(candidate);
+        let better = match best {
+            Some((_, best_score)) => score > best_score,
+            None => true,
+        };
+        if better {
+            best = Some((candidate, score));
+        }
+    }
+    best.map(|(candidate, _)| candidate)
+}
+
+/// Estimates how many tokens a piece of text occupies in a model's context.
+pub trait TokenEstimator {
+    fn estimate(&self, text: &str) -> usize;
+}
+
+/// A rough [`TokenEstimator`] dividing the character count by a fixed
+/// ratio, four characters per token by default.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CharCountEstimator {
+    pub chars_per_token: f64,
+}
+
+impl Default for CharCountEstimator {
+    fn default() -> Self {
+        CharCountEstimator {
+            chars_per_token: 4.0,
+        }
+    }
+}
+
+impl TokenEstimator for CharCountEstimator {
+    fn estimate(&self, text: &str) -> usize {
+        (text.chars().count() as f64 / self.chars_per_token).ceil() as usize
+    }
+}
+
+/// The most stop sequences [`CreateMessageParams::validate`] accepts.
+pub const MAX_STOP_SEQUENCES: usize = 16;
+
+/// The longest stop sequence, in characters, that
+/// [`CreateMessageParams::validate`] accepts.
+pub const MAX_STOP_SEQUENCE_CHARS: usize = 256;
+
+/// A sampling request that cannot be forwarded as it stands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SamplingError {
+    /// `maxTokens` must be positive.
+    NonPositiveMaxTokens(i64),
+    /// A stop sequence is the empty string.
+    EmptyStopSequence,
+    /// A stop sequence appears more than once.
+    DuplicateStopSequence(String),
+    /// There are more than [`MAX_STOP_SEQUENCES`] stop sequences.
+    TooManyStopSequences(usize),
+    /// A stop sequence is longer than [`MAX_STOP_SEQUENCE_CHARS`].
+    StopSequenceTooLong(String),
+}
+
+impl fmt::Display for SamplingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SamplingError::NonPositiveMaxTokens(max_tokens) => {
+                write!(f, "maxTokens must be positive, got {}", max_tokens)
+            }
+            SamplingError::EmptyStopSequence => f.write_str("stop sequence is empty"),
+            SamplingError::DuplicateStopSequence(sequence) => {
+                write!(f, "duplicate stop sequence {:?}", sequence)
+            }
+            SamplingError::TooManyStopSequences(count) => write!(
+                f,
+                "{} stop sequences, at most {} are allowed",
+                count, MAX_STOP_SEQUENCES
+            ),
+            SamplingError::StopSequenceTooLong(sequence) => write!(
+                f,
+                "stop sequence {:?} is longer than {} characters",
+                sequence, MAX_STOP_SEQUENCE_CHARS
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SamplingError {}
+
+impl CreateMessageParams {
+    /// Estimates the prompt size: the system prompt plus every text block.
+    /// Images and audio are not counted.
+    pub fn estimate_tokens(&self, estimator: &dyn TokenEstimator) -> usize {
+        let system = self
+            .system_prompt
+            .as_deref()
+            .map_or(0, |prompt| estimator.estimate(prompt));
+        let messages: usize = self
+            .messages
+            .iter()
+            .flat_map(SamplingMessage::contents)
+            .map(|content| match content {
+                SamplingContent::Text(text) => estimator.estimate(&text.text),
+                SamplingContent::Image(_) => 0,
+                SamplingContent::Audio(_) => 0,
+            })
+            .sum();
+        system + messages
+    }
+
+    /// Whether the estimated prompt plus `maxTokens` fits in a context
+    /// window of `context_tokens`.
+    pub fn fits_context(&self, estimator: &dyn TokenEstimator, context_tokens: usize) -> bool {
+        let max_tokens = usize::try_from(self.max_tokens).unwrap_or(0);
+        self.estimate_tokens(estimator).saturating_add(max_tokens) <= context_tokens
+    }
+
+    /// Checks the request for values providers are known to reject.
+    pub fn validate(&self) -> Result<(), SamplingError> {
+        if self.max_tokens <= 0 {
+            return Err(SamplingError::NonPositiveMaxTokens(self.max_tokens));
+        }
+        let sequences = self.stop_sequences.as_deref().unwrap_or_default();
+        if sequences.len() > MAX_STOP_SEQUENCES {
+            return Err(SamplingError::TooManyStopSequences(sequences.len()));
+        }
+        for (index, sequence) in sequences.iter().enumerate() {
+            if sequence.is_empty() {
+                return Err(SamplingError::EmptyStopSequence);
+            }
+            if sequence.chars().count() > MAX_STOP_SEQUENCE_CHARS {
+                return Err(SamplingError::StopSequenceTooLong(sequence.clone()));
+            }
+            if sequences[..index].contains(sequence) {
+                return Err(SamplingError::DuplicateStopSequence(sequence.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes empty and duplicate stop sequences, keeping the first
+    /// occurrence of each, and clears the list if nothing is left.
+    /// Whitespace is significant and left untouched.
+    pub fn normalize(&mut self) {
+        if let Some(sequences) = &mut self.stop_sequences {
+            let mut seen = std::collections::HashSet::new();
+            sequences.retain(|sequence| !sequence.is_empty() && seen.insert(sequence.clone()));
+            if sequences.is_empty() {
+                self.stop_sequences = None;
+            }
+        }
+    }
+}
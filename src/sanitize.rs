@@ -0,0 +1,102 @@
+//! Sanitization of text produced by subprocesses before it reaches a host UI.
+
+/// Which sanitization steps to apply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SanitizeOptions {
+    /// Remove ANSI escape sequences (colors, cursor movement, titles, ...).
+    pub strip_ansi: bool,
+    /// Convert `\r\n` and lone `\r` to `\n`.
+    pub normalize_line_endings: bool,
+    /// Remove control characters other than `\n` and `\t`.
+    pub remove_control_chars: bool,
+}
+
+impl Default for SanitizeOptions {
+    fn default() -> Self {
+        SanitizeOptions {
+            strip_ansi: true,
+            normalize_line_endings: true,
+            remove_control_chars: false,
+        }
+    }
+}
+
+/// Applies the steps selected in `options` to `text`.
+pub fn sanitize(text: &str, options: &SanitizeOptions) -> String {
+    let mut text = if options.strip_ansi {
+        strip_ansi(text)
+    } else {
+        text.to_string()
+    };
+    if options.normalize_line_endings {
+        text = normalize_line_endings(&text);
+    }
+    if options.remove_control_chars {
+        text = remove_control_chars(&text);
+    }
+    text
+}
+
+/// Removes ANSI escape sequences: CSI sequences (`ESC [ ... final`, or the
+/// 8-bit `U+009B` introducer), OSC sequences (`ESC ] ...` terminated by BEL
+/// or `ESC \`), nF escapes (`ESC`, intermediate bytes `0x20..=0x2F`, then a
+/// final byte, such as the charset selection `ESC ( B`), and two-character
+/// escapes.
+pub fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\u{1b}' => match chars.next() {
+                Some('[') => skip_csi(&mut chars),
+                Some(']') => skip_osc(&mut chars),
+                Some('\u{20}'..='\u{2f}') => skip_nf(&mut chars),
+                _ => {}
+            },
+            '\u{9b}' => skip_csi(&mut chars),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn skip_csi(chars: &mut impl Iterator<Item = char>) {
+    for c in chars.by_ref() {
+        if ('\u{40}'..='\u{7e}').contains(&c) {
+            break;
+        }
+    }
+}
+
+fn skip_nf(chars: &mut impl Iterator<Item = char>) {
+    for c in chars.by_ref() {
+        if !('\u{20}'..='\u{2f}').contains(&c) {
+            break;
+        }
+    }
+}
+
+fn skip_osc(chars: &mut std::iter::Peekable<impl Iterator<Item = char>>) {
+    while let Some(c) = chars.next() {
+        match c {
+            '\u{7}' => break,
+            '\u{1b}' if chars.peek() == Some(&'\\') => {
+                chars.next();
+                break;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Converts `\r\n` and lone `\r` line endings to `\n`.
+pub fn normalize_line_endings(text: &str) -> String {
+    text.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+/// Removes control characters, keeping newlines and tabs.
+pub fn remove_control_chars(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_control() || matches!(c, '\n' | '\t'))
+        .collect()
+}
@@ -0,0 +1,122 @@
+//! Typed URI schemes for resources and roots, for readable scheme-based
+//! policy decisions.
+
+use crate::types::*;
+use std::fmt;
+
+/// The scheme of a resource or root URI.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ResourceScheme {
+    File,
+    Http,
+    Https,
+    Git,
+    /// Any other scheme, lowercased (e.g. `postgres`, `screen`).
+    Custom(String),
+}
+
+impl ResourceScheme {
+    /// Extracts the scheme of `uri`, or `None` if it has no valid scheme.
+    ///
+    /// Per RFC 3986 a scheme starts with a letter followed by letters,
+    /// digits, `+`, `-`, or `.`, and is compared case-insensitively.
+    pub fn of(uri: &str) -> Option<Self> {
+        let (scheme, _) = uri.split_once(':')?;
+        let mut chars = scheme.chars();
+        let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+            && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+        if !valid {
+            return None;
+        }
+        Some(match scheme.to_ascii_lowercase().as_str() {
+            "file" => ResourceScheme::File,
+            "http" => ResourceScheme::Http,
+            "https" => ResourceScheme::Https,
+            "git" => ResourceScheme::Git,
+            other => ResourceScheme::Custom(other.to_string()),
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            ResourceScheme::File => "file",
+            ResourceScheme::Http => "http",
+            ResourceScheme::Https => "https",
+            ResourceScheme::Git => "git",
+            ResourceScheme::Custom(scheme) => scheme,
+        }
+    }
+
+    /// Whether the scheme is `http` or `https`.
+    pub fn is_web(&self) -> bool {
+        matches!(self, ResourceScheme::Http | ResourceScheme::Https)
+    }
+}
+
+impl fmt::Display for ResourceScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Something identified by a URI with a [`ResourceScheme`].
+pub trait HasScheme {
+    fn scheme(&self) -> Option<ResourceScheme>;
+}
+
+impl Resource {
+    pub fn scheme(&self) -> Option<ResourceScheme> {
+        ResourceScheme::of(&self.uri)
+    }
+}
+
+impl Root {
+    pub fn scheme(&self) -> Option<ResourceScheme> {
+        ResourceScheme::of(&self.uri)
+    }
+}
+
+impl HasScheme for Resource {
+    fn scheme(&self) -> Option<ResourceScheme> {
+        Resource::scheme(self)
+    }
+}
+
+impl HasScheme for Root {
+    fn scheme(&self) -> Option<ResourceScheme> {
+        Root::scheme(self)
+    }
+}
+
+impl HasScheme for ResourceContents {
+    fn scheme(&self) -> Option<ResourceScheme> {
+        match self {
+            ResourceContents::Text(contents) => ResourceScheme::of(&contents.uri),
+            ResourceContents::Blob(contents) => ResourceScheme::of(&contents.uri),
+        }
+    }
+}
+
+impl<T: HasScheme + ?Sized> HasScheme for &T {
+    fn scheme(&self) -> Option<ResourceScheme> {
+        (**self).scheme()
+    }
+}
+
+/// Iterator adapter filtering items by URI scheme, e.g.
+/// `resources.iter().filter_scheme(ResourceScheme::File)`.
+pub trait FilterSchemeExt: Iterator + Sized
+where
+    Self::Item: HasScheme,
+{
+    fn filter_scheme(self, scheme: ResourceScheme) -> impl Iterator<Item = Self::Item> {
+        self.filter(move |item| item.scheme().as_ref() == Some(&scheme))
+    }
+}
+
+impl<I> FilterSchemeExt for I
+where
+    I: Iterator,
+    I::Item: HasScheme,
+{
+}
@@ -0,0 +1,74 @@
+//! OAuth scopes required to call a tool.
+//!
+//! Gateways that authorize per tool read the scopes from the tool's `_meta`
+//! under [`meta_keys::REQUIRED_SCOPES`] and compare them with the scopes of
+//! the caller's access token.
+
+use crate::auth::Challenge;
+use crate::extras::HasMeta;
+use crate::meta_keys;
+use crate::types::Tool;
+use serde::{Deserialize, Serialize};
+
+/// Scopes that must all be granted, serialized as a JSON array of strings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RequiredScopes(pub Vec<String>);
+
+impl RequiredScopes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a space-separated OAuth `scope` value.
+    pub fn from_scope_str(scope: &str) -> Self {
+        RequiredScopes(scope.split_whitespace().map(str::to_string).collect())
+    }
+
+    pub fn scope(mut self, scope: impl Into<String>) -> Self {
+        self.0.push(scope.into());
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+
+    /// The required scopes that `granted` lacks.
+    pub fn missing<'a, S: AsRef<str>>(&'a self, granted: &[S]) -> Vec<&'a str> {
+        self.iter()
+            .filter(|scope| !granted.iter().any(|g| g.as_ref() == *scope))
+            .collect()
+    }
+
+    /// Whether `granted` includes every required scope. Scope comparison is
+    /// exact and case-sensitive, as in RFC 6749.
+    pub fn is_satisfied_by<S: AsRef<str>>(&self, granted: &[S]) -> bool {
+        self.missing(granted).is_empty()
+    }
+
+    /// The `insufficient_scope` challenge (RFC 6750) to answer with when the
+    /// caller's token lacks these scopes.
+    pub fn challenge(&self) -> Challenge {
+        Challenge::bearer()
+            .param("error", "insufficient_scope")
+            .param("scope", self.0.join(" "))
+    }
+}
+
+impl Tool {
+    /// The scopes a caller needs to use the tool, if it declares any.
+    pub fn required_scopes(&self) -> Option<RequiredScopes> {
+        self.get_meta_key(meta_keys::REQUIRED_SCOPES)
+    }
+
+    pub fn with_required_scopes(mut self, scopes: RequiredScopes) -> Self {
+        self.set_meta_key(meta_keys::REQUIRED_SCOPES, scopes)
+            .expect("scopes serialize");
+        self
+    }
+}
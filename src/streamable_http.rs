@@ -0,0 +1,385 @@
+//! axum glue for the Streamable HTTP transport: an extractor for the body of
+//! a client POST and a response type that answers it with a JSON body or an
+//! SSE stream.
+
+use crate::batch::JSONRPCBatchResponseItem;
+use crate::method::Method;
+use crate::origin::OriginRejection;
+use crate::resume::{EventId, LAST_EVENT_ID_HEADER};
+use crate::types::*;
+use crate::version::ProtocolVersion;
+use axum::body::Bytes;
+use axum::extract::{FromRequest, Request};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::sse::{Event, Sse};
+use axum::response::{IntoResponse, Response};
+use futures_util::stream::{BoxStream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::convert::Infallible;
+use std::fmt;
+
+/// Header carrying the session id assigned by the server at initialization.
+pub const SESSION_ID_HEADER: &str = "mcp-session-id";
+
+/// Header carrying the protocol version negotiated at initialization.
+pub const PROTOCOL_VERSION_HEADER: &str = "mcp-protocol-version";
+
+/// One message a client can POST.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ClientMessage {
+    Request(ClientRequest),
+    Notification(ClientNotification),
+    /// A response to a request the server sent the client.
+    Response(JSONRPCBatchResponseItem),
+}
+
+impl ClientMessage {
+    fn decode(value: Value) -> Result<Self, serde_json::Error> {
+        match (value.get("method"), value.get("id")) {
+            (Some(_), Some(_)) => ClientRequest::deserialize(value).map(ClientMessage::Request),
+            (Some(_), None) => {
+                ClientNotification::deserialize(value).map(ClientMessage::Notification)
+            }
+            (None, _) => JSONRPCBatchResponseItem::deserialize(value).map(ClientMessage::Response),
+        }
+    }
+}
+
+/// One message a server can send over an SSE stream.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ServerMessage {
+    Request(ServerRequest),
+    Notification(ServerNotification),
+    Response(JSONRPCBatchResponseItem<ServerResult>),
+}
+
+/// The body of a Streamable HTTP POST, with the headers that shape the reply.
+#[derive(Debug, Clone)]
+pub struct McpPost {
+    /// The `Mcp-Session-Id` header, absent before initialization.
+    pub session_id: Option<String>,
+    /// The `MCP-Protocol-Version` header, or 2025-03-26 when it is absent,
+    /// as the spec directs for clients that predate the header.
+    pub protocol_version: ProtocolVersion,
+    /// The valid messages of the body, in order.
+    pub messages: Vec<ClientMessage>,
+    /// Errors answering the batch entries that are not valid client
+    /// messages, to send along with the responses to `messages`.
+    pub errors: Vec<JSONRPCError>,
+    /// Whether the body was a JSON-RPC batch rather than a single message.
+    pub batch: bool,
+}
+
+/// The `Last-Event-ID` header of a request resuming an SSE stream.
+pub fn last_event_id(headers: &HeaderMap) -> Option<EventId> {
+    EventId::from_header(headers.get(LAST_EVENT_ID_HEADER)?.to_str().ok()?)
+}
+
+impl McpPost {
+    /// Whether any message is a request, or any entry was rejected, and so
+    /// needs a JSON or SSE reply rather than `202 Accepted`.
+    pub fn has_requests(&self) -> bool {
+        !self.errors.is_empty()
+            || self
+                .messages
+                .iter()
+                .any(|message| matches!(message, ClientMessage::Request(_)))
+    }
+}
+
+/// Why a POST was rejected before reaching the server logic.
+#[derive(Debug)]
+pub enum McpRejection {
+    /// The body was not sent as `application/json`.
+    UnsupportedMediaType,
+    /// The client does not accept both `application/json` and
+    /// `text/event-stream`.
+    NotAcceptable,
+    /// The `MCP-Protocol-Version` header names no supported revision.
+    UnsupportedProtocolVersion(String),
+    /// The body is a batch, which the negotiated revision removed.
+    BatchNotSupported(ProtocolVersion),
+    /// The body could not be read.
+    Body(String),
+    /// The body is not JSON, or not a valid client message.
+    InvalidMessage(serde_json::Error),
+}
+
+impl fmt::Display for McpRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            McpRejection::UnsupportedMediaType => f.write_str("expected application/json"),
+            McpRejection::NotAcceptable => {
+                f.write_str("client must accept application/json and text/event-stream")
+            }
+            McpRejection::UnsupportedProtocolVersion(version) => {
+                write!(f, "unsupported protocol version: {}", version)
+            }
+            McpRejection::BatchNotSupported(version) => {
+                write!(f, "batches are not supported in {}", version)
+            }
+            McpRejection::Body(err) => write!(f, "failed to read body: {}", err),
+            McpRejection::InvalidMessage(err) => write!(f, "invalid message: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for McpRejection {}
+
+impl IntoResponse for McpRejection {
+    /// Malformed bodies are answered with a JSON-RPC error (with a null
+    /// id, since none could be read); other rejections with a bare status.
+    fn into_response(self) -> Response {
+        let status = match &self {
+            McpRejection::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            McpRejection::NotAcceptable => StatusCode::NOT_ACCEPTABLE,
+            McpRejection::UnsupportedProtocolVersion(_) | McpRejection::Body(_) => {
+                StatusCode::BAD_REQUEST
+            }
+            McpRejection::BatchNotSupported(_) | McpRejection::InvalidMessage(_) => {
+                let code = match &self {
+                    McpRejection::InvalidMessage(err) if err.is_syntax() || err.is_eof() => {
+                        PARSE_ERROR
+                    }
+                    _ => INVALID_REQUEST,
+                };
+                let body = serde_json::json!({
+                    "jsonrpc": JSONRPC_VERSION,
+                    "id": null,
+                    "error": { "code": code, "message": self.to_string() },
+                });
+                return json_response(StatusCode::BAD_REQUEST, &body);
+            }
+        };
+        (status, self.to_string()).into_response()
+    }
+}
+
+impl IntoResponse for OriginRejection {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.http_status()).unwrap_or(StatusCode::FORBIDDEN);
+        (status, self.to_string()).into_response()
+    }
+}
+
+fn media_type(value: &str) -> &str {
+    value.split(';').next().unwrap_or("").trim()
+}
+
+/// Which of JSON and SSE the `Accept` header allows. A missing header
+/// allows neither, since the spec requires clients to list both.
+fn accepts(headers: &HeaderMap) -> (bool, bool) {
+    let values = headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(media_type);
+    let (mut json, mut sse) = (false, false);
+    for value in values {
+        match value {
+            "*/*" => (json, sse) = (true, true),
+            "application/*" | "application/json" => json = true,
+            "text/*" | "text/event-stream" => sse = true,
+            _ => {}
+        }
+    }
+    (json, sse)
+}
+
+impl<S: Send + Sync> FromRequest<S> for McpPost {
+    type Rejection = McpRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let headers = req.headers();
+        let content_type = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(media_type);
+        if content_type != Some("application/json") {
+            return Err(McpRejection::UnsupportedMediaType);
+        }
+        let (accepts_json, accepts_sse) = accepts(headers);
+        if !(accepts_json && accepts_sse) {
+            return Err(McpRejection::NotAcceptable);
+        }
+        let session_id = headers
+            .get(SESSION_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let protocol_version = match headers.get(PROTOCOL_VERSION_HEADER) {
+            None => ProtocolVersion::V2025_03_26,
+            Some(value) => {
+                let value = String::from_utf8_lossy(value.as_bytes());
+                value
+                    .parse()
+                    .map_err(|_| McpRejection::UnsupportedProtocolVersion(value.into_owned()))?
+            }
+        };
+
+        let body = Bytes::from_request(req, state)
+            .await
+            .map_err(|err| McpRejection::Body(err.body_text()))?;
+        let value: Value = serde_json::from_slice(&body).map_err(McpRejection::InvalidMessage)?;
+        let (values, batch) = match value {
+            Value::Array(_) if protocol_version >= ProtocolVersion::V2025_06_18 => {
+                return Err(McpRejection::BatchNotSupported(protocol_version));
+            }
+            Value::Array(values) => (values, true),
+            value => (vec![value], false),
+        };
+        let mut messages = Vec::with_capacity(values.len());
+        let mut errors = Vec::new();
+        for value in values {
+            let error = match ClientMessage::decode(value.clone()) {
+                Ok(message) => {
+                    messages.push(message);
+                    continue;
+                }
+                Err(err) if !batch => return Err(McpRejection::InvalidMessage(err)),
+                Err(err) => entry_error(&value, err),
+            };
+            errors.push(error);
+        }
+        Ok(McpPost {
+            session_id,
+            protocol_version,
+            messages,
+            errors,
+            batch,
+        })
+    }
+}
+
+/// The error answering a batch entry that failed to decode: method-not-found
+/// for methods servers do not handle, invalid-params for the ones they do,
+/// and invalid-request for anything else.
+fn entry_error(value: &Value, err: serde_json::Error) -> JSONRPCError {
+    let id = value
+        .get("id")
+        .and_then(|id| RequestId::deserialize(id).ok());
+    let error = match value.get("method").and_then(Value::as_str) {
+        Some(method) => match method.parse::<Method>() {
+            Ok(known) if known.is_client_request() || known.is_notification() => {
+                RPCErrorDetail::invalid_params(err.to_string())
+            }
+            _ => RPCErrorDetail::method_not_found(method),
+        },
+        None => RPCErrorDetail::invalid_request(err.to_string()),
+    };
+    match id {
+        Some(id) => JSONRPCError::new(id, error),
+        None => JSONRPCError::without_id(error),
+    }
+}
+
+fn json_response<T: Serialize>(status: StatusCode, body: &T) -> Response {
+    match serde_json::to_vec(body) {
+        Ok(body) => (status, [(header::CONTENT_TYPE, "application/json")], body).into_response(),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+    }
+}
+
+enum Body {
+    Accepted,
+    Json(Value),
+    Sse(BoxStream<'static, (Option<EventId>, ServerMessage)>),
+}
+
+/// The reply to a Streamable HTTP POST.
+pub struct McpResponse {
+    session_id: Option<String>,
+    body: Body,
+}
+
+impl McpResponse {
+    /// `202 Accepted` with no body, the reply to a POST carrying only
+    /// notifications and responses.
+    pub fn accepted() -> Self {
+        McpResponse {
+            session_id: None,
+            body: Body::Accepted,
+        }
+    }
+
+    /// A single JSON body: one response, or a batch response.
+    pub fn json<T: Serialize>(message: &T) -> Result<Self, serde_json::Error> {
+        Ok(McpResponse {
+            session_id: None,
+            body: Body::Json(serde_json::to_value(message)?),
+        })
+    }
+
+    /// An SSE stream, one event per message. The server should end the
+    /// stream once every request of the POST has been answered.
+    pub fn sse(messages: impl Stream<Item = ServerMessage> + Send + 'static) -> Self {
+        McpResponse {
+            session_id: None,
+            body: Body::Sse(messages.map(|message| (None, message)).boxed()),
+        }
+    }
+
+    /// An SSE stream whose events carry ids, so a client can resume it with
+    /// `Last-Event-ID`; see [`crate::ReplayBuffer`].
+    pub fn resumable_sse(
+        messages: impl Stream<Item = (EventId, ServerMessage)> + Send + 'static,
+    ) -> Self {
+        McpResponse {
+            session_id: None,
+            body: Body::Sse(messages.map(|(id, message)| (Some(id), message)).boxed()),
+        }
+    }
+
+    /// Sets the `Mcp-Session-Id` header, as the server does when answering
+    /// `initialize`.
+    pub fn session_id(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+}
+
+impl fmt::Debug for McpResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let body = match &self.body {
+            Body::Accepted => "Accepted",
+            Body::Json(_) => "Json",
+            Body::Sse(_) => "Sse",
+        };
+        f.debug_struct("McpResponse")
+            .field("session_id", &self.session_id)
+            .field("body", &body)
+            .finish()
+    }
+}
+
+impl IntoResponse for McpResponse {
+    fn into_response(self) -> Response {
+        let mut response = match self.body {
+            Body::Accepted => StatusCode::ACCEPTED.into_response(),
+            Body::Json(body) => json_response(StatusCode::OK, &body),
+            Body::Sse(messages) => {
+                let events = messages.map(|(id, message)| {
+                    let data = serde_json::to_string(&message).expect("messages serialize");
+                    let event = Event::default().event("message").data(data);
+                    Ok::<_, Infallible>(match id {
+                        Some(id) => event.id(id.as_str()),
+                        None => event,
+                    })
+                });
+                Sse::new(events).into_response()
+            }
+        };
+        if let Some(session_id) = self
+            .session_id
+            .and_then(|id| HeaderValue::from_str(&id).ok())
+        {
+            response.headers_mut().insert(SESSION_ID_HEADER, session_id);
+        }
+        response
+    }
+}
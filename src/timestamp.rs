@@ -0,0 +1,212 @@
+//! Timestamps, such as the `lastModified` annotation.
+//!
+//! [`Timestamp`] is the ISO 8601 string as sent, checked when parsed. With
+//! the `chrono` or `time` feature it converts to and from that crate's
+//! date-time type.
+
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// An ISO 8601 date and time with an offset, e.g.
+/// `2025-01-12T15:00:58Z`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(transparent)]
+pub struct Timestamp(String);
+
+impl Timestamp {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The current system time in UTC, with millisecond precision.
+    pub fn now() -> Self {
+        Timestamp::from(SystemTime::now())
+    }
+
+    /// The timestamp as a `chrono` date-time in UTC. Fails for strings the
+    /// ISO 8601 check lets through but that name no real instant, such as
+    /// February 30th.
+    #[cfg(feature = "chrono")]
+    pub fn to_chrono(&self) -> Result<chrono::DateTime<chrono::Utc>, InvalidTimestamp> {
+        chrono::DateTime::parse_from_rfc3339(&self.0)
+            .map(|time| time.with_timezone(&chrono::Utc))
+            .map_err(|_| InvalidTimestamp(self.0.clone()))
+    }
+
+    /// The timestamp as a `time` date-time, keeping its offset. Fails like
+    /// [`to_chrono`](Self::to_chrono).
+    #[cfg(feature = "time")]
+    pub fn to_time(&self) -> Result<time::OffsetDateTime, InvalidTimestamp> {
+        time::OffsetDateTime::parse(&self.0, &time::format_description::well_known::Rfc3339)
+            .map_err(|_| InvalidTimestamp(self.0.clone()))
+    }
+}
+
+/// Formats the time in UTC with millisecond precision. Times before the Unix
+/// epoch are clamped to it.
+impl From<SystemTime> for Timestamp {
+    fn from(time: SystemTime) -> Self {
+        let elapsed = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        Timestamp(format_utc(elapsed.as_secs(), elapsed.subsec_millis()))
+    }
+}
+
+/// Fails for years outside 0000-9999, which the protocol's ISO 8601 strings
+/// cannot carry.
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::DateTime<chrono::Utc>> for Timestamp {
+    type Error = InvalidTimestamp;
+
+    fn try_from(time: chrono::DateTime<chrono::Utc>) -> Result<Self, Self::Error> {
+        time.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+            .parse()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<Timestamp> for chrono::DateTime<chrono::Utc> {
+    type Error = InvalidTimestamp;
+
+    fn try_from(timestamp: Timestamp) -> Result<Self, Self::Error> {
+        timestamp.to_chrono()
+    }
+}
+
+/// Fails for years outside 0000-9999, which the protocol's ISO 8601 strings
+/// cannot carry.
+#[cfg(feature = "time")]
+impl TryFrom<time::OffsetDateTime> for Timestamp {
+    type Error = InvalidTimestamp;
+
+    fn try_from(time: time::OffsetDateTime) -> Result<Self, Self::Error> {
+        time.format(&time::format_description::well_known::Rfc3339)
+            .map_err(|_| InvalidTimestamp(time.to_string()))?
+            .parse()
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<Timestamp> for time::OffsetDateTime {
+    type Error = InvalidTimestamp;
+
+    fn try_from(timestamp: Timestamp) -> Result<Self, Self::Error> {
+        timestamp.to_time()
+    }
+}
+
+/// A string that is not an ISO 8601 date and time with an offset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidTimestamp(pub String);
+
+impl fmt::Display for InvalidTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid ISO 8601 timestamp {:?}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidTimestamp {}
+
+/// Formats seconds since the Unix epoch as a UTC timestamp with millisecond
+/// precision.
+fn format_utc(secs: u64, millis: u32) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    // Civil-from-days conversion (proleptic Gregorian calendar).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60,
+        millis
+    )
+}
+
+/// Reads `len` ASCII digits from the front of `s` if they are within
+/// `range`.
+fn number(s: &mut &str, len: usize, range: std::ops::RangeInclusive<u32>) -> Option<()> {
+    let digits = s.get(..len)?;
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    *s = &s[len..];
+    range.contains(&digits.parse().ok()?).then_some(())
+}
+
+fn literal(s: &mut &str, prefix: char) -> Option<()> {
+    *s = s.strip_prefix(prefix)?;
+    Some(())
+}
+
+/// Checks `YYYY-MM-DDTHH:MM:SS[.fraction](Z|±HH:MM)`.
+fn is_valid(mut s: &str) -> bool {
+    let s = &mut s;
+    let valid = (|| {
+        number(s, 4, 0..=9999)?;
+        literal(s, '-')?;
+        number(s, 2, 1..=12)?;
+        literal(s, '-')?;
+        number(s, 2, 1..=31)?;
+        *s = s.strip_prefix(['T', 't', ' '])?;
+        number(s, 2, 0..=23)?;
+        literal(s, ':')?;
+        number(s, 2, 0..=59)?;
+        literal(s, ':')?;
+        number(s, 2, 0..=60)?;
+        if let Some(rest) = s.strip_prefix('.') {
+            let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+            if digits == 0 {
+                return None;
+            }
+            *s = &rest[digits..];
+        }
+        if let Some(rest) = s.strip_prefix(['Z', 'z']) {
+            *s = rest;
+        } else {
+            *s = s.strip_prefix(['+', '-'])?;
+            number(s, 2, 0..=23)?;
+            literal(s, ':')?;
+            number(s, 2, 0..=59)?;
+        }
+        Some(())
+    })();
+    valid.is_some() && s.is_empty()
+}
+
+impl FromStr for Timestamp {
+    type Err = InvalidTimestamp;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if is_valid(s) {
+            Ok(Timestamp(s.to_string()))
+        } else {
+            Err(InvalidTimestamp(s.to_string()))
+        }
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,192 @@
+//! Helpers for servers that offer tools.
+
+use crate::catalog::{Catalog, ListDiff};
+use crate::extras::HasMeta;
+use crate::meta_keys;
+use crate::types::*;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Error returned by [`ToolRegistry`] mutations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToolRegistryError {
+    /// A tool with this name is already registered.
+    Duplicate(String),
+    /// No tool with this name is registered.
+    NotFound(String),
+}
+
+impl fmt::Display for ToolRegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ToolRegistryError::Duplicate(name) => {
+                write!(f, "tool {:?} is already registered", name)
+            }
+            ToolRegistryError::NotFound(name) => write!(f, "tool {:?} is not registered", name),
+        }
+    }
+}
+
+impl std::error::Error for ToolRegistryError {}
+
+/// An in-memory registry of tools, keyed by name.
+///
+/// The registry remembers what it last published to clients. After a batch
+/// of mutations, [`ToolRegistry::take_changes`] reports what changed since
+/// then, and a non-empty result means `notifications/tools/list_changed`
+/// should be sent.
+#[derive(Debug, Clone, Default)]
+pub struct ToolRegistry {
+    tools: Catalog<Tool>,
+    published: Catalog<Tool>,
+}
+
+impl ToolRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of tools per `tools/list` page.
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.tools.page_size = page_size;
+        self
+    }
+
+    /// Registers a new tool; fails if the name is taken.
+    pub fn add(&mut self, tool: Tool) -> Result<(), ToolRegistryError> {
+        if self.tools.contains(&tool.name) {
+            return Err(ToolRegistryError::Duplicate(tool.name.to_string()));
+        }
+        self.tools.insert(tool.name.to_string(), tool);
+        Ok(())
+    }
+
+    /// Replaces an existing tool, returning the previous definition.
+    pub fn replace(&mut self, tool: Tool) -> Result<Tool, ToolRegistryError> {
+        if !self.tools.contains(&tool.name) {
+            return Err(ToolRegistryError::NotFound(tool.name.to_string()));
+        }
+        Ok(self
+            .tools
+            .insert(tool.name.to_string(), tool)
+            .expect("tool is registered"))
+    }
+
+    /// Removes a tool, returning its definition.
+    pub fn remove(&mut self, name: &str) -> Result<Tool, ToolRegistryError> {
+        self.tools
+            .remove(name)
+            .ok_or_else(|| ToolRegistryError::NotFound(name.to_string()))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Tool> {
+        self.tools.get(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.tools.contains(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.tools.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tools.len() == 0
+    }
+
+    /// All tools, ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = &Tool> {
+        self.tools.values()
+    }
+
+    /// The `tools/list` page following `cursor`.
+    pub fn list(&self, cursor: Option<&str>) -> ListToolsResult {
+        let (items, next_cursor) = self.tools.page(cursor);
+        Paginated {
+            meta: None,
+            next_cursor,
+            items,
+            extra: HashMap::new(),
+        }
+    }
+
+    /// What changed since the last call (or since creation), marking the
+    /// current tools as published.
+    pub fn take_changes(&mut self) -> ListDiff {
+        let diff = self.tools.diff(&self.published);
+        self.published = self.tools.clone();
+        diff
+    }
+}
+
+impl Tool {
+    /// The tool's version, stored under [`meta_keys::TOOL_VERSION`].
+    pub fn meta_version(&self) -> Option<&str> {
+        self.meta.as_ref()?.get(meta_keys::TOOL_VERSION)?.as_str()
+    }
+
+    /// Sets the tool's version.
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.set_meta_key(meta_keys::TOOL_VERSION, version.into())
+            .expect("version serializes");
+        self
+    }
+
+    /// Compares the tool's version with a version a workflow was pinned to,
+    /// using [`compare_versions`]. `None` if the tool has no version.
+    pub fn compare_version(&self, pinned: &str) -> Option<Ordering> {
+        Some(compare_versions(self.meta_version()?, pinned))
+    }
+}
+
+/// Compares dotted version strings segment by segment. A segment starting
+/// with digits is compared by that number first, then by the rest of it, with
+/// a suffix such as `-beta` sorting before no suffix; other segments are
+/// compared as text, and a missing segment counts as `0`. `"2.10-rc1"` is
+/// newer than `"2.9"`, `"1.0-beta"` older than `"1.0"`, and `"2.1"` equals
+/// `"2.1.0"`.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let mut a = a.split('.');
+    let mut b = b.split('.');
+    loop {
+        let ordering = match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (Some(a), None) => compare_segments(a, "0"),
+            (None, Some(b)) => compare_segments("0", b),
+            (Some(a), Some(b)) => compare_segments(a, b),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+fn compare_segments(a: &str, b: &str) -> Ordering {
+    let (a_number, a_suffix) = split_number(a);
+    let (b_number, b_suffix) = split_number(b);
+    if a_number.is_empty() || b_number.is_empty() {
+        return a.cmp(b);
+    }
+    let a_number = a_number.trim_start_matches('0');
+    let b_number = b_number.trim_start_matches('0');
+    a_number
+        .len()
+        .cmp(&b_number.len())
+        .then_with(|| a_number.cmp(b_number))
+        .then_with(|| match (a_suffix.is_empty(), b_suffix.is_empty()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => a_suffix.cmp(b_suffix),
+        })
+}
+
+/// Splits a segment into its leading digits and the rest.
+fn split_number(segment: &str) -> (&str, &str) {
+    let end = segment
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(segment.len());
+    segment.split_at(end)
+}
@@ -0,0 +1,318 @@
+//! W3C Trace Context (`traceparent` / `tracestate`) carried in `_meta`.
+
+use crate::meta_keys;
+use crate::types::*;
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+/// The most `tracestate` list members the spec allows.
+const MAX_TRACESTATE_MEMBERS: usize = 32;
+
+/// A `traceparent` or `tracestate` value that does not follow the spec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceContextError {
+    InvalidTraceParent(String),
+    InvalidTraceState(String),
+}
+
+impl fmt::Display for TraceContextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceContextError::InvalidTraceParent(value) => {
+                write!(f, "invalid traceparent {:?}", value)
+            }
+            TraceContextError::InvalidTraceState(value) => {
+                write!(f, "invalid tracestate {:?}", value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TraceContextError {}
+
+/// A parsed `traceparent` header: `{version}-{trace-id}-{parent-id}-{flags}`
+/// in lowercase hex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TraceParent {
+    pub version: u8,
+    pub trace_id: [u8; 16],
+    pub parent_id: [u8; 8],
+    pub flags: u8,
+}
+
+impl TraceParent {
+    /// The `sampled` bit of the trace flags.
+    pub const FLAG_SAMPLED: u8 = 0x01;
+
+    /// A version `00` trace parent.
+    pub fn new(trace_id: [u8; 16], parent_id: [u8; 8], flags: u8) -> Self {
+        TraceParent {
+            version: 0,
+            trace_id,
+            parent_id,
+            flags,
+        }
+    }
+
+    pub fn is_sampled(&self) -> bool {
+        self.flags & Self::FLAG_SAMPLED != 0
+    }
+
+    /// The trace parent for a child span of this one: same trace, new
+    /// parent id.
+    pub fn child(&self, parent_id: [u8; 8]) -> Self {
+        TraceParent { parent_id, ..*self }
+    }
+}
+
+fn decode_hex<const N: usize>(hex: &str) -> Option<[u8; N]> {
+    if hex.len() != N * 2 {
+        return None;
+    }
+    let digit = |b: u8| match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        _ => None,
+    };
+    let mut out = [0u8; N];
+    for (byte, pair) in out.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        *byte = digit(pair[0])? << 4 | digit(pair[1])?;
+    }
+    Some(out)
+}
+
+fn write_hex(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    bytes.iter().try_for_each(|b| write!(f, "{:02x}", b))
+}
+
+impl FromStr for TraceParent {
+    type Err = TraceContextError;
+
+    /// Parses a `traceparent`. Versions above `00` may append further
+    /// fields, which are ignored; version `ff` and all-zero ids are invalid.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || TraceContextError::InvalidTraceParent(s.to_string());
+        let mut fields = s.splitn(5, '-');
+        let mut next = || fields.next().ok_or_else(invalid);
+        let [version] = decode_hex::<1>(next()?).ok_or_else(invalid)?;
+        let trace_id = decode_hex::<16>(next()?).ok_or_else(invalid)?;
+        let parent_id = decode_hex::<8>(next()?).ok_or_else(invalid)?;
+        let [flags] = decode_hex::<1>(next()?).ok_or_else(invalid)?;
+        let has_rest = fields.next().is_some();
+        if version == 0xff
+            || (version == 0 && has_rest)
+            || trace_id == [0; 16]
+            || parent_id == [0; 8]
+        {
+            return Err(invalid());
+        }
+        Ok(TraceParent {
+            version,
+            trace_id,
+            parent_id,
+            flags,
+        })
+    }
+}
+
+impl fmt::Display for TraceParent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02x}-", self.version)?;
+        write_hex(f, &self.trace_id)?;
+        f.write_str("-")?;
+        write_hex(f, &self.parent_id)?;
+        write!(f, "-{:02x}", self.flags)
+    }
+}
+
+/// A parsed `tracestate` header: vendor-specific `key=value` members, most
+/// recently updated first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TraceState(Vec<(String, String)>);
+
+fn is_valid_tracestate_key(key: &str) -> bool {
+    // Only a tenant id may start with a digit.
+    let simple = |part: &str, max: usize, leading_digit: bool| {
+        let bytes = part.as_bytes();
+        !bytes.is_empty()
+            && bytes.len() <= max
+            && (bytes[0].is_ascii_lowercase() || (leading_digit && bytes[0].is_ascii_digit()))
+            && bytes.iter().all(|b| {
+                b.is_ascii_lowercase()
+                    || b.is_ascii_digit()
+                    || matches!(b, b'_' | b'-' | b'*' | b'/')
+            })
+    };
+    match key.split_once('@') {
+        Some((tenant, system)) => simple(tenant, 241, true) && simple(system, 14, false),
+        None => simple(key, 256, false),
+    }
+}
+
+fn is_valid_tracestate_value(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= 256
+        && !value.ends_with(' ')
+        && value
+            .bytes()
+            .all(|b| (0x20..=0x7e).contains(&b) && b != b',' && b != b'=')
+}
+
+impl TraceState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The value of the member with `key`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Sets `key` to `value` and moves it to the front, as a vendor does
+    /// when it updates its entry.
+    pub fn insert(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Result<Self, TraceContextError> {
+        let (key, value) = (key.into(), value.into());
+        if !is_valid_tracestate_key(&key) || !is_valid_tracestate_value(&value) {
+            return Err(TraceContextError::InvalidTraceState(format!(
+                "{}={}",
+                key, value
+            )));
+        }
+        self.0.retain(|(k, _)| *k != key);
+        self.0.insert(0, (key, value));
+        self.0.truncate(MAX_TRACESTATE_MEMBERS);
+        Ok(self)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromStr for TraceState {
+    type Err = TraceContextError;
+
+    /// Parses a `tracestate`. Empty list members are skipped; a duplicate
+    /// key or more than 32 members makes the whole value invalid.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || TraceContextError::InvalidTraceState(s.to_string());
+        let mut members: Vec<(String, String)> = Vec::new();
+        for member in s.split(',').map(|m| m.trim_matches([' ', '\t'])) {
+            if member.is_empty() {
+                continue;
+            }
+            let (key, value) = member.split_once('=').ok_or_else(invalid)?;
+            if !is_valid_tracestate_key(key)
+                || !is_valid_tracestate_value(value)
+                || members.iter().any(|(k, _)| k == key)
+            {
+                return Err(invalid());
+            }
+            members.push((key.to_string(), value.to_string()));
+        }
+        if members.len() > MAX_TRACESTATE_MEMBERS {
+            return Err(invalid());
+        }
+        Ok(TraceState(members))
+    }
+}
+
+impl fmt::Display for TraceState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}={}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// A trace parent plus optional vendor state, propagated across MCP hops
+/// under [`meta_keys::TRACEPARENT`] and [`meta_keys::TRACESTATE`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    pub traceparent: TraceParent,
+    pub tracestate: Option<TraceState>,
+}
+
+impl TraceContext {
+    pub fn new(traceparent: TraceParent) -> Self {
+        TraceContext {
+            traceparent,
+            tracestate: None,
+        }
+    }
+
+    pub fn with_tracestate(mut self, tracestate: TraceState) -> Self {
+        self.tracestate = Some(tracestate);
+        self
+    }
+}
+
+impl From<TraceParent> for TraceContext {
+    fn from(traceparent: TraceParent) -> Self {
+        TraceContext::new(traceparent)
+    }
+}
+
+impl RequestMeta {
+    /// Sets `traceparent` and, when present and non-empty, `tracestate`.
+    pub fn with_trace_context(mut self, context: impl Into<TraceContext>) -> Self {
+        let context = context.into();
+        self.extra.insert(
+            meta_keys::TRACEPARENT.to_string(),
+            Value::String(context.traceparent.to_string()),
+        );
+        match context.tracestate.filter(|state| !state.is_empty()) {
+            Some(state) => {
+                self.extra.insert(
+                    meta_keys::TRACESTATE.to_string(),
+                    Value::String(state.to_string()),
+                );
+            }
+            None => {
+                self.extra.remove(meta_keys::TRACESTATE);
+            }
+        }
+        self
+    }
+
+    /// The trace parent, if present and well-formed.
+    pub fn traceparent(&self) -> Option<TraceParent> {
+        self.extra
+            .get(meta_keys::TRACEPARENT)?
+            .as_str()?
+            .parse()
+            .ok()
+    }
+
+    /// The trace context, if `traceparent` is present and well-formed. A
+    /// malformed `tracestate` is dropped, as the spec requires, without
+    /// discarding the trace parent.
+    pub fn trace_context(&self) -> Option<TraceContext> {
+        let traceparent = self.traceparent()?;
+        let tracestate = self
+            .extra
+            .get(meta_keys::TRACESTATE)
+            .and_then(Value::as_str)
+            .and_then(|state| state.parse().ok());
+        Some(TraceContext {
+            traceparent,
+            tracestate,
+        })
+    }
+}
@@ -11,15 +11,201 @@
 //! - All fields use `#[serde(rename_all = "camelCase")]` so Rust code remains snake_case
 //!   while JSON output remains camelCase.
 
+use crate::elicitation::RequestedSchema;
+use crate::timestamp::Timestamp;
+use mcp_schema_derive::mcp_extras;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::fmt;
+
+/// String type of short, frequently parsed fields: method names, tool and
+/// prompt names, URIs, and MIME types. With the `smol_str` feature it is
+/// stored as a [`smol_str::SmolStr`], which keeps short strings inline
+/// instead of on the heap; the API and the wire format are the same either
+/// way.
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct McpStr(McpStrRepr);
+
+#[cfg(feature = "smol_str")]
+type McpStrRepr = smol_str::SmolStr;
+#[cfg(not(feature = "smol_str"))]
+type McpStrRepr = String;
+
+impl McpStr {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::ops::Deref for McpStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for McpStr {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::borrow::Borrow<str> for McpStr {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for McpStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for McpStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for McpStr {
+    fn from(s: &str) -> Self {
+        McpStr(s.into())
+    }
+}
+
+impl From<&String> for McpStr {
+    fn from(s: &String) -> Self {
+        McpStr(s.as_str().into())
+    }
+}
+
+impl From<String> for McpStr {
+    // A no-op when the representation is `String`.
+    #[allow(clippy::useless_conversion)]
+    fn from(s: String) -> Self {
+        McpStr(s.into())
+    }
+}
+
+impl From<McpStr> for String {
+    #[allow(clippy::useless_conversion)]
+    fn from(s: McpStr) -> Self {
+        s.0.into()
+    }
+}
+
+impl PartialEq<str> for McpStr {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for McpStr {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for McpStr {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<McpStr> for str {
+    fn eq(&self, other: &McpStr) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialEq<McpStr> for &str {
+    fn eq(&self, other: &McpStr) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl PartialEq<McpStr> for String {
+    fn eq(&self, other: &McpStr) -> bool {
+        self == other.as_str()
+    }
+}
 
 /// The JSON-RPC version string (always "2.0").
 pub const JSONRPC_VERSION: &str = "2.0";
 
+/// The `jsonrpc` member of every message. It carries no data: it always
+/// serializes as [`JSONRPC_VERSION`], and deserializing any other value
+/// fails.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct JsonRpcVersion;
+
+impl JsonRpcVersion {
+    pub fn as_str(&self) -> &'static str {
+        JSONRPC_VERSION
+    }
+}
+
+impl fmt::Display for JsonRpcVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(JSONRPC_VERSION)
+    }
+}
+
+impl PartialEq<str> for JsonRpcVersion {
+    fn eq(&self, other: &str) -> bool {
+        other == JSONRPC_VERSION
+    }
+}
+
+impl PartialEq<&str> for JsonRpcVersion {
+    fn eq(&self, other: &&str) -> bool {
+        *other == JSONRPC_VERSION
+    }
+}
+
+impl Serialize for JsonRpcVersion {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(JSONRPC_VERSION)
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonRpcVersion {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct VersionVisitor;
+
+        impl serde::de::Visitor<'_> for VersionVisitor {
+            type Value = JsonRpcVersion;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "the string {:?}", JSONRPC_VERSION)
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<JsonRpcVersion, E> {
+                if value == JSONRPC_VERSION {
+                    Ok(JsonRpcVersion)
+                } else {
+                    Err(E::invalid_value(serde::de::Unexpected::Str(value), &self))
+                }
+            }
+        }
+
+        deserializer.deserialize_str(VersionVisitor)
+    }
+}
+
 /// The latest Model Context Protocol version.
+#[cfg(not(feature = "v2025_03_26"))]
 pub const LATEST_PROTOCOL_VERSION: &str = "2024-11-05";
+/// The latest Model Context Protocol version.
+#[cfg(all(feature = "v2025_03_26", not(feature = "v2025_06_18")))]
+pub const LATEST_PROTOCOL_VERSION: &str = "2025-03-26";
+/// The latest Model Context Protocol version.
+#[cfg(feature = "v2025_06_18")]
+pub const LATEST_PROTOCOL_VERSION: &str = "2025-06-18";
 
 // Below are standard JSON-RPC error codes.
 pub const PARSE_ERROR: i32 = -32700;
@@ -29,22 +215,86 @@ pub const INVALID_PARAMS: i32 = -32602;
 pub const INTERNAL_ERROR: i32 = -32603;
 
 /// A request ID for JSON-RPC, which can be either a string or a number.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Ids compare by variant first, so `1` and `"1"` are different ids; numbers
+/// sort before strings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum RequestId {
     String(String),
     Number(i64),
 }
 
+impl Ord for RequestId {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match (self, other) {
+            (RequestId::Number(a), RequestId::Number(b)) => a.cmp(b),
+            (RequestId::String(a), RequestId::String(b)) => a.cmp(b),
+            (RequestId::Number(_), RequestId::String(_)) => std::cmp::Ordering::Less,
+            (RequestId::String(_), RequestId::Number(_)) => std::cmp::Ordering::Greater,
+        }
+    }
+}
+
+impl PartialOrd for RequestId {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestId::String(s) => f.write_str(s),
+            RequestId::Number(n) => write!(f, "{}", n),
+        }
+    }
+}
+
 /// A progress token for associating progress notifications with a request.
-/// This can be either a string or a number.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// This can be either a string or a number, and compares like [`RequestId`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ProgressToken {
     String(String),
     Number(i64),
 }
 
+impl Ord for ProgressToken {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match (self, other) {
+            (ProgressToken::Number(a), ProgressToken::Number(b)) => a.cmp(b),
+            (ProgressToken::String(a), ProgressToken::String(b)) => a.cmp(b),
+            (ProgressToken::Number(_), ProgressToken::String(_)) => std::cmp::Ordering::Less,
+            (ProgressToken::String(_), ProgressToken::Number(_)) => std::cmp::Ordering::Greater,
+        }
+    }
+}
+
+impl PartialOrd for ProgressToken {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<String> for ProgressToken {
+    fn from(token: String) -> Self {
+        ProgressToken::String(token)
+    }
+}
+
+impl From<&str> for ProgressToken {
+    fn from(token: &str) -> Self {
+        ProgressToken::String(token.to_string())
+    }
+}
+
+impl From<i64> for ProgressToken {
+    fn from(token: i64) -> Self {
+        ProgressToken::Number(token)
+    }
+}
+
 /// A cursor for pagination.
 pub type Cursor = String;
 
@@ -55,13 +305,14 @@ pub type Cursor = String;
 /// - `T`: The type of the `params` field, containing request-specific data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
 pub struct JSONRPCRequest<T> {
     /// Must be "2.0" for JSON-RPC.
     #[serde(rename = "jsonrpc")]
-    pub json_rpc: String,
+    pub json_rpc: JsonRpcVersion,
 
     /// Method name.
-    pub method: String,
+    pub method: McpStr,
 
     /// Request ID (string or number).
     pub id: RequestId,
@@ -77,10 +328,11 @@ pub struct JSONRPCRequest<T> {
 /// - `T`: The type of the `params` field, containing notification-specific data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
 pub struct JSONRPCNotification<T> {
     #[serde(rename = "jsonrpc")]
-    pub json_rpc: String,
-    pub method: String,
+    pub json_rpc: JsonRpcVersion,
+    pub method: McpStr,
     pub params: T,
 }
 
@@ -91,9 +343,10 @@ pub struct JSONRPCNotification<T> {
 /// - `U`: The type of the `result` field, containing response-specific data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
 pub struct JSONRPCResponse<U> {
     #[serde(rename = "jsonrpc")]
-    pub json_rpc: String,
+    pub json_rpc: JsonRpcVersion,
     pub id: RequestId,
 
     /// Result object when the request completes successfully.
@@ -103,16 +356,20 @@ pub struct JSONRPCResponse<U> {
 /// A JSON-RPC error message, indicating that a request failed.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
 pub struct JSONRPCError {
     #[serde(rename = "jsonrpc")]
-    pub json_rpc: String,
-    pub id: RequestId,
+    pub json_rpc: JsonRpcVersion,
+    /// The id of the failed request, or `None` (`"id": null`) if it could
+    /// not be determined, e.g. because the request did not parse.
+    pub id: Option<RequestId>,
     pub error: RPCErrorDetail,
 }
 
 /// Provides details about a JSON-RPC error, including an optional `data` field.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
 pub struct RPCErrorDetail {
     pub code: i32,
     pub message: String,
@@ -120,48 +377,239 @@ pub struct RPCErrorDetail {
     pub data: Option<Value>,
 }
 
+/// Any JSON-RPC message, as read from a transport before its kind is known.
+///
+/// Decoding looks at which members are present rather than trying each
+/// variant in turn: `method` with `id` is a request, `method` alone a
+/// notification, and otherwise `result` or `error` make a response or an
+/// error. A missing `params` member is read as `None`, and `None` params
+/// are left out when serializing.
+#[derive(Debug, Clone)]
+pub enum JSONRPCMessage {
+    Request(JSONRPCRequest<Option<Value>>),
+    Notification(JSONRPCNotification<Option<Value>>),
+    Response(JSONRPCResponse<Value>),
+    Error(JSONRPCError),
+}
+
+impl JSONRPCMessage {
+    /// The id of a request, response, or error.
+    pub fn id(&self) -> Option<&RequestId> {
+        match self {
+            JSONRPCMessage::Request(request) => Some(&request.id),
+            JSONRPCMessage::Notification(_) => None,
+            JSONRPCMessage::Response(response) => Some(&response.id),
+            JSONRPCMessage::Error(error) => error.id.as_ref(),
+        }
+    }
+
+    /// The method of a request or notification.
+    pub fn method(&self) -> Option<&str> {
+        match self {
+            JSONRPCMessage::Request(request) => Some(&request.method),
+            JSONRPCMessage::Notification(notification) => Some(&notification.method),
+            JSONRPCMessage::Response(_) | JSONRPCMessage::Error(_) => None,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for JSONRPCMessage {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let object = serde_json::Map::deserialize(deserializer)?;
+        let has = |key: &str| object.contains_key(key);
+        let kind = match (has("method"), has("id"), has("result"), has("error")) {
+            (true, true, _, _) => "request",
+            (true, false, _, _) => "notification",
+            (false, _, true, false) => "response",
+            (false, _, false, true) => "error",
+            (false, _, true, true) => {
+                return Err(D::Error::custom(
+                    "message has both \"result\" and \"error\"",
+                ))
+            }
+            (false, _, false, false) => {
+                return Err(D::Error::custom(
+                    "message has none of \"method\", \"result\", or \"error\"",
+                ))
+            }
+        };
+        let value = Value::Object(object);
+        match kind {
+            "request" => serde_json::from_value(value).map(JSONRPCMessage::Request),
+            "notification" => serde_json::from_value(value).map(JSONRPCMessage::Notification),
+            "response" => serde_json::from_value(value).map(JSONRPCMessage::Response),
+            _ => serde_json::from_value(value).map(JSONRPCMessage::Error),
+        }
+        .map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for JSONRPCMessage {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let (json_rpc, id, method, params) = match self {
+            JSONRPCMessage::Request(request) => (
+                request.json_rpc,
+                Some(&request.id),
+                &request.method,
+                &request.params,
+            ),
+            JSONRPCMessage::Notification(notification) => (
+                notification.json_rpc,
+                None,
+                &notification.method,
+                &notification.params,
+            ),
+            JSONRPCMessage::Response(response) => return response.serialize(serializer),
+            JSONRPCMessage::Error(error) => return error.serialize(serializer),
+        };
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("jsonrpc", &json_rpc)?;
+        map.serialize_entry("method", method)?;
+        if let Some(id) = id {
+            map.serialize_entry("id", id)?;
+        }
+        if let Some(params) = params {
+            map.serialize_entry("params", params)?;
+        }
+        map.end()
+    }
+}
+
+impl From<JSONRPCRequest<Option<Value>>> for JSONRPCMessage {
+    fn from(request: JSONRPCRequest<Option<Value>>) -> Self {
+        JSONRPCMessage::Request(request)
+    }
+}
+
+impl From<JSONRPCNotification<Option<Value>>> for JSONRPCMessage {
+    fn from(notification: JSONRPCNotification<Option<Value>>) -> Self {
+        JSONRPCMessage::Notification(notification)
+    }
+}
+
+impl From<JSONRPCResponse<Value>> for JSONRPCMessage {
+    fn from(response: JSONRPCResponse<Value>) -> Self {
+        JSONRPCMessage::Response(response)
+    }
+}
+
+impl From<JSONRPCError> for JSONRPCMessage {
+    fn from(error: JSONRPCError) -> Self {
+        JSONRPCMessage::Error(error)
+    }
+}
+
 /// Parameters for an MCP request, allowing additional arbitrary fields via `flatten`.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MCPRequestParams {
     #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
     pub meta: Option<RequestMeta>,
-
-    /// Arbitrary extra fields.
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
 }
 
 /// `_meta` field for MCP requests, optionally containing a progress token.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct RequestMeta {
     #[serde(rename = "progressToken", skip_serializing_if = "Option::is_none")]
     pub progress_token: Option<ProgressToken>,
+
+    /// Other `_meta` entries, such as the conventions in [`crate::meta_keys`].
+    /// `_meta` is open by definition, so these are kept even with the
+    /// `strict-fields` feature.
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
 /// Parameters for an MCP notification, allowing additional arbitrary fields via `flatten`.
+#[mcp_extras]
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
-pub struct MCPNotificationParams {
-    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
-    pub meta: Option<HashMap<String, Value>>,
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
-}
+pub struct MCPNotificationParams {}
 
 /// Base result type for MCP responses.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[mcp_extras]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
-pub struct MCPResultBase {
-    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
+pub struct MCPResultBase {}
+
+/// Indicates success but carries no data.
+pub type EmptyResult = MCPResultBase;
+
+/// A payload with the `_meta` and passthrough fields every MCP object
+/// carries, serialized flattened alongside the payload's own fields.
+///
+/// `T` should be a plain struct: its fields are matched first, and whatever
+/// is left over lands in `extra`. Derefs to `T`, so payload fields read as
+/// if they were declared on the wrapper.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WithMeta<T> {
+    #[serde(rename = "_meta", default, skip_serializing_if = "Option::is_none")]
     pub meta: Option<HashMap<String, Value>>,
     #[serde(flatten)]
+    pub inner: T,
+    #[serde(flatten, deserialize_with = "crate::extras::deserialize_extra")]
     pub extra: HashMap<String, Value>,
 }
 
-/// Indicates success but carries no data.
-pub type EmptyResult = MCPResultBase;
+impl<T> WithMeta<T> {
+    pub fn new(inner: T) -> Self {
+        WithMeta {
+            meta: None,
+            inner,
+            extra: HashMap::new(),
+        }
+    }
+
+    /// Sets one `_meta` entry.
+    pub fn with_meta(mut self, key: impl Into<String>, value: Value) -> Self {
+        self.meta
+            .get_or_insert_with(HashMap::new)
+            .insert(key.into(), value);
+        self
+    }
+
+    /// Converts the payload, keeping `_meta` and the passthrough fields.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> WithMeta<U> {
+        WithMeta {
+            meta: self.meta,
+            inner: f(self.inner),
+            extra: self.extra,
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T> From<T> for WithMeta<T> {
+    fn from(inner: T) -> Self {
+        WithMeta::new(inner)
+    }
+}
+
+impl<T> std::ops::Deref for WithMeta<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T> std::ops::DerefMut for WithMeta<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
 
 /// Represents parameters for a cancelled-notification, which can be sent by either side.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -181,13 +629,10 @@ pub struct InitializeParams {
     pub client_info: Implementation,
 }
 
-/// A result returned by the server after an `initialize` request.
+/// The payload of an `initialize` result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct InitializeResult {
-    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
-    pub meta: Option<HashMap<String, Value>>,
-
+pub struct InitializePayload {
     pub protocol_version: String,
     pub capabilities: ServerCapabilities,
     pub server_info: Implementation,
@@ -195,42 +640,155 @@ pub struct InitializeResult {
     /// Optional instructions from the server.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub instructions: Option<String>,
-
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
 }
 
+/// A result returned by the server after an `initialize` request.
+pub type InitializeResult = WithMeta<InitializePayload>;
+
 /// Describes capabilities a client might support.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ClientCapabilities {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub experimental: Option<HashMap<String, Value>>,
+    pub experimental: Option<ExperimentalCapabilities>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub roots: Option<RootsCapability>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub sampling: Option<HashMap<String, Value>>,
+    pub sampling: Option<SamplingCapability>,
 
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elicitation: Option<ElicitationCapability>,
 }
 
+/// Non-standard capabilities, keyed by feature name. The spec leaves their
+/// shape open; this wrapper adds typed access on top of the raw JSON map.
+///
+/// Keys passed to [`ExperimentalCapabilities::declare`] must be namespaced
+/// (e.g. `com.example/streaming`) following the `_meta` key grammar, so that
+/// independent extensions cannot collide. Keys received from a peer are
+/// accepted as-is.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ExperimentalCapabilities(pub HashMap<String, Value>);
+
+impl ExperimentalCapabilities {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares an experimental capability, serializing `value` as its
+    /// settings object.
+    pub fn declare<T: Serialize>(
+        &mut self,
+        key: impl Into<String>,
+        value: T,
+    ) -> Result<&mut Self, ExperimentalCapabilityError> {
+        let key = key.into();
+        if !key.contains('/') {
+            return Err(ExperimentalCapabilityError::NotNamespaced(key));
+        }
+        crate::meta_keys::validate_custom_key(&key)
+            .map_err(ExperimentalCapabilityError::InvalidKey)?;
+        let value = serde_json::to_value(value).map_err(ExperimentalCapabilityError::Serialize)?;
+        self.0.insert(key, value);
+        Ok(self)
+    }
+
+    /// Decodes the settings of an experimental capability, if declared.
+    pub fn get<T: serde::de::DeserializeOwned>(
+        &self,
+        key: &str,
+    ) -> Result<Option<T>, serde_json::Error> {
+        self.0
+            .get(key)
+            .map(|value| T::deserialize(value))
+            .transpose()
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.0.contains_key(key)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<HashMap<String, Value>> for ExperimentalCapabilities {
+    fn from(map: HashMap<String, Value>) -> Self {
+        ExperimentalCapabilities(map)
+    }
+}
+
+/// Error returned by [`ExperimentalCapabilities::declare`].
+#[derive(Debug)]
+pub enum ExperimentalCapabilityError {
+    /// The key has no namespace prefix (`namespace/name`).
+    NotNamespaced(String),
+    /// The key does not follow the key grammar or uses a reserved prefix.
+    InvalidKey(crate::meta_keys::MetaKeyError),
+    /// The settings value could not be serialized.
+    Serialize(serde_json::Error),
+}
+
+impl fmt::Display for ExperimentalCapabilityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExperimentalCapabilityError::NotNamespaced(key) => write!(
+                f,
+                "experimental capability {:?} must be namespaced, e.g. \"com.example/{}\"",
+                key, key
+            ),
+            ExperimentalCapabilityError::InvalidKey(err) => err.fmt(f),
+            ExperimentalCapabilityError::Serialize(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ExperimentalCapabilityError {}
+
 /// Describes whether the client supports updated-list notifications for roots.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
 pub struct RootsCapability {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub list_changed: Option<bool>,
 }
 
+/// Indicates client support for sampling. The spec defines no settings
+/// yet, so everything lands in `extra`, even with the `strict-fields`
+/// feature.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SamplingCapability {
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// Indicates client support for elicitation (added in 2025-06-18). Like
+/// [`SamplingCapability`], it has no settings of its own.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElicitationCapability {
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
 /// A set of capabilities the server may support.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ServerCapabilities {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub experimental: Option<HashMap<String, Value>>,
+    pub experimental: Option<ExperimentalCapabilities>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub logging: Option<HashMap<String, Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -239,14 +797,15 @@ pub struct ServerCapabilities {
     pub resources: Option<ResourcesCapability>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tools: Option<ToolsCapability>,
-
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
+    /// Present if the server offers argument autocompletion (2025-03-26).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completions: Option<HashMap<String, Value>>,
 }
 
 /// Indicates server support for prompt-related features.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
 pub struct PromptsCapability {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub list_changed: Option<bool>,
@@ -255,6 +814,7 @@ pub struct PromptsCapability {
 /// Indicates server support for resource-related features.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
 pub struct ResourcesCapability {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub subscribe: Option<bool>,
@@ -265,20 +825,22 @@ pub struct ResourcesCapability {
 /// Indicates server support for tool-related features.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
 pub struct ToolsCapability {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub list_changed: Option<bool>,
 }
 
 /// Represents the name and version of an MCP implementation.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Implementation {
-    pub name: String,
+    pub name: McpStr,
+    /// A human-readable name for display (2025-06-18).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
     pub version: String,
-
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
 }
 
 /// Parameters for the `ping` method (client or server). Generally empty.
@@ -287,6 +849,7 @@ pub struct Implementation {
 pub struct PingParams {}
 
 /// Parameters for a progress notification, typically referencing a long-running request.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProgressNotificationParams {
@@ -294,123 +857,252 @@ pub struct ProgressNotificationParams {
     pub progress: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub total: Option<f64>,
-
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
+    /// A description of the current progress (2025-03-26).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
 }
 
 /// A structure for request parameters that may involve pagination.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct PaginatedParams {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
     pub _meta: Option<RequestMeta>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cursor: Option<Cursor>,
+}
 
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
+impl PaginatedParams {
+    /// Parameters requesting the first page.
+    pub fn first_page() -> Self {
+        Self::default()
+    }
+
+    /// Parameters requesting the page after `cursor`, as returned in a
+    /// previous result's `nextCursor`.
+    pub fn from_cursor(cursor: impl Into<Cursor>) -> Self {
+        PaginatedParams {
+            cursor: Some(cursor.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Asks the receiver to report progress under `token`.
+    pub fn with_progress_token(mut self, token: impl Into<ProgressToken>) -> Self {
+        self._meta
+            .get_or_insert_with(RequestMeta::default)
+            .progress_token = Some(token.into());
+        self
+    }
+}
+
+/// Parameters for `prompts/list`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(transparent)]
+pub struct ListPromptsParams(pub PaginatedParams);
+
+impl From<PaginatedParams> for ListPromptsParams {
+    fn from(params: PaginatedParams) -> Self {
+        ListPromptsParams(params)
+    }
+}
+
+/// Parameters for `resources/list`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(transparent)]
+pub struct ListResourcesParams(pub PaginatedParams);
+
+impl From<PaginatedParams> for ListResourcesParams {
+    fn from(params: PaginatedParams) -> Self {
+        ListResourcesParams(params)
+    }
+}
+
+/// Parameters for `resources/templates/list`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(transparent)]
+pub struct ListResourceTemplatesParams(pub PaginatedParams);
+
+impl From<PaginatedParams> for ListResourceTemplatesParams {
+    fn from(params: PaginatedParams) -> Self {
+        ListResourceTemplatesParams(params)
+    }
+}
+
+/// Parameters for `tools/list`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(transparent)]
+pub struct ListToolsParams(pub PaginatedParams);
+
+impl From<PaginatedParams> for ListToolsParams {
+    fn from(params: PaginatedParams) -> Self {
+        ListToolsParams(params)
+    }
 }
 
 /// Indicates that a result can include pagination metadata.
+#[mcp_extras]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PaginatedResult {
-    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
-    pub meta: Option<HashMap<String, Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next_cursor: Option<Cursor>,
+}
 
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
+/// An item type that can be listed in a [`Paginated`] result.
+pub trait PaginatedItem: Sized {
+    /// The JSON field holding the list of items (e.g. `"tools"`).
+    const FIELD: &'static str;
 }
 
-/// A result containing a list of resources known to the server.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ListResourcesResult {
-    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
+/// A paginated list result: the listed items plus the pagination metadata
+/// shared by every `*/list` method. The items are (de)serialized under the
+/// field named by [`PaginatedItem::FIELD`].
+#[derive(Debug, Clone)]
+pub struct Paginated<T> {
     pub meta: Option<HashMap<String, Value>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub next_cursor: Option<Cursor>,
-    pub resources: Vec<Resource>,
-
-    #[serde(flatten)]
+    pub items: Vec<T>,
     pub extra: HashMap<String, Value>,
 }
 
+impl<T: PaginatedItem + Serialize> Serialize for Paginated<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(meta) = &self.meta {
+            map.serialize_entry("_meta", meta)?;
+        }
+        if let Some(next_cursor) = &self.next_cursor {
+            map.serialize_entry("nextCursor", next_cursor)?;
+        }
+        map.serialize_entry(T::FIELD, &self.items)?;
+        for (key, value) in &self.extra {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de, T: PaginatedItem + serde::de::DeserializeOwned> Deserialize<'de> for Paginated<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let mut extra = HashMap::<String, Value>::deserialize(deserializer)?;
+        let items = extra
+            .remove(T::FIELD)
+            .ok_or_else(|| D::Error::missing_field(T::FIELD))?;
+        let take = |extra: &mut HashMap<String, Value>, key: &str| {
+            extra.remove(key).filter(|value| !value.is_null())
+        };
+        let meta = take(&mut extra, "_meta");
+        let next_cursor = take(&mut extra, "nextCursor");
+        let extra = crate::extras::deserialize_extra(Value::Object(extra.into_iter().collect()))
+            .map_err(D::Error::custom)?;
+
+        Ok(Paginated {
+            meta: meta.map(serde_json::from_value).transpose().map_err(D::Error::custom)?,
+            next_cursor: next_cursor
+                .map(serde_json::from_value)
+                .transpose()
+                .map_err(D::Error::custom)?,
+            items: serde_json::from_value(items).map_err(D::Error::custom)?,
+            extra,
+        })
+    }
+}
+
+/// A result containing a list of resources known to the server.
+pub type ListResourcesResult = Paginated<Resource>;
+
 /// A result containing a list of resource templates known to the server.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ListResourceTemplatesResult {
-    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
-    pub meta: Option<HashMap<String, Value>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub next_cursor: Option<Cursor>,
-    pub resource_templates: Vec<ResourceTemplate>,
+pub type ListResourceTemplatesResult = Paginated<ResourceTemplate>;
 
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
+/// A result containing a list of prompts known to the server.
+pub type ListPromptsResult = Paginated<Prompt>;
+
+/// A result listing server-provided tools.
+pub type ListToolsResult = Paginated<Tool>;
+
+impl PaginatedItem for Resource {
+    const FIELD: &'static str = "resources";
+}
+
+impl PaginatedItem for ResourceTemplate {
+    const FIELD: &'static str = "resourceTemplates";
+}
+
+impl PaginatedItem for Prompt {
+    const FIELD: &'static str = "prompts";
+}
+
+impl PaginatedItem for Tool {
+    const FIELD: &'static str = "tools";
 }
 
 /// Parameters for the `resources/read` method.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReadResourceParams {
-    pub uri: String,
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
+    pub uri: McpStr,
 }
 
-/// A result from the `resources/read` method, containing resource contents.
+/// The payload of a `resources/read` result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ReadResourceResult {
-    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
-    pub meta: Option<HashMap<String, Value>>,
+pub struct ReadResourcePayload {
     pub contents: Vec<ResourceContents>,
-
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
 }
 
+/// A result from the `resources/read` method, containing resource contents.
+pub type ReadResourceResult = WithMeta<ReadResourcePayload>;
+
 /// Parameters for `resources/subscribe`.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SubscribeParams {
-    pub uri: String,
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
+    pub uri: McpStr,
 }
 
 /// Parameters for `resources/unsubscribe`.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UnsubscribeParams {
-    pub uri: String,
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
+    pub uri: McpStr,
 }
 
 /// Parameters for a `notifications/resources/updated` message.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ResourceUpdatedParams {
-    pub uri: String,
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
+    pub uri: McpStr,
 }
 
 /// A resource object that the server can read, possibly with extra metadata.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Resource {
-    pub uri: String,
-    pub name: String,
+    /// Protocol-level metadata (2025-06-18).
+    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
+    pub meta: Option<HashMap<String, Value>>,
+    pub uri: McpStr,
+    pub name: McpStr,
+    /// A human-readable name for display (2025-06-18).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub mime_type: Option<String>,
+    pub mime_type: Option<McpStr>,
+    /// The size of the raw contents in bytes, before any encoding, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
 
     #[serde(flatten)]
     pub annotated: Annotated,
@@ -420,110 +1112,152 @@ pub struct Resource {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ResourceTemplate {
-    pub uri_template: String,
-    pub name: String,
+    /// Protocol-level metadata (2025-06-18).
+    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
+    pub meta: Option<HashMap<String, Value>>,
+    pub uri_template: McpStr,
+    pub name: McpStr,
+    /// A human-readable name for display (2025-06-18).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub mime_type: Option<String>,
+    pub mime_type: Option<McpStr>,
 
     #[serde(flatten)]
     pub annotated: Annotated,
 }
 
 /// Contents of a resource. May be text or binary data.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// The spec gives resource contents no `type` member; the variant is chosen
+/// by whether a `text` or a `blob` member is present, and contents with
+/// neither or both are rejected.
+#[derive(Debug, Clone, Serialize)]
 #[serde(untagged)]
 pub enum ResourceContents {
     Text(TextResourceContents),
     Blob(BlobResourceContents),
 }
 
+impl<'de> Deserialize<'de> for ResourceContents {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_resource_contents(deserializer, ResourceContents::Text, ResourceContents::Blob)
+    }
+}
+
+/// Deserializes resource contents of any revision, choosing `text` or `blob`
+/// by which member is present.
+pub(crate) fn deserialize_resource_contents<'de, D, R, T, B>(
+    deserializer: D,
+    text: fn(T) -> R,
+    blob: fn(B) -> R,
+) -> Result<R, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+    B: serde::de::DeserializeOwned,
+{
+    use serde::de::Error;
+
+    let object = serde_json::Map::deserialize(deserializer)?;
+    let value = match (object.contains_key("text"), object.contains_key("blob")) {
+        (true, false) => serde_json::from_value(Value::Object(object)).map(text),
+        (false, true) => serde_json::from_value(Value::Object(object)).map(blob),
+        (true, true) => {
+            return Err(D::Error::custom(
+                "resource contents have both \"text\" and \"blob\"",
+            ))
+        }
+        (false, false) => {
+            return Err(D::Error::custom(
+                "resource contents need a \"text\" or \"blob\" member",
+            ))
+        }
+    };
+    value.map_err(D::Error::custom)
+}
+
 /// Represents textual resource contents.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
 pub struct TextResourceContents {
-    pub uri: String,
+    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
+    pub meta: Option<HashMap<String, Value>>,
+    pub uri: McpStr,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub mime_type: Option<String>,
+    pub mime_type: Option<McpStr>,
     pub text: String,
 }
 
 /// Represents binary resource contents.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
 pub struct BlobResourceContents {
-    pub uri: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub mime_type: Option<String>,
-    pub blob: String,
-}
-
-/// A result containing a list of prompts known to the server.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ListPromptsResult {
     #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
     pub meta: Option<HashMap<String, Value>>,
+    pub uri: McpStr,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub next_cursor: Option<Cursor>,
-
-    pub prompts: Vec<Prompt>,
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
+    pub mime_type: Option<McpStr>,
+    pub blob: String,
 }
 
 /// Parameters for `prompts/get`.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetPromptParams {
-    pub name: String,
+    pub name: McpStr,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub arguments: Option<HashMap<String, String>>,
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
 }
 
-/// A result returned by `prompts/get`.
+/// The payload of a `prompts/get` result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct GetPromptResult {
-    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
-    pub meta: Option<HashMap<String, Value>>,
+pub struct GetPromptPayload<C = PromptContent> {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    pub messages: Vec<PromptMessage>,
-
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
+    pub messages: Vec<PromptMessage<C>>,
 }
 
+/// A result returned by `prompts/get`.
+pub type GetPromptResult<C = PromptContent> = WithMeta<GetPromptPayload<C>>;
+
 /// A prompt object or prompt template.
+#[mcp_extras]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Prompt {
-    pub name: String,
+    pub name: McpStr,
+    /// A human-readable name for display (2025-06-18).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub arguments: Option<Vec<PromptArgument>>,
-
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
 }
 
 /// Arguments accepted by a prompt, potentially required.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PromptArgument {
-    pub name: String,
+    pub name: McpStr,
+    /// A human-readable name for display (2025-06-18).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<bool>,
-
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
 }
 
 /// A role in a conversation: either "user" or "assistant".
@@ -537,43 +1271,90 @@ pub enum Role {
 /// A message returned as part of a prompt result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct PromptMessage {
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
+pub struct PromptMessage<C = PromptContent> {
     pub role: Role,
-    pub content: PromptContent,
+    pub content: C,
 }
 
-/// Represents the content of a prompt message: text, image, or embedded resource.
+/// Represents the content of a prompt message or tool result: text, image,
+/// audio, a resource link, or an embedded resource. The variant is selected
+/// by the `type` member.
+///
+/// This is the union of every supported revision's content; the revision
+/// modules define the narrower per-revision unions.
+#[non_exhaustive]
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
+#[serde(tag = "type", rename_all = "lowercase")]
 pub enum PromptContent {
     Text(TextContent),
     Image(ImageContent),
+    Audio(AudioContent),
+    #[serde(rename = "resource_link")]
+    ResourceLink(ResourceLink),
     Resource(EmbeddedResource),
 }
 
+/// Represents a text, image, or audio message in sampling. The variant is
+/// selected by the `type` member.
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SamplingContent {
+    Text(TextContent),
+    Image(ImageContent),
+    Audio(AudioContent),
+}
+
+/// A link to a resource, given by its descriptor instead of its contents,
+/// so that tools can point at large resources (2025-06-18).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceLink {
+    #[serde(flatten)]
+    pub resource: Resource,
+}
+
+impl From<Resource> for ResourceLink {
+    fn from(resource: Resource) -> Self {
+        ResourceLink { resource }
+    }
+}
+
 /// An embedded resource, which can contain a text or blob resource internally.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EmbeddedResource {
-    #[serde(rename = "type")]
-    pub kind: String, // e.g., "resource"
+    /// Protocol-level metadata (2025-06-18).
+    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
+    pub meta: Option<HashMap<String, Value>>,
     pub resource: ResourceContents,
 
     #[serde(flatten)]
     pub annotated: Annotated,
 }
 
+impl EmbeddedResource {
+    /// An embedded resource without annotations.
+    pub fn new(resource: ResourceContents) -> Self {
+        EmbeddedResource {
+            meta: None,
+            resource,
+            annotated: Annotated::default(),
+        }
+    }
+}
+
 /// Allows attaching optional annotations and arbitrary extra fields.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[mcp_extras(extra_only)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Annotated {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub annotations: Option<Annotations>,
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
 }
 
 /// Contains optional annotation data such as `audience` or `priority`.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Annotations {
@@ -581,69 +1362,145 @@ pub struct Annotations {
     pub audience: Option<Vec<Role>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub priority: Option<f64>,
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
+    /// When the annotated object was last modified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<Timestamp>,
 }
 
 /// Represents text content in a prompt or message.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TextContent {
-    #[serde(rename = "type")]
-    pub kind: String, // "text"
+    /// Protocol-level metadata (2025-06-18).
+    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
+    pub meta: Option<HashMap<String, Value>>,
     pub text: String,
 
     #[serde(flatten)]
     pub annotated: Annotated,
 }
 
+impl TextContent {
+    /// Plain text content without annotations.
+    pub fn new(text: impl Into<String>) -> Self {
+        TextContent {
+            meta: None,
+            text: text.into(),
+            annotated: Annotated::default(),
+        }
+    }
+
+    /// Truncates the text to at most `max_chars` characters, returning
+    /// whether anything was cut off.
+    pub fn truncate_to(&mut self, max_chars: usize) -> bool {
+        match self.text.char_indices().nth(max_chars) {
+            Some((end, _)) => {
+                self.text.truncate(end);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// A copy with ANSI escape sequences removed and line endings
+    /// normalized; see [`crate::sanitize`].
+    pub fn sanitized(&self) -> Self {
+        self.sanitized_with(&crate::sanitize::SanitizeOptions::default())
+    }
+
+    /// A copy sanitized with the given options.
+    pub fn sanitized_with(&self, options: &crate::sanitize::SanitizeOptions) -> Self {
+        TextContent {
+            text: crate::sanitize::sanitize(&self.text, options),
+            ..self.clone()
+        }
+    }
+
+    /// Truncates the text to at most `max_bytes` bytes of UTF-8, cutting at
+    /// a character boundary, and returns whether anything was cut off.
+    pub fn truncate_to_bytes(&mut self, max_bytes: usize) -> bool {
+        if self.text.len() <= max_bytes {
+            return false;
+        }
+        let mut end = max_bytes;
+        while !self.text.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.text.truncate(end);
+        true
+    }
+}
+
 /// Represents image content, stored in base64.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ImageContent {
-    #[serde(rename = "type")]
-    pub kind: String, // "image"
+    /// Protocol-level metadata (2025-06-18).
+    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
+    pub meta: Option<HashMap<String, Value>>,
     pub data: String,
-    pub mime_type: String,
+    pub mime_type: McpStr,
 
     #[serde(flatten)]
     pub annotated: Annotated,
 }
 
-/// A result listing server-provided tools.
+impl ImageContent {
+    /// Base64-encoded image data without annotations.
+    pub fn new(data: impl Into<String>, mime_type: impl Into<McpStr>) -> Self {
+        ImageContent {
+            meta: None,
+            data: data.into(),
+            mime_type: mime_type.into(),
+            annotated: Annotated::default(),
+        }
+    }
+}
+
+/// Represents audio content, stored in base64 (2025-03-26).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ListToolsResult {
+pub struct AudioContent {
+    /// Protocol-level metadata (2025-06-18).
     #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
     pub meta: Option<HashMap<String, Value>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub next_cursor: Option<Cursor>,
-    pub tools: Vec<Tool>,
+    pub data: String,
+    pub mime_type: McpStr,
 
     #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
+    pub annotated: Annotated,
+}
+
+impl AudioContent {
+    /// Base64-encoded audio data without annotations.
+    pub fn new(data: impl Into<String>, mime_type: impl Into<McpStr>) -> Self {
+        AudioContent {
+            meta: None,
+            data: data.into(),
+            mime_type: mime_type.into(),
+            annotated: Annotated::default(),
+        }
+    }
 }
 
 /// Parameters for the `tools/call` method.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CallToolParams {
-    pub name: String,
+    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
+    pub meta: Option<RequestMeta>,
+    pub name: McpStr,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub arguments: Option<HashMap<String, Value>>,
-
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
 }
 
-/// A result from the `tools/call` method, potentially indicating an error.
+/// The payload of a `tools/call` result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct CallToolResult {
-    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
-    pub meta: Option<HashMap<String, Value>>,
-    pub content: Vec<PromptContent>,
-    
+pub struct CallToolPayload<C = PromptContent> {
+    pub content: Vec<C>,
+
     /// Structured content that conforms to the tool's output schema.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub structured_content: Option<Value>,
@@ -651,14 +1508,60 @@ pub struct CallToolResult {
     /// True if the tool call ended in an error.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_error: Option<bool>,
+}
 
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
+/// A result from the `tools/call` method, potentially indicating an error.
+pub type CallToolResult<C = PromptContent> = WithMeta<CallToolPayload<C>>;
+
+impl CallToolResult {
+    /// A successful result carrying a single text block.
+    pub fn text(text: impl Into<String>) -> Self {
+        CallToolResult::new(CallToolPayload {
+            content: vec![PromptContent::Text(TextContent::new(text))],
+            structured_content: None,
+            is_error: None,
+        })
+    }
+
+    /// A tool-level error result (`isError: true`) with the message as text.
+    pub fn error(message: impl Into<String>) -> Self {
+        let mut result = CallToolResult::text(message);
+        result.is_error = Some(true);
+        result
+    }
+}
+
+/// Lets tool handlers return a `Result` and convert it with `.into()`.
+///
+/// `Ok` values that serialize to a JSON object become `structuredContent`,
+/// with their JSON serialization as a text fallback for clients that ignore
+/// structured output; the spec requires structured content to be an object.
+/// A string is sent as its raw text, and any other value as its JSON text
+/// only. `Err` values become a tool-level error result carrying the error
+/// message as text.
+impl<T: Serialize, E: fmt::Display> From<Result<T, E>> for CallToolResult {
+    fn from(result: Result<T, E>) -> Self {
+        let value = match result {
+            Ok(value) => serde_json::to_value(value),
+            Err(err) => return CallToolResult::error(err.to_string()),
+        };
+        match value {
+            Ok(Value::String(text)) => CallToolResult::text(text),
+            Ok(value @ Value::Object(_)) => {
+                let mut result = CallToolResult::text(value.to_string());
+                result.structured_content = Some(value);
+                result
+            }
+            Ok(value) => CallToolResult::text(value.to_string()),
+            Err(err) => CallToolResult::error(format!("failed to serialize tool output: {}", err)),
+        }
+    }
 }
 
 /// Annotations that describe tool behavior hints.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
 pub struct ToolAnnotations {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
@@ -673,10 +1576,11 @@ pub struct ToolAnnotations {
 }
 
 /// Defines a tool that can be invoked by the client.
+#[mcp_extras]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Tool {
-    pub name: String,
+    pub name: McpStr,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -686,12 +1590,14 @@ pub struct Tool {
     pub output_schema: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub annotations: Option<ToolAnnotations>,
-
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
 }
 
 /// Describes the schema for a tool's input parameters.
+///
+/// This is an open JSON Schema object, so members other than `type`,
+/// `properties`, and `required` (such as `$schema` or
+/// `additionalProperties`) are ignored even with the `strict-fields`
+/// feature.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ToolInputSchema {
@@ -703,17 +1609,80 @@ pub struct ToolInputSchema {
     pub required: Option<Vec<String>>,
 }
 
+/// Describes the structure of a tool's `structuredContent`. Build one with
+/// [`ToolOutputSchema::object`] and store it in `Tool::output_schema` via
+/// [`Tool::with_output_schema`].
+#[mcp_extras(extra_only)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolOutputSchema {
+    #[serde(rename = "type")]
+    pub type_: String, // "object"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<HashMap<String, Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<Vec<String>>,
+}
+
+impl ToolOutputSchema {
+    /// An object schema with no properties.
+    pub fn object() -> Self {
+        ToolOutputSchema {
+            type_: "object".to_string(),
+            properties: None,
+            required: None,
+            extra: HashMap::new(),
+        }
+    }
+
+    /// Adds a property with its JSON Schema.
+    pub fn property(mut self, name: impl Into<String>, schema: Value) -> Self {
+        self.properties
+            .get_or_insert_with(HashMap::new)
+            .insert(name.into(), schema);
+        self
+    }
+
+    /// Marks a property as always present.
+    pub fn required(mut self, name: impl Into<String>) -> Self {
+        self.required.get_or_insert_with(Vec::new).push(name.into());
+        self
+    }
+}
+
+impl From<ToolOutputSchema> for Value {
+    fn from(schema: ToolOutputSchema) -> Self {
+        serde_json::to_value(schema).expect("output schema serializes")
+    }
+}
+
+impl Tool {
+    /// Sets the tool's output schema.
+    pub fn with_output_schema(mut self, schema: ToolOutputSchema) -> Self {
+        self.output_schema = Some(schema.into());
+        self
+    }
+
+    /// The output schema as a [`ToolOutputSchema`], if there is one and it
+    /// has that shape.
+    pub fn typed_output_schema(&self) -> Option<ToolOutputSchema> {
+        self.output_schema
+            .as_ref()
+            .and_then(|schema| ToolOutputSchema::deserialize(schema).ok())
+    }
+}
+
 /// Parameters for enabling or adjusting server-side logging.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SetLevelParams {
     pub level: LoggingLevel,
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
 }
 
-/// Syslog-like logging severity levels.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Syslog-like logging severity levels, ordered from least (`Debug`) to most
+/// (`Emergency`) severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum LoggingLevel {
     Debug,
@@ -726,7 +1695,23 @@ pub enum LoggingLevel {
     Emergency,
 }
 
+impl fmt::Display for LoggingLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LoggingLevel::Debug => "debug",
+            LoggingLevel::Info => "info",
+            LoggingLevel::Notice => "notice",
+            LoggingLevel::Warning => "warning",
+            LoggingLevel::Error => "error",
+            LoggingLevel::Critical => "critical",
+            LoggingLevel::Alert => "alert",
+            LoggingLevel::Emergency => "emergency",
+        })
+    }
+}
+
 /// A notification with a log message from the server.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LoggingMessageParams {
@@ -734,16 +1719,14 @@ pub struct LoggingMessageParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub logger: Option<String>,
     pub data: Value,
-
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
 }
 
 /// Parameters for the `sampling/createMessage` method.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct CreateMessageParams {
-    pub messages: Vec<SamplingMessage>,
+pub struct CreateMessageParams<C = SamplingContent> {
+    pub messages: Vec<SamplingMessage<C>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub model_preferences: Option<ModelPreferences>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -757,42 +1740,208 @@ pub struct CreateMessageParams {
     pub stop_sequences: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, Value>>,
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
 }
 
-/// A result from `sampling/createMessage`.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct CreateMessageResult {
-    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
-    pub meta: Option<HashMap<String, Value>>,
+impl CreateMessageParams {
+    /// Builds sampling parameters from the messages of a `prompts/get` result.
+    ///
+    /// Sampling messages only carry text, images, and audio, so embedded
+    /// resources are inlined: text resources become text content, image and
+    /// audio blobs become image and audio content, and other blobs are
+    /// replaced by a short text placeholder naming the resource.
+    pub fn from_prompt(result: &GetPromptResult, max_tokens: i64) -> Self {
+        let messages = result
+            .messages
+            .iter()
+            .map(|message| {
+                SamplingMessage::new(message.role.clone(), inline_prompt_content(&message.content))
+            })
+            .collect();
+        CreateMessageParams {
+            messages,
+            model_preferences: None,
+            system_prompt: None,
+            include_context: None,
+            temperature: None,
+            max_tokens,
+            stop_sequences: None,
+            metadata: None,
+            extra: HashMap::new(),
+        }
+    }
+}
+
+/// Error returned when a message cannot be moved between the prompts and
+/// sampling subsystems without losing information.
+#[derive(Debug, Clone)]
+pub enum MessageConversionError {
+    /// Sampling messages cannot carry embedded resources. Use
+    /// [`CreateMessageParams::from_prompt`] to inline them instead.
+    EmbeddedResource(Box<EmbeddedResource>),
+    /// Sampling messages cannot carry resource links either.
+    ResourceLink(Box<ResourceLink>),
+    /// The target carries exactly one content block, but the draft
+    /// sampling message had this many.
+    MultipleContents(usize),
+}
+
+impl fmt::Display for MessageConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MessageConversionError::EmbeddedResource(_) => {
+                f.write_str("embedded resources cannot be used in sampling messages")
+            }
+            MessageConversionError::ResourceLink(_) => {
+                f.write_str("resource links cannot be used in sampling messages")
+            }
+            MessageConversionError::MultipleContents(count) => {
+                write!(f, "expected a single content block, found {}", count)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MessageConversionError {}
+
+/// Text, image, and audio content convert as-is; embedded resources and
+/// resource links are rejected.
+impl TryFrom<PromptContent> for SamplingContent {
+    type Error = MessageConversionError;
+
+    fn try_from(content: PromptContent) -> Result<Self, Self::Error> {
+        match content {
+            PromptContent::Text(content) => Ok(SamplingContent::Text(content)),
+            PromptContent::Image(content) => Ok(SamplingContent::Image(content)),
+            PromptContent::Audio(content) => Ok(SamplingContent::Audio(content)),
+            PromptContent::ResourceLink(link) => {
+                Err(MessageConversionError::ResourceLink(Box::new(link)))
+            }
+            PromptContent::Resource(resource) => {
+                Err(MessageConversionError::EmbeddedResource(Box::new(resource)))
+            }
+        }
+    }
+}
+
+impl From<SamplingContent> for PromptContent {
+    fn from(content: SamplingContent) -> Self {
+        match content {
+            SamplingContent::Text(content) => PromptContent::Text(content),
+            SamplingContent::Image(content) => PromptContent::Image(content),
+            SamplingContent::Audio(content) => PromptContent::Audio(content),
+        }
+    }
+}
+
+/// Fails if the prompt message embeds or links a resource, since sampling
+/// messages only carry text, images, and audio.
+impl TryFrom<PromptMessage> for SamplingMessage {
+    type Error = MessageConversionError;
+
+    fn try_from(message: PromptMessage) -> Result<Self, Self::Error> {
+        Ok(SamplingMessage::new(message.role, message.content.try_into()?))
+    }
+}
+
+impl From<SamplingMessage> for PromptMessage {
+    fn from(message: SamplingMessage) -> Self {
+        PromptMessage {
+            role: message.role,
+            content: message.content.into(),
+        }
+    }
+}
+
+fn inline_prompt_content(content: &PromptContent) -> SamplingContent {
+    let text = |text: String, meta: &Option<HashMap<String, Value>>, annotated: &Annotated| {
+        SamplingContent::Text(TextContent {
+            meta: meta.clone(),
+            annotated: annotated.clone(),
+            ..TextContent::new(text)
+        })
+    };
+    match content {
+        PromptContent::Text(content) => SamplingContent::Text(content.clone()),
+        PromptContent::Image(content) => SamplingContent::Image(content.clone()),
+        PromptContent::Audio(content) => SamplingContent::Audio(content.clone()),
+        PromptContent::ResourceLink(link) => text(
+            format!("[resource {}]", link.resource.uri),
+            &link.resource.meta,
+            &link.resource.annotated,
+        ),
+        PromptContent::Resource(embedded) => match &embedded.resource {
+            ResourceContents::Text(resource) => {
+                text(resource.text.clone(), &embedded.meta, &embedded.annotated)
+            }
+            ResourceContents::Blob(resource) => match &resource.mime_type {
+                Some(mime_type) if mime_type.starts_with("image/") => {
+                    SamplingContent::Image(ImageContent {
+                        meta: embedded.meta.clone(),
+                        data: resource.blob.clone(),
+                        mime_type: mime_type.clone(),
+                        annotated: embedded.annotated.clone(),
+                    })
+                }
+                Some(mime_type) if mime_type.starts_with("audio/") => {
+                    SamplingContent::Audio(AudioContent {
+                        meta: embedded.meta.clone(),
+                        data: resource.blob.clone(),
+                        mime_type: mime_type.clone(),
+                        annotated: embedded.annotated.clone(),
+                    })
+                }
+                mime_type => text(
+                    format!(
+                        "[binary resource {} ({})]",
+                        resource.uri,
+                        mime_type.as_deref().unwrap_or("unknown type")
+                    ),
+                    &embedded.meta,
+                    &embedded.annotated,
+                ),
+            },
+        },
+    }
+}
+
+/// The payload of a `sampling/createMessage` result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMessagePayload<C = SamplingContent> {
     pub role: Role,
-    pub content: SamplingContent,
+    pub content: C,
     pub model: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stop_reason: Option<String>,
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
 }
 
-/// Represents a text or image message in sampling.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
-pub enum SamplingContent {
-    Text(TextContent),
-    Image(ImageContent),
-}
+/// A result from `sampling/createMessage`.
+pub type CreateMessageResult<C = SamplingContent> = WithMeta<CreateMessagePayload<C>>;
 
 /// A sampling message (one item in `CreateMessageParams`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct SamplingMessage {
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
+pub struct SamplingMessage<C = SamplingContent> {
     pub role: Role,
-    pub content: SamplingContent,
+    pub content: C,
+}
+
+impl SamplingMessage {
+    /// A message with a single content block.
+    pub fn new(role: Role, content: SamplingContent) -> Self {
+        SamplingMessage { role, content }
+    }
+
+    /// The message's content blocks: always exactly one in the released
+    /// revisions.
+    pub fn contents(&self) -> &[SamplingContent] {
+        std::slice::from_ref(&self.content)
+    }
 }
 
 /// Preferences for selecting a model, including cost or speed priorities.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ModelPreferences {
@@ -804,66 +1953,153 @@ pub struct ModelPreferences {
     pub speed_priority: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub intelligence_priority: Option<f64>,
-
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
 }
 
 /// A hint to use when selecting a model (e.g., substring matches).
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ModelHint {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
+}
+
+impl ModelHint {
+    /// A hint naming (part of) a model, e.g. `"claude-3-5-sonnet"` or `"sonnet"`.
+    pub fn new(name: impl Into<String>) -> Self {
+        ModelHint {
+            name: Some(name.into()),
+            extra: HashMap::new(),
+        }
+    }
+
+    /// Whether `model_name` satisfies this hint.
+    ///
+    /// Following the spec, the hint's name is treated as a substring of the
+    /// model name (compared case-insensitively), so `"sonnet"` matches
+    /// `"claude-3-5-sonnet-20241022"`. A hint without a name matches nothing.
+    pub fn matches(&self, model_name: &str) -> bool {
+        match &self.name {
+            Some(name) if !name.is_empty() => model_name
+                .to_lowercase()
+                .contains(&name.to_lowercase()),
+            _ => false,
+        }
+    }
 }
 
 /// Parameters for `completion/complete`.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CompleteParams {
     #[serde(rename = "ref")]
     pub r#ref: ReferenceType,
     pub argument: CompleteArgument,
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<CompleteContext>,
 }
 
-/// A result from `completion/complete`.
+/// What the client already knows when asking for completions.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
+pub struct CompleteContext {
+    /// Argument values already resolved, keyed by argument name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub arguments: HashMap<String, String>,
+}
+
+/// The payload of a `completion/complete` result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct CompleteResult {
-    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
-    pub meta: Option<HashMap<String, Value>>,
+pub struct CompletePayload {
     pub completion: CompletionData,
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
 }
 
-/// A reference to either a resource or a prompt.
+/// A result from `completion/complete`.
+pub type CompleteResult = WithMeta<CompletePayload>;
+
+/// A reference to either a resource (template) or a prompt.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", tag = "type")]
 pub enum ReferenceType {
+    /// A reference to a resource template, keyed by the template's URI (or
+    /// the URI of a concrete resource). Newer spec revisions name this
+    /// `ResourceTemplateReference`; it shares the `ref/resource` tag with the
+    /// older `ResourceReference`, so both forms deserialize into this variant.
     #[serde(rename = "ref/resource")]
     Resource { uri: String },
     #[serde(rename = "ref/prompt")]
-    Prompt { name: String },
+    Prompt {
+        name: String,
+        /// A human-readable name for display (2025-06-18).
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+    },
+}
+
+impl ReferenceType {
+    /// A reference to a prompt by name.
+    pub fn prompt(name: impl Into<String>) -> Self {
+        ReferenceType::Prompt {
+            name: name.into(),
+            title: None,
+        }
+    }
+
+    /// A reference to a resource template (or resource) by URI.
+    pub fn resource_template(uri: impl Into<String>) -> Self {
+        ReferenceType::Resource { uri: uri.into() }
+    }
+
+    /// A reference to a resource by URI. Builds the same
+    /// [`ReferenceType::Resource`] as [`ReferenceType::resource_template`].
+    pub fn resource(uri: impl Into<String>) -> Self {
+        ReferenceType::resource_template(uri)
+    }
+
+    /// The referenced URI, for resource references.
+    pub fn uri(&self) -> Option<&str> {
+        match self {
+            ReferenceType::Resource { uri } => Some(uri),
+            ReferenceType::Prompt { .. } => None,
+        }
+    }
+
+    /// The referenced prompt name, for prompt references.
+    pub fn prompt_name(&self) -> Option<&str> {
+        match self {
+            ReferenceType::Prompt { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ReferenceType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReferenceType::Resource { uri } => {
+                write!(f, "resource:{}", uri)
+            }
+            ReferenceType::Prompt { name, .. } => write!(f, "prompt:{}", name),
+        }
+    }
 }
 
 /// An argument for `completion/complete` (name + value).
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CompleteArgument {
     pub name: String,
     pub value: String,
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
 }
 
 /// Data returned in the `completion` field, containing possible completions.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
 pub struct CompletionData {
     pub values: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -872,307 +2108,611 @@ pub struct CompletionData {
     pub has_more: Option<bool>,
 }
 
+/// The maximum number of values a `completion/complete` result may carry.
+pub const MAX_COMPLETION_VALUES: usize = 100;
+
+impl CompletionData {
+    /// Completion data with no values.
+    pub fn empty() -> Self {
+        CompletionData {
+            values: Vec::new(),
+            total: Some(0),
+            has_more: Some(false),
+        }
+    }
+
+    /// Collects completion values, keeping at most [`MAX_COMPLETION_VALUES`]
+    /// and recording the full count in `total` and `has_more`.
+    pub fn from_values<I, S>(values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut total = 0usize;
+        let mut kept = Vec::new();
+        for value in values {
+            if total < MAX_COMPLETION_VALUES {
+                kept.push(value.into());
+            }
+            total += 1;
+        }
+        CompletionData {
+            values: kept,
+            total: Some(total as i64),
+            has_more: Some(total > MAX_COMPLETION_VALUES),
+        }
+    }
+}
+
+impl CompleteResult {
+    /// Builds a result from completion values, truncated to
+    /// [`MAX_COMPLETION_VALUES`] as required by the spec.
+    pub fn from_values<I, S>(values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        CompleteResult::new(CompletePayload {
+            completion: CompletionData::from_values(values),
+        })
+    }
+}
+
 /// Parameters for `roots/list`.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
-pub struct ListRootsParams {
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
-}
+pub struct ListRootsParams {}
 
-/// A result listing root URIs from the client.
+/// The payload of a `roots/list` result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ListRootsResult {
-    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
-    pub meta: Option<HashMap<String, Value>>,
+pub struct ListRootsPayload {
     pub roots: Vec<Root>,
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
 }
 
+/// A result listing root URIs from the client.
+pub type ListRootsResult = WithMeta<ListRootsPayload>;
+
 /// Represents a root directory or file, typically starting with `file://`.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Root {
-    pub uri: String,
+    /// Protocol-level metadata (2025-06-18).
+    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
+    pub meta: Option<HashMap<String, Value>>,
+    pub uri: McpStr,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
 }
 
 /// Parameters for the elicitation/create request.
+#[mcp_extras(extra_only)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ElicitationCreateParams {
     /// The prompt message to display to the user.
     pub message: String,
-    
-    /// A JSON Schema defining the structure of the expected user response.
-    pub requested_schema: Value,
-    
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
+
+    /// The structure of the expected user response.
+    pub requested_schema: RequestedSchema,
 }
 
-/// Result from the elicitation/create request.
+/// The payload of an `elicitation/create` result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ElicitationCreateResult {
+pub struct ElicitationCreatePayload {
     /// The action taken by the user.
     pub action: ElicitationAction,
-    
+
     /// The user's response conforming to the requested schema (if accepted).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<Value>,
-    
-    #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
 }
 
+/// Result from the elicitation/create request.
+pub type ElicitationCreateResult = WithMeta<ElicitationCreatePayload>;
+
 /// Possible actions for elicitation responses.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ElicitationAction {
     Accept,
-    Reject,
+    /// The user explicitly declined. Early drafts of the spec called this
+    /// `reject`, which is still accepted when deserializing.
+    #[serde(alias = "reject")]
+    Decline,
     Cancel,
 }
+
+/// Deserializes a `params` member that the schema marks optional: a missing
+/// member (via `#[serde(default)]`) and an explicit `null` both produce the
+/// default value.
+pub(crate) fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
 /// A union of all possible client requests. The `method` field identifies the variant.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "method", rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
 pub enum ClientRequest {
     #[serde(rename = "ping")]
     Ping {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         id: RequestId,
-        #[serde(default)]
+        #[serde(default, deserialize_with = "null_as_default")]
         params: PingParams,
     },
     #[serde(rename = "initialize")]
     Initialize {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         id: RequestId,
         params: InitializeParams,
     },
     #[serde(rename = "completion/complete")]
     Complete {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         id: RequestId,
         params: CompleteParams,
     },
     #[serde(rename = "logging/setLevel")]
     SetLevel {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         id: RequestId,
         params: SetLevelParams,
     },
     #[serde(rename = "prompts/get")]
     GetPrompt {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         id: RequestId,
         params: GetPromptParams,
     },
     #[serde(rename = "prompts/list")]
     ListPrompts {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         id: RequestId,
-        #[serde(default)]
-        params: PaginatedParams,
+        #[serde(default, deserialize_with = "null_as_default")]
+        params: ListPromptsParams,
     },
     #[serde(rename = "resources/list")]
     ListResources {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         id: RequestId,
-        #[serde(default)]
-        params: PaginatedParams,
+        #[serde(default, deserialize_with = "null_as_default")]
+        params: ListResourcesParams,
     },
     #[serde(rename = "resources/templates/list")]
     ListResourceTemplates {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         id: RequestId,
-        #[serde(default)]
-        params: PaginatedParams,
+        #[serde(default, deserialize_with = "null_as_default")]
+        params: ListResourceTemplatesParams,
     },
     #[serde(rename = "resources/read")]
     ReadResource {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         id: RequestId,
         params: ReadResourceParams,
     },
     #[serde(rename = "resources/subscribe")]
     Subscribe {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         id: RequestId,
         params: SubscribeParams,
     },
     #[serde(rename = "resources/unsubscribe")]
     Unsubscribe {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         id: RequestId,
         params: UnsubscribeParams,
     },
     #[serde(rename = "tools/call")]
     CallTool {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         id: RequestId,
         params: CallToolParams,
     },
     #[serde(rename = "tools/list")]
     ListTools {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         id: RequestId,
-        #[serde(default)]
-        params: PaginatedParams,
+        #[serde(default, deserialize_with = "null_as_default")]
+        params: ListToolsParams,
     },
+    /// `elicitation/create` is sent by servers; this variant only remains
+    /// so that existing code keeps compiling.
+    #[deprecated(
+        note = "elicitation/create is a server request; use `ServerRequest::ElicitationCreate`"
+    )]
     #[serde(rename = "elicitation/create")]
     ElicitationCreate {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         id: RequestId,
         params: ElicitationCreateParams,
     },
 }
 
+/// Short, human-readable summary of a client request: the method, the request
+/// id, and the most salient parameters (tool name, resource URI, ...).
+/// Intended for logs and inspectors; use `serde_json` for the full message.
+impl fmt::Display for ClientRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientRequest::Ping { id, .. } => write!(f, "ping #{}", id),
+            ClientRequest::Initialize { id, params, .. } => write!(
+                f,
+                "initialize #{} client={}/{} protocol={}",
+                id, params.client_info.name, params.client_info.version, params.protocol_version
+            ),
+            ClientRequest::Complete { id, params, .. } => {
+                write!(
+                    f,
+                    "completion/complete #{} ref={} argument={}",
+                    id, params.r#ref, params.argument.name
+                )
+            }
+            ClientRequest::SetLevel { id, params, .. } => {
+                write!(f, "logging/setLevel #{} level={}", id, params.level)
+            }
+            ClientRequest::GetPrompt { id, params, .. } => {
+                write!(f, "prompts/get #{} name={}", id, params.name)
+            }
+            ClientRequest::ListPrompts { id, params, .. } => {
+                write_list(f, "prompts/list", id, &params.0)
+            }
+            ClientRequest::ListResources { id, params, .. } => {
+                write_list(f, "resources/list", id, &params.0)
+            }
+            ClientRequest::ListResourceTemplates { id, params, .. } => {
+                write_list(f, "resources/templates/list", id, &params.0)
+            }
+            ClientRequest::ReadResource { id, params, .. } => {
+                write!(f, "resources/read #{} uri={}", id, params.uri)
+            }
+            ClientRequest::Subscribe { id, params, .. } => {
+                write!(f, "resources/subscribe #{} uri={}", id, params.uri)
+            }
+            ClientRequest::Unsubscribe { id, params, .. } => {
+                write!(f, "resources/unsubscribe #{} uri={}", id, params.uri)
+            }
+            ClientRequest::CallTool { id, params, .. } => {
+                write!(f, "tools/call #{} name={}", id, params.name)
+            }
+            ClientRequest::ListTools { id, params, .. } => {
+                write_list(f, "tools/list", id, &params.0)
+            }
+            #[allow(deprecated)]
+            ClientRequest::ElicitationCreate { id, params, .. } => {
+                write!(f, "elicitation/create #{} message={:?}", id, params.message)
+            }
+        }
+    }
+}
+
+fn write_list(
+    f: &mut fmt::Formatter<'_>,
+    method: &str,
+    id: &RequestId,
+    params: &PaginatedParams,
+) -> fmt::Result {
+    write!(f, "{} #{}", method, id)?;
+    if let Some(cursor) = &params.cursor {
+        write!(f, " cursor={}", cursor)?;
+    }
+    Ok(())
+}
+
 /// A union of all possible client notifications.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "method", rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
 pub enum ClientNotification {
     #[serde(rename = "notifications/cancelled")]
     Cancelled {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         params: CancelledNotificationParams,
     },
     #[serde(rename = "notifications/progress")]
     Progress {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         params: ProgressNotificationParams,
     },
     #[serde(rename = "notifications/initialized")]
     Initialized {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
-        #[serde(default)]
+        json_rpc: JsonRpcVersion,
+        #[serde(default, deserialize_with = "null_as_default")]
         params: MCPNotificationParams,
     },
     #[serde(rename = "notifications/roots/list_changed")]
     RootsListChanged {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
-        #[serde(default)]
+        json_rpc: JsonRpcVersion,
+        #[serde(default, deserialize_with = "null_as_default")]
         params: MCPNotificationParams,
     },
 }
 
 /// A union of possible server requests.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "method", rename_all = "camelCase")]
-pub enum ServerRequest {
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
+pub enum ServerRequest<C = SamplingContent> {
     #[serde(rename = "ping")]
     Ping {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         id: RequestId,
-        #[serde(default)]
+        #[serde(default, deserialize_with = "null_as_default")]
         params: PingParams,
     },
     #[serde(rename = "sampling/createMessage")]
     CreateMessage {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         id: RequestId,
-        params: CreateMessageParams,
+        params: CreateMessageParams<C>,
     },
     #[serde(rename = "roots/list")]
     ListRoots {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         id: RequestId,
-        #[serde(default)]
+        #[serde(default, deserialize_with = "null_as_default")]
         params: ListRootsParams,
     },
+    /// Asks the user for input through the client (2025-06-18).
+    #[serde(rename = "elicitation/create")]
+    ElicitationCreate {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        params: ElicitationCreateParams,
+    },
+}
+
+/// Short, human-readable summary of a server request, mirroring the
+/// `Display` implementation of [`ClientRequest`].
+impl fmt::Display for ServerRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServerRequest::Ping { id, .. } => write!(f, "ping #{}", id),
+            ServerRequest::CreateMessage { id, params, .. } => write!(
+                f,
+                "sampling/createMessage #{} messages={} max_tokens={}",
+                id,
+                params.messages.len(),
+                params.max_tokens
+            ),
+            ServerRequest::ListRoots { id, .. } => write!(f, "roots/list #{}", id),
+            ServerRequest::ElicitationCreate { id, params, .. } => {
+                write!(f, "elicitation/create #{} message={:?}", id, params.message)
+            }
+        }
+    }
 }
 
 /// A union of possible server notifications.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "method", rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
 pub enum ServerNotification {
     #[serde(rename = "notifications/cancelled")]
     Cancelled {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         params: CancelledNotificationParams,
     },
     #[serde(rename = "notifications/progress")]
     Progress {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         params: ProgressNotificationParams,
     },
     #[serde(rename = "notifications/message")]
     LoggingMessage {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         params: LoggingMessageParams,
     },
     #[serde(rename = "notifications/resources/updated")]
     ResourceUpdated {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
+        json_rpc: JsonRpcVersion,
         params: ResourceUpdatedParams,
     },
     #[serde(rename = "notifications/resources/list_changed")]
     ResourceListChanged {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
-        #[serde(default)]
+        json_rpc: JsonRpcVersion,
+        #[serde(default, deserialize_with = "null_as_default")]
         params: MCPNotificationParams,
     },
     #[serde(rename = "notifications/tools/list_changed")]
     ToolListChanged {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
-        #[serde(default)]
+        json_rpc: JsonRpcVersion,
+        #[serde(default, deserialize_with = "null_as_default")]
         params: MCPNotificationParams,
     },
     #[serde(rename = "notifications/prompts/list_changed")]
     PromptListChanged {
         #[serde(rename = "jsonrpc")]
-        json_rpc: String,
-        #[serde(default)]
+        json_rpc: JsonRpcVersion,
+        #[serde(default, deserialize_with = "null_as_default")]
         params: MCPNotificationParams,
     },
 }
 
 /// A union of all possible server results.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
-pub enum ServerResult {
-    Empty(EmptyResult),
+pub enum ServerResult<C = PromptContent> {
     Initialize(InitializeResult),
     Complete(CompleteResult),
-    GetPrompt(GetPromptResult),
+    GetPrompt(GetPromptResult<C>),
     ListPrompts(ListPromptsResult),
     ListResources(ListResourcesResult),
     ListResourceTemplates(ListResourceTemplatesResult),
     ReadResource(ReadResourceResult),
-    CallTool(CallToolResult),
+    CallTool(CallToolResult<C>),
     ListTools(ListToolsResult),
+    /// `elicitation/create` is answered by clients; this variant only
+    /// remains so that existing code keeps compiling.
+    #[deprecated(
+        note = "elicitation/create is answered by clients; use `ClientResult::ElicitationCreate`"
+    )]
+    ElicitationCreate(ElicitationCreateResult),
+    /// Listed last: an empty result matches any object, so every other
+    /// variant must get a chance to deserialize first.
+    Empty(EmptyResult),
+}
+
+impl ServerResult {
+    /// Returns which kind of result this is.
+    pub fn kind(&self) -> ServerResultKind {
+        match self {
+            ServerResult::Initialize(_) => ServerResultKind::Initialize,
+            ServerResult::Complete(_) => ServerResultKind::Complete,
+            ServerResult::GetPrompt(_) => ServerResultKind::GetPrompt,
+            ServerResult::ListPrompts(_) => ServerResultKind::ListPrompts,
+            ServerResult::ListResources(_) => ServerResultKind::ListResources,
+            ServerResult::ListResourceTemplates(_) => ServerResultKind::ListResourceTemplates,
+            ServerResult::ReadResource(_) => ServerResultKind::ReadResource,
+            ServerResult::CallTool(_) => ServerResultKind::CallTool,
+            ServerResult::ListTools(_) => ServerResultKind::ListTools,
+            #[allow(deprecated)]
+            ServerResult::ElicitationCreate(_) => ServerResultKind::ElicitationCreate,
+            ServerResult::Empty(_) => ServerResultKind::Empty,
+        }
+    }
+
+    /// Returns the method(s) whose result has the same shape as this one.
+    ///
+    /// Useful when analyzing captured traffic where the originating request
+    /// is not available. An empty result is ambiguous and yields every method
+    /// that answers with one.
+    pub fn likely_method(&self) -> &'static [&'static str] {
+        self.kind().methods()
+    }
+}
+
+/// The kind of a [`ServerResult`], without its payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ServerResultKind {
+    Initialize,
+    Complete,
+    GetPrompt,
+    ListPrompts,
+    ListResources,
+    ListResourceTemplates,
+    ReadResource,
+    CallTool,
+    ListTools,
+    #[deprecated(
+        note = "elicitation/create is answered by clients; use `ClientResultKind::ElicitationCreate`"
+    )]
+    ElicitationCreate,
+    Empty,
+}
+
+impl ServerResultKind {
+    /// Returns the request method(s) answered by a result of this kind.
+    pub fn methods(self) -> &'static [&'static str] {
+        match self {
+            ServerResultKind::Initialize => &["initialize"],
+            ServerResultKind::Complete => &["completion/complete"],
+            ServerResultKind::GetPrompt => &["prompts/get"],
+            ServerResultKind::ListPrompts => &["prompts/list"],
+            ServerResultKind::ListResources => &["resources/list"],
+            ServerResultKind::ListResourceTemplates => &["resources/templates/list"],
+            ServerResultKind::ReadResource => &["resources/read"],
+            ServerResultKind::CallTool => &["tools/call"],
+            ServerResultKind::ListTools => &["tools/list"],
+            #[allow(deprecated)]
+            ServerResultKind::ElicitationCreate => &["elicitation/create"],
+            ServerResultKind::Empty => &[
+                "ping",
+                "logging/setLevel",
+                "resources/subscribe",
+                "resources/unsubscribe",
+            ],
+        }
+    }
+}
+
+/// A union of all possible client results, answering the requests in
+/// [`ServerRequest`].
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ClientResult<C = SamplingContent> {
+    CreateMessage(CreateMessageResult<C>),
+    ListRoots(ListRootsResult),
     ElicitationCreate(ElicitationCreateResult),
+    /// Listed last: an empty result matches any object, so every other
+    /// variant must get a chance to deserialize first.
+    Empty(EmptyResult),
+}
+
+impl ClientResult {
+    /// Returns which kind of result this is.
+    pub fn kind(&self) -> ClientResultKind {
+        match self {
+            ClientResult::CreateMessage(_) => ClientResultKind::CreateMessage,
+            ClientResult::ListRoots(_) => ClientResultKind::ListRoots,
+            ClientResult::ElicitationCreate(_) => ClientResultKind::ElicitationCreate,
+            ClientResult::Empty(_) => ClientResultKind::Empty,
+        }
+    }
+
+    /// Returns the method(s) whose result has the same shape as this one.
+    pub fn likely_method(&self) -> &'static [&'static str] {
+        self.kind().methods()
+    }
+}
+
+/// The kind of a [`ClientResult`], without its payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClientResultKind {
+    CreateMessage,
+    ListRoots,
+    ElicitationCreate,
+    Empty,
+}
+
+impl ClientResultKind {
+    /// Returns the request method(s) answered by a result of this kind.
+    pub fn methods(self) -> &'static [&'static str] {
+        match self {
+            ClientResultKind::CreateMessage => &["sampling/createMessage"],
+            ClientResultKind::ListRoots => &["roots/list"],
+            ClientResultKind::ElicitationCreate => &["elicitation/create"],
+            ClientResultKind::Empty => &["ping"],
+        }
+    }
 }
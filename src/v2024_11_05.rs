@@ -0,0 +1,819 @@
+//! The types of the 2024-11-05 revision.
+//!
+//! Structs that later revisions extended (tools, tool results, prompts and
+//! their arguments, resources, roots, completion references, capabilities,
+//! annotations, and the content, listings, and messages that carry them) are
+//! defined here with this revision's members only, along with the unions that
+//! hold them. Everything else is re-exported from the crate root, so
+//! `use mcp_schema::v2024_11_05::*` brings in a complete set. Each struct
+//! defined here converts to and from the crate root's; converting from the
+//! root drops the members this revision lacks. Content and tool results only
+//! convert up, since the root's may hold blocks this revision cannot carry.
+
+pub use crate::types::{
+    CallToolParams, CancelledNotificationParams, CompleteArgument, CompleteResult, CompletionData,
+    Cursor, EmptyResult, ExperimentalCapabilities, ExperimentalCapabilityError, GetPromptParams,
+    JSONRPCError, JSONRPCMessage, JSONRPCNotification, JSONRPCRequest, JSONRPCResponse,
+    JsonRpcVersion, ListPromptsParams, ListResourceTemplatesParams, ListResourcesParams,
+    ListRootsParams, ListToolsParams, LoggingLevel, LoggingMessageParams, MCPNotificationParams,
+    MCPRequestParams, MCPResultBase, McpStr, ModelHint, ModelPreferences, Paginated, PaginatedItem,
+    PaginatedParams, PaginatedResult, PingParams, ProgressToken, PromptsCapability, RPCErrorDetail,
+    ReadResourceParams, RequestId, RequestMeta, ResourceUpdatedParams, ResourcesCapability, Role,
+    RootsCapability, SamplingCapability, SetLevelParams, SubscribeParams, ToolInputSchema,
+    ToolsCapability, UnsubscribeParams, WithMeta, INTERNAL_ERROR, INVALID_PARAMS, INVALID_REQUEST,
+    JSONRPC_VERSION, MAX_COMPLETION_VALUES, METHOD_NOT_FOUND, PARSE_ERROR,
+};
+
+use crate::types::{deserialize_resource_contents, null_as_default};
+use crate::version::{revision_conversions, with_meta_conversions, ProtocolVersion};
+use mcp_schema_derive::mcp_extras;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// The revision these types belong to.
+pub const VERSION: ProtocolVersion = ProtocolVersion::V2024_11_05;
+
+/// Optional hints for the client about who the annotated data is for and
+/// how important it is.
+#[mcp_extras(extra_only)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Annotations {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audience: Option<Vec<Role>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<f64>,
+}
+
+/// Allows attaching optional annotations and arbitrary extra fields.
+#[mcp_extras(extra_only)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Annotated {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<Annotations>,
+}
+
+/// Represents text content in a prompt or message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextContent {
+    pub text: String,
+
+    #[serde(flatten)]
+    pub annotated: Annotated,
+}
+
+/// Represents image content, stored in base64.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageContent {
+    pub data: String,
+    pub mime_type: McpStr,
+
+    #[serde(flatten)]
+    pub annotated: Annotated,
+}
+
+/// Represents textual resource contents.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
+pub struct TextResourceContents {
+    pub uri: McpStr,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<McpStr>,
+    pub text: String,
+}
+
+/// Represents binary resource contents.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
+pub struct BlobResourceContents {
+    pub uri: McpStr,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<McpStr>,
+    pub blob: String,
+}
+
+/// Contents of a resource, chosen by whether a `text` or a `blob` member is
+/// present.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum ResourceContents {
+    Text(TextResourceContents),
+    Blob(BlobResourceContents),
+}
+
+impl<'de> Deserialize<'de> for ResourceContents {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_resource_contents(deserializer, ResourceContents::Text, ResourceContents::Blob)
+    }
+}
+
+/// The payload of a `resources/read` result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadResourcePayload {
+    pub contents: Vec<ResourceContents>,
+}
+
+/// A result from the `resources/read` method, containing resource contents.
+pub type ReadResourceResult = WithMeta<ReadResourcePayload>;
+
+/// An embedded resource, which can contain a text or blob resource internally.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddedResource {
+    pub resource: ResourceContents,
+
+    #[serde(flatten)]
+    pub annotated: Annotated,
+}
+
+/// A resource object that the server can read.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Resource {
+    pub uri: McpStr,
+    pub name: McpStr,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<McpStr>,
+    /// The size of the raw contents in bytes, before any encoding, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+
+    #[serde(flatten)]
+    pub annotated: Annotated,
+}
+
+/// A resource template, which can be used to generate resource URIs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceTemplate {
+    pub uri_template: McpStr,
+    pub name: McpStr,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<McpStr>,
+
+    #[serde(flatten)]
+    pub annotated: Annotated,
+}
+
+/// Represents the name and version of an MCP implementation.
+#[mcp_extras(extra_only)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Implementation {
+    pub name: McpStr,
+    pub version: String,
+}
+
+/// A prompt object or prompt template.
+#[mcp_extras(extra_only)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Prompt {
+    pub name: McpStr,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<Vec<PromptArgument>>,
+}
+
+/// Arguments accepted by a prompt, potentially required.
+#[mcp_extras(extra_only)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptArgument {
+    pub name: McpStr,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+}
+
+/// Defines a tool that can be invoked by the client.
+#[mcp_extras(extra_only)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tool {
+    pub name: McpStr,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub input_schema: ToolInputSchema,
+}
+
+/// The payload of a `tools/call` result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallToolPayload {
+    pub content: Vec<PromptContent>,
+
+    /// True if the tool call ended in an error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_error: Option<bool>,
+}
+
+/// A result from the `tools/call` method, potentially indicating an error.
+pub type CallToolResult = WithMeta<CallToolPayload>;
+
+/// A set of capabilities the server may support.
+#[mcp_extras(extra_only)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerCapabilities {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub experimental: Option<ExperimentalCapabilities>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logging: Option<HashMap<String, Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompts: Option<PromptsCapability>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resources: Option<ResourcesCapability>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<ToolsCapability>,
+}
+
+/// Describes capabilities a client might support.
+#[mcp_extras(extra_only)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientCapabilities {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub experimental: Option<ExperimentalCapabilities>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roots: Option<RootsCapability>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sampling: Option<SamplingCapability>,
+}
+
+/// Parameters for initializing communication (client -> server).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeParams {
+    pub protocol_version: String,
+    pub capabilities: ClientCapabilities,
+    pub client_info: Implementation,
+}
+
+/// The payload of an `initialize` result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializePayload {
+    pub protocol_version: String,
+    pub capabilities: ServerCapabilities,
+    pub server_info: Implementation,
+
+    /// Optional instructions from the server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+}
+
+/// A result returned by the server after an `initialize` request.
+pub type InitializeResult = WithMeta<InitializePayload>;
+
+/// Parameters for `completion/complete`.
+#[mcp_extras(extra_only)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompleteParams {
+    #[serde(rename = "ref")]
+    pub r#ref: ReferenceType,
+    pub argument: CompleteArgument,
+}
+
+/// A reference to either a resource (template) or a prompt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum ReferenceType {
+    #[serde(rename = "ref/resource")]
+    Resource { uri: String },
+    #[serde(rename = "ref/prompt")]
+    Prompt { name: String },
+}
+
+/// Represents a root directory or file, typically starting with `file://`.
+#[mcp_extras(extra_only)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Root {
+    pub uri: McpStr,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// The payload of a `roots/list` result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListRootsPayload {
+    pub roots: Vec<Root>,
+}
+
+/// A result listing root URIs from the client.
+pub type ListRootsResult = WithMeta<ListRootsPayload>;
+
+/// Parameters for a progress notification, typically referencing a long-running request.
+#[mcp_extras(extra_only)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgressNotificationParams {
+    pub progress_token: ProgressToken,
+    pub progress: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<f64>,
+}
+
+impl PaginatedItem for Resource {
+    const FIELD: &'static str = "resources";
+}
+
+impl PaginatedItem for ResourceTemplate {
+    const FIELD: &'static str = "resourceTemplates";
+}
+
+impl PaginatedItem for Prompt {
+    const FIELD: &'static str = "prompts";
+}
+
+impl PaginatedItem for Tool {
+    const FIELD: &'static str = "tools";
+}
+
+/// The result of a `resources/list` request.
+pub type ListResourcesResult = Paginated<Resource>;
+
+/// The result of a `resources/templates/list` request.
+pub type ListResourceTemplatesResult = Paginated<ResourceTemplate>;
+
+/// The result of a `prompts/list` request.
+pub type ListPromptsResult = Paginated<Prompt>;
+
+/// The result of a `tools/list` request.
+pub type ListToolsResult = Paginated<Tool>;
+
+/// Represents the content of a prompt message: text, image, or embedded resource.
+/// The variant is selected by the `type` member.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum PromptContent {
+    Text(TextContent),
+    Image(ImageContent),
+    Resource(EmbeddedResource),
+}
+
+/// Represents a text or image message in sampling. The variant is selected
+/// by the `type` member.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SamplingContent {
+    Text(TextContent),
+    Image(ImageContent),
+}
+
+pub type PromptMessage = crate::types::PromptMessage<PromptContent>;
+pub type GetPromptResult = crate::types::GetPromptResult<PromptContent>;
+pub type SamplingMessage = crate::types::SamplingMessage<SamplingContent>;
+pub type CreateMessageParams = crate::types::CreateMessageParams<SamplingContent>;
+pub type CreateMessageResult = crate::types::CreateMessageResult<SamplingContent>;
+
+/// A union of all possible client requests. The `method` field identifies the variant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
+pub enum ClientRequest {
+    #[serde(rename = "ping")]
+    Ping {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        #[serde(default, deserialize_with = "null_as_default")]
+        params: PingParams,
+    },
+    #[serde(rename = "initialize")]
+    Initialize {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        params: InitializeParams,
+    },
+    #[serde(rename = "completion/complete")]
+    Complete {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        params: CompleteParams,
+    },
+    #[serde(rename = "logging/setLevel")]
+    SetLevel {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        params: SetLevelParams,
+    },
+    #[serde(rename = "prompts/get")]
+    GetPrompt {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        params: GetPromptParams,
+    },
+    #[serde(rename = "prompts/list")]
+    ListPrompts {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        #[serde(default, deserialize_with = "null_as_default")]
+        params: ListPromptsParams,
+    },
+    #[serde(rename = "resources/list")]
+    ListResources {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        #[serde(default, deserialize_with = "null_as_default")]
+        params: ListResourcesParams,
+    },
+    #[serde(rename = "resources/templates/list")]
+    ListResourceTemplates {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        #[serde(default, deserialize_with = "null_as_default")]
+        params: ListResourceTemplatesParams,
+    },
+    #[serde(rename = "resources/read")]
+    ReadResource {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        params: ReadResourceParams,
+    },
+    #[serde(rename = "resources/subscribe")]
+    Subscribe {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        params: SubscribeParams,
+    },
+    #[serde(rename = "resources/unsubscribe")]
+    Unsubscribe {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        params: UnsubscribeParams,
+    },
+    #[serde(rename = "tools/call")]
+    CallTool {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        params: CallToolParams,
+    },
+    #[serde(rename = "tools/list")]
+    ListTools {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        #[serde(default, deserialize_with = "null_as_default")]
+        params: ListToolsParams,
+    },
+}
+
+/// A union of all possible client notifications.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
+pub enum ClientNotification {
+    #[serde(rename = "notifications/cancelled")]
+    Cancelled {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        params: CancelledNotificationParams,
+    },
+    #[serde(rename = "notifications/progress")]
+    Progress {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        params: ProgressNotificationParams,
+    },
+    #[serde(rename = "notifications/initialized")]
+    Initialized {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        #[serde(default, deserialize_with = "null_as_default")]
+        params: MCPNotificationParams,
+    },
+    #[serde(rename = "notifications/roots/list_changed")]
+    RootsListChanged {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        #[serde(default, deserialize_with = "null_as_default")]
+        params: MCPNotificationParams,
+    },
+}
+
+/// A union of possible server requests.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
+pub enum ServerRequest {
+    #[serde(rename = "ping")]
+    Ping {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        #[serde(default, deserialize_with = "null_as_default")]
+        params: PingParams,
+    },
+    #[serde(rename = "sampling/createMessage")]
+    CreateMessage {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        params: CreateMessageParams,
+    },
+    #[serde(rename = "roots/list")]
+    ListRoots {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        #[serde(default, deserialize_with = "null_as_default")]
+        params: ListRootsParams,
+    },
+}
+
+/// A union of possible server notifications.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
+pub enum ServerNotification {
+    #[serde(rename = "notifications/cancelled")]
+    Cancelled {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        params: CancelledNotificationParams,
+    },
+    #[serde(rename = "notifications/progress")]
+    Progress {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        params: ProgressNotificationParams,
+    },
+    #[serde(rename = "notifications/message")]
+    LoggingMessage {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        params: LoggingMessageParams,
+    },
+    #[serde(rename = "notifications/resources/updated")]
+    ResourceUpdated {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        params: ResourceUpdatedParams,
+    },
+    #[serde(rename = "notifications/resources/list_changed")]
+    ResourceListChanged {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        #[serde(default, deserialize_with = "null_as_default")]
+        params: MCPNotificationParams,
+    },
+    #[serde(rename = "notifications/tools/list_changed")]
+    ToolListChanged {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        #[serde(default, deserialize_with = "null_as_default")]
+        params: MCPNotificationParams,
+    },
+    #[serde(rename = "notifications/prompts/list_changed")]
+    PromptListChanged {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        #[serde(default, deserialize_with = "null_as_default")]
+        params: MCPNotificationParams,
+    },
+}
+
+/// A union of the server results defined by this revision.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ServerResult {
+    Initialize(InitializeResult),
+    Complete(CompleteResult),
+    GetPrompt(GetPromptResult),
+    ListPrompts(ListPromptsResult),
+    ListResources(ListResourcesResult),
+    ListResourceTemplates(ListResourceTemplatesResult),
+    ReadResource(ReadResourceResult),
+    CallTool(CallToolResult),
+    ListTools(ListToolsResult),
+    /// Listed last: an empty result matches any object, so every other
+    /// variant must get a chance to deserialize first.
+    Empty(EmptyResult),
+}
+
+/// A union of the client results defined by this revision, answering the
+/// requests in [`ServerRequest`].
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ClientResult {
+    CreateMessage(CreateMessageResult),
+    ListRoots(ListRootsResult),
+    /// Listed last: an empty result matches any object, so every other
+    /// variant must get a chance to deserialize first.
+    Empty(EmptyResult),
+}
+
+revision_conversions! {
+    TextResourceContents { uri, mime_type, text } + { meta };
+    BlobResourceContents { uri, mime_type, blob } + { meta };
+    Annotations { audience, priority, extra } + { last_modified };
+    TextContent { text, annotated } + { meta };
+    ImageContent { data, mime_type, annotated } + { meta };
+    EmbeddedResource { resource, annotated } + { meta };
+    Resource { uri, name, description, mime_type, size, annotated } + { meta, title };
+    ResourceTemplate { uri_template, name, description, mime_type, annotated } + { meta, title };
+    Implementation { name, version, extra } + { title };
+    PromptArgument { name, description, required, extra } + { title };
+    Root { uri, name, extra } + { meta };
+    Tool { name, description, input_schema, extra } + { meta, title, output_schema, annotations };
+    ServerCapabilities { experimental, logging, prompts, resources, tools, extra } + { completions };
+    ClientCapabilities { experimental, roots, sampling, extra } + { elicitation };
+    InitializeParams { protocol_version, capabilities, client_info };
+    InitializePayload { protocol_version, capabilities, server_info, instructions };
+    CompleteParams { r#ref, argument, extra } + { context };
+    ProgressNotificationParams { progress_token, progress, total, extra } + { message };
+}
+
+impl From<Annotated> for crate::types::Annotated {
+    fn from(value: Annotated) -> Self {
+        crate::types::Annotated {
+            annotations: value.annotations.map(Into::into),
+            extra: value.extra,
+        }
+    }
+}
+
+impl From<crate::types::Annotated> for Annotated {
+    fn from(value: crate::types::Annotated) -> Self {
+        Annotated {
+            annotations: value.annotations.map(Into::into),
+            extra: value.extra,
+        }
+    }
+}
+
+impl From<Prompt> for crate::types::Prompt {
+    fn from(value: Prompt) -> Self {
+        crate::types::Prompt {
+            meta: None,
+            name: value.name,
+            title: None,
+            description: value.description,
+            arguments: value
+                .arguments
+                .map(|arguments| arguments.into_iter().map(Into::into).collect()),
+            extra: value.extra,
+        }
+    }
+}
+
+impl From<crate::types::Prompt> for Prompt {
+    fn from(value: crate::types::Prompt) -> Self {
+        Prompt {
+            name: value.name,
+            description: value.description,
+            arguments: value
+                .arguments
+                .map(|arguments| arguments.into_iter().map(Into::into).collect()),
+            extra: value.extra,
+        }
+    }
+}
+
+impl From<ReferenceType> for crate::types::ReferenceType {
+    fn from(value: ReferenceType) -> Self {
+        match value {
+            ReferenceType::Resource { uri } => crate::types::ReferenceType::Resource { uri },
+            ReferenceType::Prompt { name } => {
+                crate::types::ReferenceType::Prompt { name, title: None }
+            }
+        }
+    }
+}
+
+impl From<crate::types::ReferenceType> for ReferenceType {
+    fn from(value: crate::types::ReferenceType) -> Self {
+        match value {
+            crate::types::ReferenceType::Resource { uri } => ReferenceType::Resource { uri },
+            crate::types::ReferenceType::Prompt { name, .. } => ReferenceType::Prompt { name },
+        }
+    }
+}
+
+impl From<ListRootsPayload> for crate::types::ListRootsPayload {
+    fn from(value: ListRootsPayload) -> Self {
+        crate::types::ListRootsPayload {
+            roots: value.roots.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<crate::types::ListRootsPayload> for ListRootsPayload {
+    fn from(value: crate::types::ListRootsPayload) -> Self {
+        ListRootsPayload {
+            roots: value.roots.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<ResourceContents> for crate::types::ResourceContents {
+    fn from(value: ResourceContents) -> Self {
+        match value {
+            ResourceContents::Text(text) => crate::types::ResourceContents::Text(text.into()),
+            ResourceContents::Blob(blob) => crate::types::ResourceContents::Blob(blob.into()),
+        }
+    }
+}
+
+impl From<crate::types::ResourceContents> for ResourceContents {
+    fn from(value: crate::types::ResourceContents) -> Self {
+        match value {
+            crate::types::ResourceContents::Text(text) => ResourceContents::Text(text.into()),
+            crate::types::ResourceContents::Blob(blob) => ResourceContents::Blob(blob.into()),
+        }
+    }
+}
+
+impl From<ReadResourcePayload> for crate::types::ReadResourcePayload {
+    fn from(value: ReadResourcePayload) -> Self {
+        crate::types::ReadResourcePayload {
+            contents: value.contents.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<crate::types::ReadResourcePayload> for ReadResourcePayload {
+    fn from(value: crate::types::ReadResourcePayload) -> Self {
+        ReadResourcePayload {
+            contents: value.contents.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+with_meta_conversions!(InitializePayload, ListRootsPayload, ReadResourcePayload);
+
+/// Only goes up: a result of the crate root may hold content blocks this
+/// revision cannot carry.
+impl From<CallToolPayload> for crate::types::CallToolPayload {
+    fn from(value: CallToolPayload) -> Self {
+        crate::types::CallToolPayload {
+            content: value.content.into_iter().map(Into::into).collect(),
+            structured_content: None,
+            is_error: value.is_error,
+        }
+    }
+}
+
+impl From<CallToolResult> for crate::types::CallToolResult {
+    fn from(value: CallToolResult) -> Self {
+        value.map(Into::into)
+    }
+}
+
+/// Every content block of this revision is also one of the crate root's.
+impl From<PromptContent> for crate::types::PromptContent {
+    fn from(content: PromptContent) -> Self {
+        match content {
+            PromptContent::Text(content) => crate::types::PromptContent::Text(content.into()),
+            PromptContent::Image(content) => crate::types::PromptContent::Image(content.into()),
+            PromptContent::Resource(content) => {
+                crate::types::PromptContent::Resource(content.into())
+            }
+        }
+    }
+}
+
+impl From<SamplingContent> for crate::types::SamplingContent {
+    fn from(content: SamplingContent) -> Self {
+        match content {
+            SamplingContent::Text(content) => crate::types::SamplingContent::Text(content.into()),
+            SamplingContent::Image(content) => crate::types::SamplingContent::Image(content.into()),
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! The types of the 2025-06-18 revision, which adds elicitation and
+//! resource links.
+//!
+//! The unions whose variants differ between revisions (content, requests,
+//! and results) are defined here; the structs every revision shares are
+//! re-exported from the crate root, so `use mcp_schema::v2025_06_18::*`
+//! brings in a complete set.
+
+pub use crate::types::{
+    Annotated, Annotations, AudioContent, BlobResourceContents, CallToolParams,
+    CancelledNotificationParams, ClientCapabilities, ClientNotification, CompleteArgument,
+    CompleteContext, CompleteParams, CompleteResult, CompletionData, CreateMessageParams,
+    CreateMessageResult, Cursor, ElicitationAction, ElicitationCreateParams,
+    ElicitationCreateResult, EmbeddedResource, EmptyResult, ExperimentalCapabilities,
+    ExperimentalCapabilityError, GetPromptParams, ImageContent, Implementation, InitializeParams,
+    InitializePayload, InitializeResult, JSONRPCError, JSONRPCMessage, JSONRPCNotification,
+    JSONRPCRequest, JSONRPCResponse, JsonRpcVersion, ListPromptsParams, ListPromptsResult,
+    ListResourceTemplatesParams, ListResourceTemplatesResult, ListResourcesParams,
+    ListResourcesResult, ListRootsParams, ListRootsResult, ListToolsParams, ListToolsResult,
+    LoggingLevel, LoggingMessageParams, MCPNotificationParams, MCPRequestParams, MCPResultBase,
+    McpStr, ModelHint, ModelPreferences, Paginated, PaginatedItem, PaginatedParams,
+    PaginatedResult, PingParams, ProgressNotificationParams, ProgressToken, Prompt, PromptArgument,
+    PromptsCapability, RPCErrorDetail, ReadResourceParams, ReadResourceResult, ReferenceType,
+    RequestId, RequestMeta, Resource, ResourceContents, ResourceLink, ResourceTemplate,
+    ResourceUpdatedParams, ResourcesCapability, Role, Root, RootsCapability, SamplingCapability,
+    SamplingContent, SamplingMessage, ServerCapabilities, ServerNotification, SetLevelParams,
+    SubscribeParams, TextContent, TextResourceContents, Tool, ToolAnnotations, ToolInputSchema,
+    ToolOutputSchema, ToolsCapability, UnsubscribeParams, WithMeta, INTERNAL_ERROR, INVALID_PARAMS,
+    INVALID_REQUEST, JSONRPC_VERSION, MAX_COMPLETION_VALUES, METHOD_NOT_FOUND, PARSE_ERROR,
+};
+
+use crate::types::null_as_default;
+use crate::version::ProtocolVersion;
+use serde::{Deserialize, Serialize};
+
+/// The revision these types belong to.
+pub const VERSION: ProtocolVersion = ProtocolVersion::V2025_06_18;
+
+/// Represents the content of a prompt message or tool result: text, image,
+/// audio, a resource link, or an embedded resource. The variant is selected
+/// by the `type` member.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum PromptContent {
+    Text(TextContent),
+    Image(ImageContent),
+    Audio(AudioContent),
+    #[serde(rename = "resource_link")]
+    ResourceLink(ResourceLink),
+    Resource(EmbeddedResource),
+}
+
+pub type PromptMessage = crate::types::PromptMessage<PromptContent>;
+pub type GetPromptResult = crate::types::GetPromptResult<PromptContent>;
+pub type CallToolResult = crate::types::CallToolResult<PromptContent>;
+
+/// A union of the client requests defined by this revision. The `method`
+/// field identifies the variant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-fields", serde(deny_unknown_fields))]
+pub enum ClientRequest {
+    #[serde(rename = "ping")]
+    Ping {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        #[serde(default, deserialize_with = "null_as_default")]
+        params: PingParams,
+    },
+    #[serde(rename = "initialize")]
+    Initialize {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        params: InitializeParams,
+    },
+    #[serde(rename = "completion/complete")]
+    Complete {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        params: CompleteParams,
+    },
+    #[serde(rename = "logging/setLevel")]
+    SetLevel {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        params: SetLevelParams,
+    },
+    #[serde(rename = "prompts/get")]
+    GetPrompt {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        params: GetPromptParams,
+    },
+    #[serde(rename = "prompts/list")]
+    ListPrompts {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        #[serde(default, deserialize_with = "null_as_default")]
+        params: ListPromptsParams,
+    },
+    #[serde(rename = "resources/list")]
+    ListResources {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        #[serde(default, deserialize_with = "null_as_default")]
+        params: ListResourcesParams,
+    },
+    #[serde(rename = "resources/templates/list")]
+    ListResourceTemplates {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        #[serde(default, deserialize_with = "null_as_default")]
+        params: ListResourceTemplatesParams,
+    },
+    #[serde(rename = "resources/read")]
+    ReadResource {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        params: ReadResourceParams,
+    },
+    #[serde(rename = "resources/subscribe")]
+    Subscribe {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        params: SubscribeParams,
+    },
+    #[serde(rename = "resources/unsubscribe")]
+    Unsubscribe {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        params: UnsubscribeParams,
+    },
+    #[serde(rename = "tools/call")]
+    CallTool {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        params: CallToolParams,
+    },
+    #[serde(rename = "tools/list")]
+    ListTools {
+        #[serde(rename = "jsonrpc")]
+        json_rpc: JsonRpcVersion,
+        id: RequestId,
+        #[serde(default, deserialize_with = "null_as_default")]
+        params: ListToolsParams,
+    },
+}
+
+pub type ServerRequest = crate::types::ServerRequest<SamplingContent>;
+
+/// A union of the server results defined by this revision.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ServerResult {
+    Initialize(InitializeResult),
+    Complete(CompleteResult),
+    GetPrompt(GetPromptResult),
+    ListPrompts(ListPromptsResult),
+    ListResources(ListResourcesResult),
+    ListResourceTemplates(ListResourceTemplatesResult),
+    ReadResource(ReadResourceResult),
+    CallTool(CallToolResult),
+    ListTools(ListToolsResult),
+    /// Listed last: an empty result matches any object, so every other
+    /// variant must get a chance to deserialize first.
+    Empty(EmptyResult),
+}
+
+pub type ClientResult = crate::types::ClientResult<SamplingContent>;
+
+/// Every content block of this revision is also one of the crate root's.
+impl From<PromptContent> for crate::types::PromptContent {
+    fn from(content: PromptContent) -> Self {
+        match content {
+            PromptContent::Text(content) => crate::types::PromptContent::Text(content),
+            PromptContent::Image(content) => crate::types::PromptContent::Image(content),
+            PromptContent::Audio(content) => crate::types::PromptContent::Audio(content),
+            PromptContent::ResourceLink(content) => {
+                crate::types::PromptContent::ResourceLink(content)
+            }
+            PromptContent::Resource(content) => crate::types::PromptContent::Resource(content),
+        }
+    }
+}
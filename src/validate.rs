@@ -0,0 +1,428 @@
+//! Structural validation of raw messages, reporting every violation found
+//! rather than failing on the first one, for linters and conformance tools.
+
+use crate::method::Method;
+use crate::middleware::EnforceCapabilities;
+use crate::types::*;
+use crate::version::ProtocolVersion;
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// Which peer sent a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageDirection {
+    ClientToServer,
+    ServerToClient,
+}
+
+impl MessageDirection {
+    pub fn reverse(self) -> Self {
+        match self {
+            MessageDirection::ClientToServer => MessageDirection::ServerToClient,
+            MessageDirection::ServerToClient => MessageDirection::ClientToServer,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The peer may handle the message differently than intended.
+    Warning,
+    /// The message breaks the spec.
+    Error,
+}
+
+/// The rule a [`Violation`] breaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rule {
+    /// The message is not a JSON-RPC object.
+    InvalidMessage,
+    /// `jsonrpc` is missing or not `"2.0"`.
+    JsonRpcVersion,
+    /// The method is not defined for this direction or protocol version.
+    UnknownMethod,
+    /// A member the spec requires is missing or has the wrong type.
+    MissingField,
+    /// The method relies on a capability the receiver did not advertise.
+    CapabilityMismatch,
+}
+
+impl Rule {
+    /// A stable identifier, suitable for suppression lists.
+    pub fn id(self) -> &'static str {
+        match self {
+            Rule::InvalidMessage => "invalid-message",
+            Rule::JsonRpcVersion => "jsonrpc-version",
+            Rule::UnknownMethod => "unknown-method",
+            Rule::MissingField => "missing-field",
+            Rule::CapabilityMismatch => "capability-mismatch",
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+/// One problem found in a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// JSON pointer (RFC 6901) to the offending member; empty for the whole
+    /// message.
+    pub pointer: String,
+    pub severity: Severity,
+    pub rule: Rule,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{} [{}]", severity, self.rule)?;
+        if !self.pointer.is_empty() {
+            write!(f, " at {}", self.pointer)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Whether `method` is a request (or, if not `is_request`, a notification)
+/// sent in `direction`.
+fn is_known_method(direction: MessageDirection, is_request: bool, method: &str) -> bool {
+    let Ok(method) = method.parse::<Method>() else {
+        return false;
+    };
+    match (direction, is_request) {
+        (MessageDirection::ClientToServer, true) => method.is_client_request(),
+        (MessageDirection::ClientToServer, false) => method.is_client_notification(),
+        (MessageDirection::ServerToClient, true) => method.is_server_request(),
+        (MessageDirection::ServerToClient, false) => method.is_server_notification(),
+    }
+}
+
+/// The first revision that defines `method`.
+fn introduced_in(method: &str) -> ProtocolVersion {
+    match method {
+        "elicitation/create" => ProtocolVersion::V2025_06_18,
+        _ => ProtocolVersion::V2024_11_05,
+    }
+}
+
+/// Members of `params` the spec requires for `method`.
+fn required_params(method: &str) -> &'static [&'static str] {
+    match method {
+        "initialize" => &["protocolVersion", "capabilities", "clientInfo"],
+        "completion/complete" => &["ref", "argument"],
+        "logging/setLevel" => &["level"],
+        "prompts/get" | "tools/call" => &["name"],
+        "resources/read" | "resources/subscribe" | "resources/unsubscribe" => &["uri"],
+        "elicitation/create" => &["message", "requestedSchema"],
+        "sampling/createMessage" => &["messages", "maxTokens"],
+        "notifications/cancelled" => &["requestId"],
+        "notifications/progress" => &["progressToken", "progress"],
+        "notifications/message" => &["level", "data"],
+        "notifications/resources/updated" => &["uri"],
+        _ => &[],
+    }
+}
+
+/// Escapes a member name for use in a JSON pointer.
+fn escape(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
+
+/// Validates messages against the spec, optionally also against the
+/// capabilities each side advertised during initialization.
+#[derive(Debug, Clone)]
+pub struct Validator {
+    pub version: ProtocolVersion,
+    pub client_capabilities: Option<ClientCapabilities>,
+    pub server_capabilities: Option<ServerCapabilities>,
+}
+
+impl Validator {
+    pub fn new(version: ProtocolVersion) -> Self {
+        Validator {
+            version,
+            client_capabilities: None,
+            server_capabilities: None,
+        }
+    }
+
+    /// Checks server-to-client traffic against what the client advertised.
+    pub fn client_capabilities(mut self, capabilities: ClientCapabilities) -> Self {
+        self.client_capabilities = Some(capabilities);
+        self
+    }
+
+    /// Checks client-to-server traffic against what the server advertised.
+    pub fn server_capabilities(mut self, capabilities: ServerCapabilities) -> Self {
+        self.server_capabilities = Some(capabilities);
+        self
+    }
+
+    /// Validates a single message or a batch, returning every violation
+    /// found; an empty list means the message is valid.
+    pub fn validate(&self, message: &Value, direction: MessageDirection) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        match message {
+            Value::Array(batch) if batch.is_empty() => {
+                push(&mut violations, "", Rule::InvalidMessage, "empty batch");
+            }
+            // Batching was added in 2025-03-26 and removed again in 2025-06-18.
+            Value::Array(_) if self.version != ProtocolVersion::V2025_03_26 => {
+                push(
+                    &mut violations,
+                    "",
+                    Rule::InvalidMessage,
+                    format!("batches are not supported in {}", self.version),
+                );
+            }
+            Value::Array(batch) => {
+                for (i, entry) in batch.iter().enumerate() {
+                    self.check(entry, direction, &format!("/{}", i), &mut violations);
+                }
+            }
+            _ => self.check(message, direction, "", &mut violations),
+        }
+        violations
+    }
+
+    fn check(
+        &self,
+        message: &Value,
+        direction: MessageDirection,
+        base: &str,
+        violations: &mut Vec<Violation>,
+    ) {
+        let Some(object) = message.as_object() else {
+            push(
+                violations,
+                base,
+                Rule::InvalidMessage,
+                "message is not an object",
+            );
+            return;
+        };
+
+        match object.get("jsonrpc") {
+            Some(Value::String(version)) if version == JSONRPC_VERSION => {}
+            Some(other) => push(
+                violations,
+                &format!("{}/jsonrpc", base),
+                Rule::JsonRpcVersion,
+                format!("\"jsonrpc\" must be \"2.0\", found {}", other),
+            ),
+            None => push(
+                violations,
+                &format!("{}/jsonrpc", base),
+                Rule::JsonRpcVersion,
+                "missing \"jsonrpc\" member",
+            ),
+        }
+
+        match object.get("method") {
+            Some(Value::String(method)) => {
+                self.check_call(object, method, direction, base, violations)
+            }
+            Some(_) => push(
+                violations,
+                &format!("{}/method", base),
+                Rule::MissingField,
+                "\"method\" must be a string",
+            ),
+            None if object.contains_key("result") || object.contains_key("error") => {
+                check_response(object, base, violations)
+            }
+            None => push(
+                violations,
+                &format!("{}/method", base),
+                Rule::MissingField,
+                "message has neither \"method\" nor \"result\"/\"error\"",
+            ),
+        }
+    }
+
+    fn check_call(
+        &self,
+        object: &Map<String, Value>,
+        method: &str,
+        direction: MessageDirection,
+        base: &str,
+        violations: &mut Vec<Violation>,
+    ) {
+        let is_request = object.contains_key("id");
+        if is_request {
+            check_id(object, base, violations);
+        }
+
+        let pointer = format!("{}/method", base);
+        if !is_known_method(direction, is_request, method) {
+            let message = if is_known_method(direction.reverse(), is_request, method) {
+                format!("{:?} is not sent in this direction", method)
+            } else {
+                format!("unknown method {:?}", method)
+            };
+            // Receivers ignore notifications they do not understand.
+            let severity = if is_request {
+                Severity::Error
+            } else {
+                Severity::Warning
+            };
+            violations.push(Violation {
+                pointer,
+                severity,
+                rule: Rule::UnknownMethod,
+                message,
+            });
+            return;
+        }
+        let since = introduced_in(method);
+        if since > self.version {
+            push(
+                violations,
+                &pointer,
+                Rule::UnknownMethod,
+                format!(
+                    "{:?} requires protocol version {}, not {}",
+                    method, since, self.version
+                ),
+            );
+        }
+        if !self.capable(method, direction) {
+            push(
+                violations,
+                &pointer,
+                Rule::CapabilityMismatch,
+                format!(
+                    "{:?} was not enabled by the receiver's capabilities",
+                    method
+                ),
+            );
+        }
+
+        let required = required_params(method);
+        if required.is_empty() {
+            return;
+        }
+        match object.get("params") {
+            Some(Value::Object(params)) => {
+                for name in required.iter().filter(|name| !params.contains_key(**name)) {
+                    push(
+                        violations,
+                        &format!("{}/params/{}", base, escape(name)),
+                        Rule::MissingField,
+                        format!("{:?} requires params.{}", method, name),
+                    );
+                }
+            }
+            _ => push(
+                violations,
+                &format!("{}/params", base),
+                Rule::MissingField,
+                format!("{:?} requires a params object", method),
+            ),
+        }
+    }
+
+    /// Whether the receiver's capabilities, when known, allow `method`.
+    fn capable(&self, method: &str, direction: MessageDirection) -> bool {
+        match direction {
+            MessageDirection::ClientToServer => match &self.server_capabilities {
+                Some(capabilities) => EnforceCapabilities::new(capabilities.clone())
+                    .with_version(self.version)
+                    .allows(method),
+                None => true,
+            },
+            MessageDirection::ServerToClient => match &self.client_capabilities {
+                Some(capabilities) => match method {
+                    "sampling/createMessage" => capabilities.sampling.is_some(),
+                    "roots/list" => capabilities.roots.is_some(),
+                    "elicitation/create" => capabilities.elicitation.is_some(),
+                    _ => true,
+                },
+                None => true,
+            },
+        }
+    }
+}
+
+fn push(violations: &mut Vec<Violation>, pointer: &str, rule: Rule, message: impl Into<String>) {
+    violations.push(Violation {
+        pointer: pointer.to_string(),
+        severity: Severity::Error,
+        rule,
+        message: message.into(),
+    });
+}
+
+fn check_id(object: &Map<String, Value>, base: &str, violations: &mut Vec<Violation>) {
+    match object.get("id") {
+        Some(Value::String(_)) | Some(Value::Number(_)) => {}
+        _ => push(
+            violations,
+            &format!("{}/id", base),
+            Rule::MissingField,
+            "\"id\" must be a string or a number",
+        ),
+    }
+}
+
+fn check_response(object: &Map<String, Value>, base: &str, violations: &mut Vec<Violation>) {
+    // An error answering a request whose id could not be read carries
+    // `"id": null`.
+    let null_id_error = object.get("id") == Some(&Value::Null)
+        && object.contains_key("error")
+        && !object.contains_key("result");
+    if !null_id_error {
+        check_id(object, base, violations);
+    }
+    match (object.get("result"), object.get("error")) {
+        (Some(_), Some(_)) => push(
+            violations,
+            base,
+            Rule::InvalidMessage,
+            "response has both \"result\" and \"error\"",
+        ),
+        (None, Some(Value::Object(error))) => {
+            if !error.get("code").is_some_and(Value::is_i64) {
+                push(
+                    violations,
+                    &format!("{}/error/code", base),
+                    Rule::MissingField,
+                    "error requires an integer \"code\"",
+                );
+            }
+            if !error.get("message").is_some_and(Value::is_string) {
+                push(
+                    violations,
+                    &format!("{}/error/message", base),
+                    Rule::MissingField,
+                    "error requires a string \"message\"",
+                );
+            }
+        }
+        (None, Some(_)) => push(
+            violations,
+            &format!("{}/error", base),
+            Rule::MissingField,
+            "\"error\" must be an object",
+        ),
+        _ => {}
+    }
+}
+
+/// Validates a message without knowledge of the negotiated capabilities.
+/// See [`Validator`] to also check capability mismatches.
+pub fn validate_message(
+    message: &Value,
+    direction: MessageDirection,
+    version: ProtocolVersion,
+) -> Vec<Violation> {
+    Validator::new(version).validate(message, direction)
+}
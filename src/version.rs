@@ -0,0 +1,222 @@
+//! Revisions of the MCP specification.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+/// A published revision of the MCP specification, ordered by date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProtocolVersion {
+    V2024_11_05,
+    V2025_03_26,
+    V2025_06_18,
+}
+
+impl ProtocolVersion {
+    /// Every known revision, oldest first.
+    pub const ALL: [ProtocolVersion; 3] = [
+        ProtocolVersion::V2024_11_05,
+        ProtocolVersion::V2025_03_26,
+        ProtocolVersion::V2025_06_18,
+    ];
+
+    /// The revision string sent in `initialize`, e.g. `"2024-11-05"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProtocolVersion::V2024_11_05 => "2024-11-05",
+            ProtocolVersion::V2025_03_26 => "2025-03-26",
+            ProtocolVersion::V2025_06_18 => "2025-06-18",
+        }
+    }
+}
+
+/// A revision string that names no known [`ProtocolVersion`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownProtocolVersion(pub String);
+
+impl fmt::Display for UnknownProtocolVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown protocol version {:?}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownProtocolVersion {}
+
+impl FromStr for ProtocolVersion {
+    type Err = UnknownProtocolVersion;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ProtocolVersion::ALL
+            .into_iter()
+            .find(|version| version.as_str() == s)
+            .ok_or_else(|| UnknownProtocolVersion(s.to_string()))
+    }
+}
+
+impl fmt::Display for ProtocolVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for ProtocolVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ProtocolVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// The earliest revision in which the features used by one message exist.
+fn feature_floor(message: &Value) -> Option<ProtocolVersion> {
+    let mut floor = None;
+    let mut raise = |version: ProtocolVersion| {
+        floor = Some(floor.map_or(version, |floor: ProtocolVersion| floor.max(version)));
+    };
+    match message {
+        Value::Array(batch) => {
+            // Batching was added in 2025-03-26 and removed again in 2025-06-18.
+            raise(ProtocolVersion::V2025_03_26);
+            batch.iter().filter_map(feature_floor).for_each(&mut raise);
+        }
+        Value::Object(object) => {
+            if object.get("method").and_then(Value::as_str) == Some("elicitation/create") {
+                raise(ProtocolVersion::V2025_06_18);
+            }
+            if let Some(result) = object.get("result") {
+                if result.get("structuredContent").is_some() {
+                    raise(ProtocolVersion::V2025_06_18);
+                }
+                let content = result.get("content").and_then(Value::as_array);
+                for block in content.into_iter().flatten() {
+                    match block.get("type").and_then(Value::as_str) {
+                        Some("resource_link") => raise(ProtocolVersion::V2025_06_18),
+                        Some("audio") => raise(ProtocolVersion::V2025_03_26),
+                        _ => {}
+                    }
+                }
+                let tools = result.get("tools").and_then(Value::as_array);
+                for tool in tools.into_iter().flatten() {
+                    if tool.get("outputSchema").is_some() {
+                        raise(ProtocolVersion::V2025_06_18);
+                    } else if tool.get("annotations").is_some() {
+                        raise(ProtocolVersion::V2025_03_26);
+                    }
+                }
+                let capabilities = result.get("capabilities");
+                if capabilities.is_some_and(|c| c.get("completions").is_some()) {
+                    raise(ProtocolVersion::V2025_03_26);
+                }
+            }
+        }
+        _ => {}
+    }
+    floor
+}
+
+fn initialize_version(message: &Value, key: &str) -> Option<ProtocolVersion> {
+    message
+        .get(key)?
+        .get("protocolVersion")?
+        .as_str()?
+        .parse()
+        .ok()
+}
+
+/// Infers the protocol revision of captured traffic.
+///
+/// The version in an `initialize` result is authoritative. Without one, the
+/// version the client proposed in its `initialize` request is used, raised
+/// to the earliest revision that has every feature seen in the traffic
+/// (structured tool output, audio content, batching, ...). Returns `None`
+/// when the messages give no evidence at all.
+pub fn sniff_protocol_version<'a>(
+    messages: impl Iterator<Item = &'a Value>,
+) -> Option<ProtocolVersion> {
+    let mut proposed = None;
+    let mut floor: Option<ProtocolVersion> = None;
+    for message in messages {
+        let entries = match message {
+            Value::Array(batch) => batch.iter().collect(),
+            message => vec![message],
+        };
+        for entry in entries {
+            if entry
+                .get("result")
+                .is_some_and(|r| r.get("serverInfo").is_some())
+            {
+                if let Some(version) = initialize_version(entry, "result") {
+                    return Some(version);
+                }
+            }
+            if entry.get("method").and_then(Value::as_str) == Some("initialize") {
+                proposed = proposed.or(initialize_version(entry, "params"));
+            }
+        }
+        if let Some(version) = feature_floor(message) {
+            floor = Some(floor.map_or(version, |floor| floor.max(version)));
+        }
+    }
+    match (proposed, floor) {
+        (Some(proposed), Some(floor)) => Some(proposed.max(floor)),
+        (proposed, floor) => proposed.or(floor),
+    }
+}
+
+/// Converts between a revision module's struct and the crate root's struct
+/// of the same name, field by field. Members listed after `+` exist only in
+/// the crate root: they are left empty going up and dropped going down.
+macro_rules! revision_conversions {
+    ($($name:ident { $($field:ident),* } $(+ { $($added:ident),* })?;)*) => {
+        $(
+            #[allow(clippy::useless_conversion)]
+            impl From<$name> for crate::types::$name {
+                fn from(value: $name) -> Self {
+                    crate::types::$name {
+                        $($field: value.$field.into(),)*
+                        $($($added: Default::default(),)*)?
+                    }
+                }
+            }
+
+            #[allow(clippy::useless_conversion)]
+            impl From<crate::types::$name> for $name {
+                fn from(value: crate::types::$name) -> Self {
+                    $name {
+                        $($field: value.$field.into(),)*
+                    }
+                }
+            }
+        )*
+    };
+}
+pub(crate) use revision_conversions;
+
+/// Converts between [`WithMeta`](crate::types::WithMeta) results of a
+/// revision module's payload and the crate root's payload of the same name,
+/// keeping `_meta` and the passthrough fields. The payloads must convert with
+/// [`revision_conversions!`].
+macro_rules! with_meta_conversions {
+    ($($name:ident),* $(,)?) => {
+        $(
+            impl From<crate::types::WithMeta<$name>> for crate::types::WithMeta<crate::types::$name> {
+                fn from(value: crate::types::WithMeta<$name>) -> Self {
+                    value.map(Into::into)
+                }
+            }
+
+            impl From<crate::types::WithMeta<crate::types::$name>> for crate::types::WithMeta<$name> {
+                fn from(value: crate::types::WithMeta<crate::types::$name>) -> Self {
+                    value.map(Into::into)
+                }
+            }
+        )*
+    };
+}
+pub(crate) use with_meta_conversions;
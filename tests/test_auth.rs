@@ -0,0 +1,58 @@
+use mcp_schema::*;
+
+#[test]
+fn test_parse_www_authenticate() {
+    let header = r#"Bearer resource_metadata="https://mcp.example.com/.well-known/oauth-protected-resource", scope="files:read files:write", Basic realm="legacy""#;
+    let challenges = parse_challenges(header).unwrap();
+    assert_eq!(challenges.len(), 2);
+    assert!(challenges[0].is_bearer());
+    assert_eq!(
+        challenges[0].resource_metadata(),
+        Some("https://mcp.example.com/.well-known/oauth-protected-resource")
+    );
+    assert_eq!(
+        challenges[0].scopes().collect::<Vec<_>>(),
+        ["files:read", "files:write"]
+    );
+    assert_eq!(challenges[1].scheme, "Basic");
+    assert_eq!(challenges[1].get("REALM"), Some("legacy"));
+    assert_eq!(
+        resource_metadata_url(header).as_deref(),
+        Some("https://mcp.example.com/.well-known/oauth-protected-resource")
+    );
+
+    let challenges = parse_challenges("Negotiate YII=, Bearer").unwrap();
+    assert_eq!(challenges[0].token68.as_deref(), Some("YII="));
+    assert!(challenges[1].params.is_empty());
+
+    assert!(parse_challenges(r#"Bearer error="unterminated"#).is_err());
+    assert!(parse_challenges(r#"Bearer realm="a" "junk""#).is_err());
+}
+
+#[test]
+fn test_challenge_roundtrip() {
+    let challenge = Challenge::bearer()
+        .param("error", "invalid_token")
+        .param("error_description", r#"the "token" expired"#)
+        .with_resource_metadata("https://mcp.example.com/.well-known/oauth-protected-resource");
+    let header = challenge.to_string();
+    assert_eq!(
+        header,
+        r#"Bearer error="invalid_token", error_description="the \"token\" expired", resource_metadata="https://mcp.example.com/.well-known/oauth-protected-resource""#
+    );
+    let parsed: Challenge = header.parse().unwrap();
+    assert_eq!(parsed, challenge);
+    assert_eq!(parsed.error(), Some("invalid_token"));
+}
+
+#[test]
+fn test_bearer_token() {
+    let token = BearerToken::from_authorization_header("bearer abc.DEF-123=").unwrap();
+    assert_eq!(token.secret(), "abc.DEF-123=");
+    assert_eq!(token.authorization_header(), "Bearer abc.DEF-123=");
+    assert!(!format!("{:?}", token).contains("abc"));
+
+    assert!(BearerToken::from_authorization_header("Basic dXNlcg==").is_none());
+    assert!(BearerToken::from_authorization_header("Bearer ").is_none());
+    assert!(BearerToken::from_authorization_header("Bearer a b").is_none());
+}
@@ -0,0 +1,66 @@
+use mcp_schema::*;
+use serde_json::json;
+
+fn batch_requests() -> Vec<serde_json::Value> {
+    vec![
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list" }),
+        json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+        json!({ "jsonrpc": "2.0", "id": "b", "method": "prompts/list" }),
+        json!({ "jsonrpc": "2.0", "id": 3, "method": "resources/list" }),
+    ]
+}
+
+#[test]
+fn test_batch_correlation_by_id() {
+    let requests = batch_requests();
+    let responses: JSONRPCBatchResponse = serde_json::from_value(json!([
+        { "jsonrpc": "2.0", "id": "b", "result": { "prompts": [] } },
+        { "jsonrpc": "2.0", "id": 1, "error": { "code": -32601, "message": "Method not found" } },
+        { "jsonrpc": "2.0", "id": 99, "result": {} }
+    ]))
+    .unwrap();
+
+    let correlation = BatchCorrelator::new(&requests).correlate(responses);
+    assert_eq!(correlation.entries.len(), 4);
+    assert!(matches!(
+        correlation.entries[0],
+        CorrelatedEntry::Error(_, _)
+    ));
+    assert!(matches!(
+        correlation.entries[1],
+        CorrelatedEntry::Notification(_)
+    ));
+    assert!(matches!(
+        correlation.entries[2],
+        CorrelatedEntry::Response(_, _)
+    ));
+    assert!(matches!(
+        correlation.entries[3],
+        CorrelatedEntry::Missing(_)
+    ));
+
+    assert!(!correlation.is_complete());
+    assert_eq!(correlation.missing().count(), 1);
+    assert_eq!(correlation.unmatched.len(), 1);
+    assert!(matches!(
+        correlation.unmatched[0].id(),
+        Some(RequestId::Number(99))
+    ));
+}
+
+#[test]
+fn test_batch_correlation_flags_duplicates() {
+    let requests = batch_requests();
+    let responses: JSONRPCBatchResponse = serde_json::from_value(json!([
+        { "jsonrpc": "2.0", "id": 1, "result": {} },
+        { "jsonrpc": "2.0", "id": 1, "result": {} },
+        { "jsonrpc": "2.0", "id": "b", "result": {} },
+        { "jsonrpc": "2.0", "id": 3, "result": {} }
+    ]))
+    .unwrap();
+
+    let correlation = BatchCorrelator::new(&requests).correlate(responses);
+    assert_eq!(correlation.missing().count(), 0);
+    assert_eq!(correlation.unmatched.len(), 1);
+    assert!(!correlation.is_complete());
+}
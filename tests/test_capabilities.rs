@@ -0,0 +1,169 @@
+use mcp_schema::*;
+use serde_json::json;
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct StreamingSettings {
+    chunk_size: u32,
+}
+
+#[test]
+fn test_experimental_capabilities_typed_access() {
+    let mut experimental = ExperimentalCapabilities::new();
+    experimental
+        .declare(
+            "com.example/streaming",
+            StreamingSettings { chunk_size: 64 },
+        )
+        .unwrap();
+    assert!(experimental.contains("com.example/streaming"));
+    assert_eq!(
+        experimental
+            .get::<StreamingSettings>("com.example/streaming")
+            .unwrap(),
+        Some(StreamingSettings { chunk_size: 64 })
+    );
+    assert_eq!(
+        experimental.get::<StreamingSettings>("missing").unwrap(),
+        None
+    );
+
+    let capabilities: ClientCapabilities = serde_json::from_value(json!({
+        "experimental": { "com.example/streaming": { "chunk_size": 8 } }
+    }))
+    .unwrap();
+    let settings: Option<StreamingSettings> = capabilities
+        .experimental
+        .unwrap()
+        .get("com.example/streaming")
+        .unwrap();
+    assert_eq!(settings, Some(StreamingSettings { chunk_size: 8 }));
+}
+
+#[test]
+fn test_sampling_capability() {
+    let capabilities: ClientCapabilities = serde_json::from_value(json!({
+        "sampling": { "com.example/maxTokens": 4096 }
+    }))
+    .unwrap();
+    let sampling = capabilities.sampling.unwrap();
+    assert_eq!(
+        sampling.get_extra::<u32>("com.example/maxTokens"),
+        Some(4096)
+    );
+    assert_eq!(
+        serde_json::to_value(SamplingCapability::default()).unwrap(),
+        json!({})
+    );
+}
+
+#[test]
+fn test_elicitation_capability() {
+    let value = json!({ "elicitation": {}, "sampling": {} });
+    let capabilities: ClientCapabilities = serde_json::from_value(value.clone()).unwrap();
+    assert!(capabilities.elicitation.is_some());
+    assert_eq!(serde_json::to_value(&capabilities).unwrap(), value);
+
+    let none: ClientCapabilities = serde_json::from_value(json!({})).unwrap();
+    assert!(none.elicitation.is_none());
+    assert_eq!(serde_json::to_value(&none).unwrap(), json!({}));
+}
+
+#[test]
+fn test_experimental_capabilities_key_validation() {
+    let mut experimental = ExperimentalCapabilities::new();
+    assert!(matches!(
+        experimental.declare("streaming", true),
+        Err(ExperimentalCapabilityError::NotNamespaced(_))
+    ));
+    assert!(matches!(
+        experimental.declare("modelcontextprotocol.io/streaming", true),
+        Err(ExperimentalCapabilityError::InvalidKey(_))
+    ));
+    assert!(experimental.is_empty());
+}
+
+fn server_caps(value: serde_json::Value) -> ServerCapabilities {
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn test_server_capabilities_merge() {
+    let a = server_caps(json!({
+        "tools": { "listChanged": true },
+        "resources": { "subscribe": false },
+        "experimental": { "com.example/a": {} }
+    }));
+    let b = server_caps(json!({
+        "prompts": {},
+        "resources": { "subscribe": true, "listChanged": false },
+        "logging": {},
+        "experimental": { "com.example/b": {} }
+    }));
+    let merged = a.merge(b);
+    assert_eq!(merged.tools.unwrap().list_changed, Some(true));
+    assert!(merged.prompts.is_some());
+    assert!(merged.logging.is_some());
+    let resources = merged.resources.unwrap();
+    assert_eq!(resources.subscribe, Some(true));
+    assert_eq!(resources.list_changed, Some(false));
+    let experimental = merged.experimental.unwrap();
+    assert!(experimental.contains("com.example/a"));
+    assert!(experimental.contains("com.example/b"));
+}
+
+#[test]
+fn test_server_capabilities_intersect() {
+    let a = server_caps(json!({
+        "tools": { "listChanged": true },
+        "resources": { "subscribe": true, "listChanged": true },
+        "experimental": { "com.example/a": {}, "com.example/b": {} }
+    }));
+    let b = server_caps(json!({
+        "resources": { "subscribe": true },
+        "prompts": {},
+        "experimental": { "com.example/b": {} }
+    }));
+    let common = a.intersect(b);
+    assert!(common.tools.is_none());
+    assert!(common.prompts.is_none());
+    let resources = common.resources.unwrap();
+    assert_eq!(resources.subscribe, Some(true));
+    assert_eq!(resources.list_changed, Some(false));
+    let experimental = common.experimental.unwrap();
+    assert!(!experimental.contains("com.example/a"));
+    assert!(experimental.contains("com.example/b"));
+}
+
+#[test]
+fn test_client_capabilities_intersect() {
+    let client_caps = |value| serde_json::from_value::<ClientCapabilities>(value).unwrap();
+    let a = client_caps(json!({
+        "roots": { "listChanged": true },
+        "sampling": {},
+        "experimental": { "com.example/a": {} }
+    }));
+    let b = client_caps(json!({
+        "roots": {},
+        "experimental": { "com.example/a": {}, "com.example/b": {} }
+    }));
+    let common = a.clone().intersect(b.clone());
+    assert_eq!(common.roots.unwrap().list_changed, Some(false));
+    assert!(common.sampling.is_none());
+    let experimental = common.experimental.unwrap();
+    assert!(experimental.contains("com.example/a"));
+    assert!(!experimental.contains("com.example/b"));
+
+    let merged = a.merge(b);
+    assert_eq!(merged.roots.unwrap().list_changed, Some(true));
+    assert!(merged.sampling.is_some());
+    assert!(merged.experimental.unwrap().contains("com.example/b"));
+}
+
+#[test]
+fn test_completions_capability() {
+    let a = server_caps(json!({ "completions": {} }));
+    let b = server_caps(json!({ "tools": {} }));
+    assert!(a.completions.is_some());
+    assert!(a.clone().merge(b.clone()).completions.is_some());
+    assert!(a.intersect(b).completions.is_none());
+}
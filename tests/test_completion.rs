@@ -0,0 +1,81 @@
+use mcp_schema::*;
+use serde_json::json;
+use std::collections::HashMap;
+
+#[test]
+fn test_static_completions_filter_by_prefix() {
+    let reference = ReferenceType::prompt("code_review");
+    let provider =
+        StaticCompletions::new().with(&reference, "language", ["python", "pytorch", "rust"]);
+
+    let params: CompleteParams = serde_json::from_value(json!({
+        "ref": { "type": "ref/prompt", "name": "code_review" },
+        "argument": { "name": "language", "value": "py" }
+    }))
+    .unwrap();
+    let result = provider.complete_request(&params);
+    assert_eq!(result.completion.values, ["python", "pytorch"]);
+    assert_eq!(result.completion.total, Some(2));
+    assert_eq!(result.completion.has_more, Some(false));
+
+    let other = ReferenceType::prompt("other");
+    let data = provider.complete(&other, &params.argument, &HashMap::new());
+    assert!(data.values.is_empty());
+}
+
+#[test]
+fn test_static_completions_cap_values() {
+    let reference = ReferenceType::resource_template("file:///{path}");
+    let provider =
+        StaticCompletions::new().with(&reference, "path", (0..250).map(|i| format!("file{}", i)));
+    let argument: CompleteArgument =
+        serde_json::from_value(json!({ "name": "path", "value": "file" })).unwrap();
+    let data = provider.complete(&reference, &argument, &HashMap::new());
+    assert_eq!(data.values.len(), MAX_COMPLETION_VALUES);
+    assert_eq!(data.total, Some(250));
+    assert_eq!(data.has_more, Some(true));
+}
+
+struct Frameworks;
+
+impl CompletionProvider for Frameworks {
+    fn complete(
+        &self,
+        _reference: &ReferenceType,
+        _argument: &CompleteArgument,
+        context: &HashMap<String, String>,
+    ) -> CompletionData {
+        match context.get("language").map(String::as_str) {
+            Some("python") => CompletionData::from_values(["django", "flask"]),
+            Some("rust") => CompletionData::from_values(["axum"]),
+            _ => CompletionData::empty(),
+        }
+    }
+}
+
+#[test]
+fn test_completion_context() {
+    let request = ClientRequest::complete(1, ReferenceType::prompt("scaffold"), "framework", "")
+        .context_argument("language", "rust")
+        .build();
+    let value = serde_json::to_value(&request).unwrap();
+    assert_eq!(
+        value["params"]["context"],
+        json!({ "arguments": { "language": "rust" } })
+    );
+    let ClientRequest::Complete { params, .. } = serde_json::from_value(value).unwrap() else {
+        panic!("expected a completion request");
+    };
+    assert_eq!(
+        Frameworks.complete_request(&params).completion.values,
+        ["axum"]
+    );
+
+    let params: CompleteParams = serde_json::from_value(json!({
+        "ref": { "type": "ref/prompt", "name": "scaffold" },
+        "argument": { "name": "framework", "value": "" },
+        "context": {}
+    }))
+    .unwrap();
+    assert!(params.context.unwrap().arguments.is_empty());
+}
@@ -0,0 +1,73 @@
+use mcp_schema::*;
+use serde_json::json;
+
+#[test]
+fn test_parse_mcp_json() {
+    let config: McpConfig = serde_json::from_value(json!({
+        "mcpServers": {
+            "files": {
+                "command": "npx",
+                "args": ["-y", "server-files", "/tmp"],
+                "env": { "DEBUG": "1" }
+            },
+            "remote": {
+                "type": "http",
+                "url": "https://mcp.example.com/mcp",
+                "headers": { "Authorization": "Bearer token" }
+            }
+        },
+        "globalShortcut": "Ctrl+Space"
+    }))
+    .unwrap();
+    assert!(config.validate().is_ok());
+    match &config.mcp_servers["files"] {
+        ServerConfig::Stdio(files) => {
+            assert_eq!(files.command, "npx");
+            assert_eq!(files.args.len(), 3);
+            assert_eq!(files.env["DEBUG"], "1");
+        }
+        other => panic!("unexpected config: {:?}", other),
+    }
+    assert!(matches!(
+        &config.mcp_servers["remote"],
+        ServerConfig::Remote(remote) if remote.headers["Authorization"] == "Bearer token"
+    ));
+    assert_eq!(config.extra["globalShortcut"], "Ctrl+Space");
+}
+
+#[test]
+fn test_generate_and_validate_mcp_json() {
+    let config = McpConfig::new()
+        .server("git", StdioServerConfig::new("uvx").arg("mcp-server-git"))
+        .server(
+            "remote",
+            RemoteServerConfig::new("https://mcp.example.com/sse"),
+        );
+    assert_eq!(
+        serde_json::to_value(&config).unwrap(),
+        json!({
+            "mcpServers": {
+                "git": { "command": "uvx", "args": ["mcp-server-git"] },
+                "remote": { "url": "https://mcp.example.com/sse" }
+            }
+        })
+    );
+
+    let config = McpConfig::new().server("bad", RemoteServerConfig::new("ftp://example.com"));
+    assert!(matches!(
+        config.validate(),
+        Err(ConfigError::InvalidUrl { .. })
+    ));
+    let mut stdio = StdioServerConfig::new("node");
+    stdio.r#type = Some("sse".to_string());
+    assert!(matches!(
+        McpConfig::new().server("x", stdio).validate(),
+        Err(ConfigError::TypeMismatch { .. })
+    ));
+    assert_eq!(
+        McpConfig::new()
+            .server("empty", StdioServerConfig::new(" "))
+            .validate(),
+        Err(ConfigError::EmptyCommand("empty".to_string()))
+    );
+}
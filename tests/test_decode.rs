@@ -0,0 +1,173 @@
+use mcp_schema::*;
+use serde_json::json;
+
+#[test]
+fn test_lenient_decoder_repairs_envelope() {
+    let decoded: Decoded<ClientRequest> = LenientDecoder::new()
+        .decode(json!({
+            "jsonrpc": "2",
+            "id": 4.0,
+            "method": "initialize",
+            "params": {
+                "protocol_version": "2024-11-05",
+                "capabilities": {},
+                "clientInfo": { "name": "sloppy", "version": "0.1" }
+            }
+        }))
+        .unwrap();
+    match &decoded.value {
+        ClientRequest::Initialize { id, params, .. } => {
+            assert!(matches!(id, RequestId::Number(4)));
+            assert_eq!(params.protocol_version, "2024-11-05");
+        }
+        other => panic!("unexpected request: {:?}", other),
+    }
+    assert_eq!(
+        decoded.warnings,
+        vec![
+            DecodeWarning::NonStandardJsonRpc(json!("2")),
+            DecodeWarning::FloatId(4.0),
+            DecodeWarning::SnakeCaseKey {
+                from: "protocol_version".into(),
+                to: "protocolVersion".into()
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_lenient_decoder_missing_jsonrpc() {
+    let decoded: Decoded<ClientRequest> = LenientDecoder::new()
+        .decode_str(r#"{"id": 1, "method": "ping"}"#)
+        .unwrap();
+    assert_eq!(decoded.warnings, vec![DecodeWarning::MissingJsonRpc]);
+
+    let strict = LenientDecoder {
+        allow_missing_jsonrpc: false,
+        ..LenientDecoder::default()
+    };
+    assert!(strict
+        .decode_str::<ClientRequest>(r#"{"id": 1, "method": "ping"}"#)
+        .is_err());
+}
+
+#[test]
+fn test_lenient_decoder_keeps_nested_keys() {
+    let decoded: Decoded<ClientRequest> = LenientDecoder::new()
+        .decode(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "search", "arguments": { "max_results": 5 } }
+        }))
+        .unwrap();
+    assert!(decoded.warnings.is_empty());
+    match decoded.value {
+        ClientRequest::CallTool { params, .. } => {
+            assert!(params.arguments.unwrap().contains_key("max_results"));
+        }
+        other => panic!("unexpected request: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(not(feature = "strict-fields"))]
+fn test_lenient_decoder_keeps_vendor_keys() {
+    let decoded: Decoded<ClientRequest> = LenientDecoder::new()
+        .decode(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": {
+                "name": "search",
+                "com.example/trace_id": "t-1",
+                "x_vendor_flag": true
+            }
+        }))
+        .unwrap();
+    assert!(decoded.warnings.is_empty());
+    match decoded.value {
+        ClientRequest::CallTool { params, .. } => {
+            assert!(params.extra.contains_key("com.example/trace_id"));
+            assert!(params.extra.contains_key("x_vendor_flag"));
+        }
+        other => panic!("unexpected request: {:?}", other),
+    }
+}
+
+#[test]
+fn test_text_content_truncation() {
+    let mut text = TextContent::new("héllo wörld");
+    assert!(!text.truncate_to(100));
+    assert!(text.truncate_to(5));
+    assert_eq!(text.text, "héllo");
+    assert!(text.truncate_to_bytes(2));
+    assert_eq!(text.text, "h");
+}
+
+#[test]
+fn test_decode_limits() {
+    let limits = DecodeLimits {
+        max_text_chars: Some(4),
+        max_text_bytes: None,
+    };
+    let mut contents = vec![
+        PromptContent::Text(TextContent::new("short")),
+        PromptContent::Text(TextContent::new("ok")),
+    ];
+    assert_eq!(limits.enforce_all(&mut contents), 1);
+
+    let decoder = LenientDecoder {
+        limits,
+        ..LenientDecoder::default()
+    };
+    let message = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "result": { "content": [{ "type": "text", "text": "too long" }] }
+    });
+    let err = decoder
+        .decode::<JSONRPCResponse<CallToolResult>>(message)
+        .unwrap_err();
+    assert!(err.to_string().contains("limit is 4"));
+}
+
+#[test]
+fn test_parse_strict_rejects_malformed_envelopes() {
+    let notification = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "notifications/initialized"
+    });
+    #[cfg(not(feature = "strict-fields"))]
+    assert!(serde_json::from_value::<ClientNotification>(notification.clone()).is_ok());
+    assert!(matches!(
+        from_value_strict::<ClientNotification>(notification),
+        Err(StrictError::NotificationWithId(method)) if method == "notifications/initialized"
+    ));
+
+    let response = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "result": {},
+        "error": { "code": -32603, "message": "boom" }
+    });
+    #[cfg(not(feature = "strict-fields"))]
+    assert!(serde_json::from_value::<JSONRPCResponse<serde_json::Value>>(response.clone()).is_ok());
+    assert!(matches!(
+        from_value_strict::<JSONRPCResponse<serde_json::Value>>(response),
+        Err(StrictError::ResultAndError)
+    ));
+
+    let error = parse_strict::<ClientRequest>(r#"{"jsonrpc": "1.0", "id": 1, "method": "ping"}"#)
+        .unwrap_err();
+    assert_eq!(error.to_string(), r#""jsonrpc" must be "2.0", found "1.0""#);
+    assert!(matches!(
+        parse_strict::<Vec<JSONRPCMessage>>(r#"[{"id": 1, "method": "ping"}]"#),
+        Err(StrictError::JsonRpcVersion(None))
+    ));
+
+    let request: ClientRequest =
+        parse_strict(r#"{"jsonrpc": "2.0", "id": 1, "method": "ping"}"#).unwrap();
+    assert_eq!(request.to_string(), "ping #1");
+}
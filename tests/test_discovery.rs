@@ -0,0 +1,59 @@
+use mcp_schema::*;
+use serde_json::json;
+
+#[test]
+fn test_discovery_document() {
+    let document: DiscoveryDocument = serde_json::from_value(json!({
+        "name": "weather",
+        "version": "1.2.0",
+        "protocolVersions": ["2025-06-18"],
+        "transports": [{ "type": "streamable-http", "url": "https://weather.example/mcp" }],
+        "capabilities": { "tools": { "listChanged": true } },
+        "protectedResourceMetadata": "https://weather.example/.well-known/oauth-protected-resource"
+    }))
+    .unwrap();
+    assert!(document.validate().is_ok());
+    assert_eq!(document.transports[0].r#type, "streamable-http");
+
+    assert_eq!(
+        DiscoveryDocument::new("empty").validate(),
+        Err(DiscoveryError::MissingField("transports"))
+    );
+    assert!(matches!(
+        DiscoveryDocument::new("bad")
+            .transport("sse", "ws://weather.example/sse")
+            .validate(),
+        Err(DiscoveryError::InvalidUrl { .. })
+    ));
+}
+
+#[test]
+fn test_oauth_metadata() {
+    let resource = ProtectedResourceMetadata::new("https://weather.example/mcp")
+        .authorization_server("https://auth.example");
+    assert!(resource.validate().is_ok());
+    assert_eq!(
+        serde_json::to_value(&resource).unwrap(),
+        json!({
+            "resource": "https://weather.example/mcp",
+            "authorization_servers": ["https://auth.example"]
+        })
+    );
+    assert_eq!(
+        ProtectedResourceMetadata::new("https://weather.example/mcp").validate(),
+        Err(DiscoveryError::MissingField("authorization_servers"))
+    );
+
+    let mut server: AuthorizationServerMetadata = serde_json::from_value(json!({
+        "issuer": "https://auth.example",
+        "authorization_endpoint": "https://auth.example/authorize",
+        "token_endpoint": "https://auth.example/token",
+        "registration_endpoint": "https://auth.example/register",
+        "response_types_supported": ["code"],
+        "code_challenge_methods_supported": ["S256"]
+    }))
+    .unwrap();
+    assert!(server.validate().is_ok());
+    server.code_challenge_methods_supported = vec!["plain".to_string()];
+    assert_eq!(server.validate(), Err(DiscoveryError::PkceUnsupported));
+}
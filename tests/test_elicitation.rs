@@ -0,0 +1,154 @@
+use mcp_schema::*;
+use serde_json::json;
+
+#[test]
+fn test_enum_field_with_display_names() {
+    let params = ElicitationSchema::new()
+        .enum_field("color", ["r", "g"], ["Red", "Green"])
+        .required("color")
+        .into_params("Pick a color");
+    assert_eq!(
+        serde_json::to_value(&params.requested_schema).unwrap(),
+        json!({
+            "type": "object",
+            "properties": {
+                "color": { "type": "string", "enum": ["r", "g"], "enumNames": ["Red", "Green"] }
+            },
+            "required": ["color"]
+        })
+    );
+
+    let PrimitiveSchemaDefinition::Enum(schema) = &params.requested_schema.properties["color"]
+    else {
+        panic!("expected an enum schema");
+    };
+    assert_eq!(schema.label_for("g"), Some("Green"));
+    assert_eq!(schema.label_for("b"), None);
+    assert_eq!(EnumSchema::new(["a"]).label_for("a"), Some("a"));
+}
+
+#[test]
+#[should_panic(expected = "one display name per value")]
+fn test_enum_names_must_match_values() {
+    EnumSchema::new(["r", "g"]).with_names(["Red"]);
+}
+
+#[test]
+fn test_elicitation_result_constructors() {
+    let accepted = ElicitationCreateResult::accepted(json!({ "color": "r" }));
+    assert_eq!(
+        serde_json::to_value(&accepted).unwrap(),
+        json!({ "action": "accept", "content": { "color": "r" } })
+    );
+    assert_eq!(
+        serde_json::to_value(ElicitationCreateResult::declined()).unwrap(),
+        json!({ "action": "decline" })
+    );
+    let legacy: ElicitationCreateResult =
+        serde_json::from_value(json!({ "action": "reject" })).unwrap();
+    assert_eq!(legacy.action, ElicitationAction::Decline);
+    assert_eq!(
+        serde_json::to_value(ElicitationCreateResult::cancelled()).unwrap(),
+        json!({ "action": "cancel" })
+    );
+}
+
+#[test]
+fn test_elicitation_result_validation() {
+    assert!(ElicitationCreateResult::declined().validate().is_ok());
+    let mut result = ElicitationCreateResult::cancelled();
+    result.content = Some(json!({}));
+    assert_eq!(
+        result.validate(),
+        Err(ElicitationError::UnexpectedContent(
+            ElicitationAction::Cancel
+        ))
+    );
+    result.action = ElicitationAction::Accept;
+    result.content = None;
+    assert_eq!(result.validate(), Err(ElicitationError::MissingContent));
+
+    let schema = ElicitationSchema::new()
+        .enum_field("color", ["r", "g"], ["Red", "Green"])
+        .property("count", NumberSchema::integer())
+        .required("color")
+        .build();
+    let check = |content| ElicitationCreateResult::accepted(content).validate_against(&schema);
+    assert!(check(json!({ "color": "r", "count": 2 })).is_ok());
+    assert_eq!(
+        check(json!({ "count": 2 })),
+        Err(ElicitationError::MissingProperty("color".into()))
+    );
+    assert_eq!(
+        check(json!({ "color": "b" })),
+        Err(ElicitationError::InvalidProperty("color".into()))
+    );
+    assert_eq!(
+        check(json!({ "color": "r", "count": 1.5 })),
+        Err(ElicitationError::InvalidProperty("count".into()))
+    );
+    assert_eq!(check(json!("r")), Err(ElicitationError::NotAnObject));
+}
+
+#[test]
+fn test_elicitation_is_a_server_request() {
+    let request: ServerRequest = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "id": 5,
+        "method": "elicitation/create",
+        "params": {
+            "message": "Pick a city",
+            "requestedSchema": { "type": "object", "properties": { "city": { "type": "string" } } }
+        }
+    }))
+    .unwrap();
+    assert_eq!(request.method(), Method::ElicitationCreate);
+    assert_eq!(
+        request.to_string(),
+        "elicitation/create #5 message=\"Pick a city\""
+    );
+}
+
+#[test]
+fn test_requested_schema_is_typed() {
+    let schema: RequestedSchema = serde_json::from_value(json!({
+        "type": "object",
+        "properties": {
+            "email": { "type": "string", "title": "Email", "format": "email" },
+            "age": { "type": "integer", "minimum": 0 },
+            "subscribe": { "type": "boolean", "default": true },
+            "plan": { "type": "string", "enum": ["free", "pro"] }
+        },
+        "required": ["email"]
+    }))
+    .unwrap();
+    let email = &schema.properties["email"];
+    assert_eq!(email.title(), Some("Email"));
+    assert!(matches!(
+        email,
+        PrimitiveSchemaDefinition::String(StringSchema {
+            format: Some(StringFormat::Email),
+            ..
+        })
+    ));
+    assert!(schema.properties["age"].accepts(&json!(3)));
+    assert!(!schema.properties["age"].accepts(&json!(3.5)));
+    assert!(matches!(
+        schema.properties["subscribe"],
+        PrimitiveSchemaDefinition::Boolean(BooleanSchema {
+            default: Some(true),
+            ..
+        })
+    ));
+    assert!(matches!(
+        schema.properties["plan"],
+        PrimitiveSchemaDefinition::Enum(_)
+    ));
+
+    let nested = json!({
+        "type": "object",
+        "properties": { "address": { "type": "object", "properties": {} } }
+    });
+    let err = serde_json::from_value::<RequestedSchema>(nested).unwrap_err();
+    assert!(err.to_string().contains("cannot have type \"object\""));
+}
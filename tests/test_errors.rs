@@ -0,0 +1,246 @@
+use mcp_schema::*;
+use serde_json::json;
+use std::time::Duration;
+
+#[test]
+fn test_tool_error_as_tool_result() {
+    let err = ToolError::timeout("weather service did not respond")
+        .with_data(json!({ "elapsedMs": 5000 }));
+    let result = CallToolResult::from(err);
+    assert_eq!(result.is_error, Some(true));
+    assert!(result.structured_content.is_none());
+    match &result.content[0] {
+        PromptContent::Text(text) => {
+            assert_eq!(text.text, "timed out: weather service did not respond")
+        }
+        other => panic!("unexpected content: {:?}", other),
+    }
+    match &result.content[1] {
+        PromptContent::Text(text) => assert_eq!(text.text, r#"{"elapsedMs":5000}"#),
+        other => panic!("unexpected content: {:?}", other),
+    }
+
+    let result = CallToolResult::from(ToolError::execution_failed("disk full"));
+    assert_eq!(result.content.len(), 1);
+}
+
+#[test]
+fn test_tool_error_as_protocol_error() {
+    let err = ToolError::invalid_arguments("missing `city`");
+    let error = err.into_jsonrpc_error(RequestId::Number(9));
+    assert_eq!(error.error.code, INVALID_PARAMS);
+    assert_eq!(error.error.message, "invalid arguments: missing `city`");
+    assert_eq!(error.json_rpc, JSONRPC_VERSION);
+
+    let detail = RPCErrorDetail::from(ToolError::custom(-32050, "quota exceeded"));
+    assert_eq!(detail.code, -32050);
+    assert_eq!(detail.message, "quota exceeded");
+}
+
+#[derive(Debug)]
+struct Outer(std::io::Error);
+
+impl std::fmt::Display for Outer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("failed to load config")
+    }
+}
+
+impl std::error::Error for Outer {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+#[test]
+fn test_internal_error_captures_chain() {
+    let err = Outer(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        "config.toml missing",
+    ));
+    let detail = RPCErrorDetail::internal_from(&err);
+    assert_eq!(detail.code, INTERNAL_ERROR);
+    assert_eq!(detail.message, "failed to load config");
+    assert_eq!(
+        detail.data,
+        Some(json!({ "chain": ["failed to load config", "config.toml missing"] }))
+    );
+}
+
+#[test]
+fn test_rate_limited_error() {
+    let error = JSONRPCError::rate_limited(7, Duration::from_millis(1500));
+    let value = serde_json::to_value(&error).unwrap();
+    assert_eq!(value["error"]["code"], RATE_LIMITED);
+    assert_eq!(value["error"]["data"], json!({ "retryAfterMs": 1500 }));
+
+    let detail: RPCErrorDetail = serde_json::from_value(json!({
+        "code": RATE_LIMITED,
+        "message": "slow down",
+        "data": { "retryAfterMs": 200, "limit": 60, "remaining": 0 }
+    }))
+    .unwrap();
+    let data = detail.rate_limited_data().unwrap();
+    assert_eq!(
+        data,
+        RateLimitedData::new(Duration::from_millis(200)).with_limit(60, 0)
+    );
+    assert_eq!(data.retry_after(), Duration::from_millis(200));
+
+    let other = RPCErrorDetail::method_not_found("tools/call");
+    assert!(other.rate_limited_data().is_none());
+}
+
+#[cfg(feature = "anyhow")]
+#[test]
+fn test_jsonrpc_error_from_anyhow() {
+    use anyhow::Context;
+
+    let err = std::fs::read("/definitely/not/here")
+        .context("reading fixture")
+        .unwrap_err();
+    let error = JSONRPCError::from_anyhow(1, &err);
+    assert_eq!(error.error.code, INTERNAL_ERROR);
+    assert_eq!(error.error.message, "reading fixture");
+    let chain = error.error.data.unwrap()["chain"].as_array().unwrap().len();
+    assert_eq!(chain, 2);
+}
+
+#[cfg(feature = "jsonrpsee")]
+#[test]
+fn test_jsonrpsee_interop() {
+    use jsonrpsee_core::traits::ToRpcParams;
+    use jsonrpsee_types::ErrorObjectOwned;
+
+    let detail = RPCErrorDetail {
+        code: INVALID_PARAMS,
+        message: "bad uri".to_string(),
+        data: Some(json!({ "uri": "x" })),
+    };
+    let object = ErrorObjectOwned::from(detail);
+    assert_eq!(object.code(), INVALID_PARAMS);
+    let back = RPCErrorDetail::from(object);
+    assert_eq!(back.message, "bad uri");
+    assert_eq!(back.data, Some(json!({ "uri": "x" })));
+
+    let params = ReadResourceParams {
+        uri: "file:///a.txt".into(),
+        extra: Default::default(),
+    }
+    .to_rpc_params()
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(params.get()).unwrap(),
+        json!({ "uri": "file:///a.txt" })
+    );
+}
+
+#[cfg(feature = "jsonrpc-core")]
+#[test]
+fn test_jsonrpc_core_interop() {
+    let error = jsonrpc_core::Error::from(RPCErrorDetail {
+        code: METHOD_NOT_FOUND,
+        message: "no such method".to_string(),
+        data: None,
+    });
+    assert_eq!(error.code, jsonrpc_core::ErrorCode::MethodNotFound);
+    assert_eq!(RPCErrorDetail::from(error).code, METHOD_NOT_FOUND);
+
+    let params = jsonrpc_core::Params::try_from(GetPromptParams {
+        name: "greet".into(),
+        arguments: None,
+        extra: Default::default(),
+    })
+    .unwrap();
+    let parsed: GetPromptParams = params.parse().unwrap();
+    assert_eq!(parsed.name, "greet");
+}
+
+#[test]
+fn test_error_with_null_id() {
+    let raw = json!({
+        "jsonrpc": "2.0",
+        "id": null,
+        "error": { "code": PARSE_ERROR, "message": "Parse error" }
+    });
+    let error: JSONRPCError = serde_json::from_value(raw.clone()).unwrap();
+    assert_eq!(error.id, None);
+    let message: JSONRPCMessage = serde_json::from_value(raw.clone()).unwrap();
+    assert!(matches!(message, JSONRPCMessage::Error(_)));
+    assert!(message.id().is_none());
+
+    let error = JSONRPCError::without_id(RPCErrorDetail {
+        code: PARSE_ERROR,
+        message: "Parse error".to_string(),
+        data: None,
+    });
+    assert_eq!(serde_json::to_value(&error).unwrap(), raw);
+
+    let error = JSONRPCError::new(3, RPCErrorDetail::method_not_found("nope"));
+    assert_eq!(serde_json::to_value(&error).unwrap()["id"], 3);
+}
+
+#[test]
+fn test_error_codes() {
+    assert_eq!(i32::from(ErrorCode::MethodNotFound), METHOD_NOT_FOUND);
+    assert_eq!(ErrorCode::from(-32002), ErrorCode::ResourceNotFound);
+    assert_eq!(ErrorCode::from(-32050), ErrorCode::Other(-32050));
+    assert_eq!(ErrorCode::Other(-32601), ErrorCode::MethodNotFound);
+    let codes: std::collections::HashSet<_> =
+        [ErrorCode::Other(-32601), ErrorCode::MethodNotFound].into();
+    assert_eq!(codes.len(), 1);
+    for code in ErrorCode::ALL {
+        assert_eq!(ErrorCode::from(code.code()), *code);
+    }
+    assert_eq!(
+        ErrorCode::InvalidParams.to_string(),
+        "Invalid params (-32602)"
+    );
+
+    let error = RPCErrorDetail::invalid_params("missing `uri`");
+    assert_eq!(error.code, INVALID_PARAMS);
+    assert_eq!(error.error_code(), ErrorCode::InvalidParams);
+    assert_eq!(
+        RPCErrorDetail::new(ErrorCode::ParseError, "bad json").code,
+        PARSE_ERROR
+    );
+
+    let error = RPCErrorDetail::from(ResourceNotFound {
+        uri: "file:///missing".to_string(),
+    });
+    assert_eq!(error.code, RESOURCE_NOT_FOUND);
+    assert_eq!(error.data, Some(json!({ "uri": "file:///missing" })));
+}
+
+#[test]
+fn test_typed_error_data() {
+    let error = RPCErrorDetail::resource_not_found("file:///missing");
+    assert_eq!(
+        error.data_as::<ResourceNotFoundData>(),
+        Some(ResourceNotFoundData {
+            uri: "file:///missing".to_string()
+        })
+    );
+    assert!(error.data_as::<RateLimitedData>().is_none());
+
+    let error = RPCErrorDetail::unsupported_protocol_version("1.0.0");
+    assert_eq!(error.code, INVALID_PARAMS);
+    let data: UnsupportedProtocolVersionData = error.data_as().unwrap();
+    assert_eq!(data.requested, "1.0.0");
+    assert!(data.supported.iter().any(|version| version == "2024-11-05"));
+
+    let error = RPCErrorDetail::new(-32050, "quota exceeded")
+        .with_data(json!({ "quota": 10 }))
+        .unwrap();
+    assert_eq!(error.data, Some(json!({ "quota": 10 })));
+    let mut map = std::collections::HashMap::new();
+    map.insert((1, 2), "tuple keys are not JSON object keys");
+    assert!(RPCErrorDetail::new(-32050, "bad data")
+        .with_data(map)
+        .is_err());
+
+    let error = RPCErrorDetail::internal_from(&std::fmt::Error);
+    let data: InternalErrorData = error.data_as().unwrap();
+    assert_eq!(data.chain, vec![std::fmt::Error.to_string()]);
+}
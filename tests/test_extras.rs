@@ -0,0 +1,105 @@
+// Every test here keeps unknown fields, which `strict-fields` rejects.
+#![cfg(not(feature = "strict-fields"))]
+
+use mcp_schema::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// A vendor type declared the way spec types are.
+#[mcp_extras]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Widget {
+    widget_id: String,
+}
+
+#[test]
+fn test_mcp_extras_attribute() {
+    let value = json!({
+        "_meta": { "com.example/owner": "ops" },
+        "widgetId": "w-1",
+        "color": "blue"
+    });
+    let mut widget: Widget = serde_json::from_value(value.clone()).unwrap();
+    assert_eq!(widget.widget_id, "w-1");
+    assert_eq!(widget.meta().unwrap()["com.example/owner"], "ops");
+    assert_eq!(widget.extra()["color"], "blue");
+    assert_eq!(serde_json::to_value(&widget).unwrap(), value);
+
+    widget.meta_mut().clear();
+    widget.extra_mut().remove("color");
+    assert_eq!(
+        serde_json::to_value(&widget).unwrap(),
+        json!({ "_meta": {}, "widgetId": "w-1" })
+    );
+
+    let root: Root = serde_json::from_value(json!({ "uri": "file:///work", "x": 1 })).unwrap();
+    assert_eq!(root.extra()["x"], 1);
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Ui {
+    icon: String,
+}
+
+#[test]
+fn test_typed_extras() {
+    let mut tool: Tool = serde_json::from_value(json!({
+        "name": "search",
+        "inputSchema": { "type": "object" },
+        "com.example/ui": { "icon": "magnifier" }
+    }))
+    .unwrap();
+    assert_eq!(
+        tool.get_extra::<Ui>("com.example/ui"),
+        Some(Ui {
+            icon: "magnifier".to_string()
+        })
+    );
+    assert_eq!(tool.get_extra::<u32>("com.example/ui"), None);
+    assert_eq!(tool.get_extra::<Ui>("missing"), None);
+
+    tool.set_meta_key(
+        "com.example/ui",
+        Ui {
+            icon: "gear".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        tool.get_meta_key::<Ui>("com.example/ui").unwrap().icon,
+        "gear"
+    );
+    assert_eq!(tool.get_meta_key::<u32>("com.example/ui"), None);
+
+    tool.set_extra("com.example/rank", 3).unwrap();
+    assert_eq!(tool.extra_keys(), ["com.example/rank", "com.example/ui"]);
+    assert_eq!(
+        serde_json::to_value(&tool).unwrap()["com.example/rank"],
+        json!(3)
+    );
+    assert_eq!(tool.remove_extra("com.example/rank"), Some(json!(3)));
+
+    let mut config = McpConfig::new();
+    config.set_extra("globalShortcut", "Ctrl+Space").unwrap();
+    assert_eq!(
+        serde_json::to_value(&config).unwrap(),
+        json!({ "mcpServers": {}, "globalShortcut": "Ctrl+Space" })
+    );
+}
+
+#[test]
+fn test_extras_on_revision_results() {
+    let mut result: v2024_11_05::CallToolResult = serde_json::from_value(json!({
+        "_meta": { "com.example/trace": "t-1" },
+        "content": [{ "type": "text", "text": "done" }],
+        "com.example/cost": 2
+    }))
+    .unwrap();
+    assert_eq!(result.meta().unwrap()["com.example/trace"], "t-1");
+    assert_eq!(result.get_extra::<u32>("com.example/cost"), Some(2));
+
+    result.meta_mut().clear();
+    result.remove_extra("com.example/cost");
+    assert!(result.extra_keys().is_empty());
+}
@@ -0,0 +1,57 @@
+use mcp_schema::*;
+use serde_json::{json, Value};
+
+#[test]
+fn test_frame_parser_split_frames() {
+    let mut parser = FrameParser::<Value>::new();
+    assert!(parser.feed(br#"{"jsonrpc":"2.0","id":1,"me"#).is_empty());
+    assert!(parser.pending() > 0);
+    let messages = parser.feed(br#"thod":"ping"}"#);
+    assert_eq!(messages.len(), 1);
+    assert_eq!(
+        messages[0].as_ref().unwrap(),
+        &json!({ "jsonrpc": "2.0", "id": 1, "method": "ping" })
+    );
+    assert_eq!(parser.pending(), 0);
+}
+
+#[test]
+fn test_frame_parser_concatenated_frames() {
+    let mut parser = FrameParser::<ClientRequest>::new();
+    let input = concat!(
+        r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#,
+        r#"{"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"x}{","arguments":{"s":"\"}"}}}"#,
+        "\n",
+        r#"{"jsonrpc":"2.0","id":3,"#
+    );
+    let messages = parser.feed(input.as_bytes());
+    assert_eq!(messages.len(), 2);
+    match messages[1].as_ref().unwrap() {
+        ClientRequest::CallTool { params, .. } => assert_eq!(params.name, "x}{"),
+        other => panic!("unexpected request: {:?}", other),
+    }
+    let messages = parser.feed(br#""method":"tools/list"}"#);
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].is_ok());
+}
+
+#[test]
+fn test_frame_parser_reports_garbage() {
+    let mut parser = FrameParser::<Value>::new();
+    let messages = parser.feed(b"garbage {\"a\":1}");
+    assert_eq!(messages.len(), 2);
+    assert!(messages[0].is_err());
+    assert_eq!(messages[1].as_ref().unwrap(), &json!({ "a": 1 }));
+}
+
+#[test]
+fn test_frame_parser_rejects_oversized_frames() {
+    let mut parser = FrameParser::<Value>::new().with_max_frame_len(16);
+    let messages = parser.feed(br#"{"data":"0123456789abcdef"#);
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].is_err());
+    assert_eq!(parser.pending(), 0);
+    let messages = parser.feed(br#"0123"}{"a":1}"#);
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].as_ref().unwrap(), &json!({ "a": 1 }));
+}
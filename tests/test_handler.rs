@@ -0,0 +1,96 @@
+use mcp_schema::*;
+use serde_json::json;
+
+struct Tools;
+
+impl McpServerHandler for Tools {
+    fn list_tools(&self, params: ListToolsParams) -> Result<ListToolsResult, RPCErrorDetail> {
+        let mut registry = ToolRegistry::new();
+        registry
+            .add(
+                serde_json::from_value(json!({
+                    "name": "echo",
+                    "inputSchema": { "type": "object" }
+                }))
+                .unwrap(),
+            )
+            .unwrap();
+        Ok(registry.list(params.0.cursor.as_deref()))
+    }
+}
+
+fn request(value: serde_json::Value) -> ClientRequest {
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn test_dispatch_routes_to_handler() {
+    let response = dispatch_client_request(
+        &Tools,
+        request(json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list" })),
+    )
+    .unwrap();
+    assert_eq!(response.id.to_string(), "1");
+    match response.result {
+        ServerResult::ListTools(result) => assert_eq!(result.items[0].name, "echo"),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let response = dispatch_client_request(
+        &Tools,
+        request(json!({ "jsonrpc": "2.0", "id": 2, "method": "ping" })),
+    )
+    .unwrap();
+    assert!(matches!(response.result, ServerResult::Empty(_)));
+}
+
+#[test]
+fn test_dispatch_defaults_to_method_not_found() {
+    let error = dispatch_client_request(
+        &Tools,
+        request(json!({
+            "jsonrpc": "2.0",
+            "id": "a",
+            "method": "resources/read",
+            "params": { "uri": "file:///x" }
+        })),
+    )
+    .unwrap_err();
+    assert_eq!(error.id, Some(RequestId::from("a")));
+    assert_eq!(error.error.code, METHOD_NOT_FOUND);
+    assert_eq!(error.error.message, "method not found: resources/read");
+}
+
+struct Roots;
+
+impl McpClientHandler for Roots {
+    fn list_roots(&self, _params: ListRootsParams) -> Result<ListRootsResult, RPCErrorDetail> {
+        Ok(serde_json::from_value(json!({ "roots": [{ "uri": "file:///work" }] })).unwrap())
+    }
+}
+
+#[test]
+fn test_dispatch_server_request() {
+    let request: ServerRequest =
+        serde_json::from_value(json!({ "jsonrpc": "2.0", "id": 5, "method": "roots/list" }))
+            .unwrap();
+    let response = dispatch_server_request(&Roots, request).unwrap();
+    match &response.result {
+        ClientResult::ListRoots(result) => assert_eq!(result.roots[0].uri, "file:///work"),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(
+        serde_json::to_value(&response).unwrap()["result"]["roots"][0]["uri"],
+        "file:///work"
+    );
+
+    let request: ServerRequest = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "id": 6,
+        "method": "sampling/createMessage",
+        "params": { "messages": [], "maxTokens": 10 }
+    }))
+    .unwrap();
+    let error = dispatch_server_request(&Roots, request).unwrap_err();
+    assert_eq!(error.error.code, METHOD_NOT_FOUND);
+}
@@ -59,12 +59,13 @@ fn test_call_tool_result_with_structured_content() {
     assert!(result.structured_content.is_some());
     assert_eq!(result.is_error, Some(false));
     
-    let structured = result.structured_content.unwrap();
+    let structured = result.into_inner().structured_content.unwrap();
     assert_eq!(structured["temperature"], 22.5);
     assert_eq!(structured["humidity"], 65);
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_elicitation_create_request() {
     let request_json = json!({
         "jsonrpc": "2.0",
@@ -89,7 +90,7 @@ fn test_elicitation_create_request() {
     
     if let ClientRequest::ElicitationCreate { params, .. } = request {
         assert_eq!(params.message, "Please provide your email");
-        assert!(params.requested_schema.is_object());
+        assert_eq!(params.requested_schema.required, Some(vec!["email".to_string()]));
     } else {
         panic!("Expected ElicitationCreate variant");
     }
@@ -113,10 +114,22 @@ fn test_elicitation_create_result() {
     });
     
     let result: ElicitationCreateResult = serde_json::from_value(reject_result).unwrap();
-    assert!(matches!(result.action, ElicitationAction::Reject));
+    assert!(matches!(result.action, ElicitationAction::Decline));
     assert!(result.content.is_none());
 }
 
+#[test]
+fn test_elicitation_create_result_decline() {
+    let decline_result = json!({
+        "action": "decline"
+    });
+
+    let result: ElicitationCreateResult = serde_json::from_value(decline_result.clone()).unwrap();
+    assert!(matches!(result.action, ElicitationAction::Decline));
+    assert!(result.content.is_none());
+    assert_eq!(serde_json::to_value(&result).unwrap(), decline_result);
+}
+
 #[test]
 fn test_backward_compatibility() {
     // Verify that old-style tools without new fields still work
@@ -0,0 +1,227 @@
+use mcp_schema::*;
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_log_record_into_params() {
+    let err = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "connection reset");
+    let record = LogRecord::new(LoggingLevel::Error, "search failed")
+        .logger("search")
+        .field("query", "rust")
+        .unwrap()
+        .field("attempt", 3)
+        .unwrap()
+        .error(&err)
+        .timestamp("2025-01-01T12:00:00Z".parse().unwrap());
+    let params = LoggingMessageParams::from(record);
+    assert!(matches!(params.level, LoggingLevel::Error));
+    assert_eq!(params.logger.as_deref(), Some("search"));
+    assert_eq!(
+        params.data,
+        json!({
+            "message": "search failed",
+            "fields": { "query": "rust", "attempt": 3 },
+            "error": ["connection reset"],
+            "timestamp": "2025-01-01T12:00:00Z"
+        })
+    );
+}
+
+#[test]
+fn test_log_record_minimal_and_timestamp_now() {
+    let params = LoggingMessageParams::from(LogRecord::new(LoggingLevel::Info, "ready"));
+    assert_eq!(params.data, json!({ "message": "ready" }));
+
+    let record = LogRecord::new(LoggingLevel::Debug, "tick").timestamp_now();
+    let timestamp = record.timestamp.unwrap().to_string();
+    assert_eq!(timestamp.len(), "2025-01-01T12:00:00.000Z".len());
+    assert_eq!(&timestamp[10..11], "T");
+    assert!(timestamp.ends_with('Z'));
+    assert!(timestamp.as_str() > "2024");
+}
+
+#[test]
+fn test_log_level_filter() {
+    let mut emitted = Vec::new();
+    {
+        let mut filter = LogLevelFilter::new(LoggingLevel::Info, |params: LoggingMessageParams| {
+            emitted.push(params.level)
+        });
+        assert!(!filter.maybe_emit(LogRecord::new(LoggingLevel::Debug, "noise").into()));
+        assert!(filter.maybe_emit(LogRecord::new(LoggingLevel::Info, "hello").into()));
+
+        let params: SetLevelParams = serde_json::from_value(json!({ "level": "error" })).unwrap();
+        filter.set_level(&params);
+        assert_eq!(filter.level(), LoggingLevel::Error);
+        assert!(!filter.enabled(LoggingLevel::Warning));
+        assert!(filter.enabled(LoggingLevel::Critical));
+        assert!(!filter.maybe_emit(LogRecord::new(LoggingLevel::Warning, "careful").into()));
+        assert!(filter.maybe_emit(LogRecord::new(LoggingLevel::Alert, "fire").into()));
+    }
+    assert_eq!(emitted, vec![LoggingLevel::Info, LoggingLevel::Alert]);
+}
+
+#[cfg(feature = "otel")]
+#[test]
+fn test_otel_attributes() {
+    use mcp_schema::otel::{self, RequestTimings};
+    use opentelemetry::{KeyValue, Value as OtelValue};
+    use std::time::{Duration, Instant};
+
+    fn get<'a>(attributes: &'a [KeyValue], key: &str) -> Option<&'a OtelValue> {
+        attributes
+            .iter()
+            .find(|kv| kv.key.as_str() == key)
+            .map(|kv| &kv.value)
+    }
+
+    let request = JSONRPCRequest {
+        json_rpc: JsonRpcVersion,
+        method: "tools/call".into(),
+        id: RequestId::Number(7),
+        params: json!({ "name": "search", "arguments": {} }),
+    };
+    let attributes = otel::request_attributes(&request);
+    assert_eq!(
+        get(&attributes, "mcp.method.name"),
+        Some(&OtelValue::from("tools/call"))
+    );
+    assert_eq!(
+        get(&attributes, "gen_ai.tool.name"),
+        Some(&OtelValue::from("search"))
+    );
+    assert_eq!(
+        get(&attributes, "jsonrpc.request.id"),
+        Some(&OtelValue::from("7"))
+    );
+
+    let response = JSONRPCResponse {
+        json_rpc: JsonRpcVersion,
+        id: RequestId::Number(7),
+        result: json!({ "content": [], "isError": true }),
+    };
+    let attributes = otel::response_attributes(&response);
+    assert_eq!(
+        get(&attributes, "error.type"),
+        Some(&OtelValue::from("tool_error"))
+    );
+
+    let error = JSONRPCError {
+        json_rpc: JsonRpcVersion,
+        id: Some(RequestId::Number(8)),
+        error: RPCErrorDetail {
+            code: METHOD_NOT_FOUND,
+            message: "no such method".to_string(),
+            data: None,
+        },
+    };
+    let attributes = otel::error_attributes(&error);
+    assert_eq!(
+        get(&attributes, "rpc.jsonrpc.error_code"),
+        Some(&OtelValue::I64(-32601))
+    );
+
+    let event = otel::logging_event(&LoggingMessageParams::from(
+        LogRecord::new(LoggingLevel::Warning, "slow").logger("db"),
+    ));
+    assert_eq!(event.name, otel::LOG_EVENT_NAME);
+    assert_eq!(
+        get(&event.attributes, "mcp.log.level"),
+        Some(&OtelValue::from("warning"))
+    );
+    assert_eq!(
+        get(&event.attributes, "mcp.log.logger"),
+        Some(&OtelValue::from("db"))
+    );
+
+    let mut timings = RequestTimings::new();
+    let start = Instant::now();
+    timings.start_at(&RequestId::Number(7), "tools/call", start);
+    assert!(timings
+        .finish(&RequestId::String("7".to_string()))
+        .is_none());
+    let (method, duration) = timings
+        .finish_at(&RequestId::Number(7), start + Duration::from_millis(250))
+        .unwrap();
+    assert_eq!(method, "tools/call");
+    assert_eq!(duration, Duration::from_millis(250));
+    assert!(timings.is_empty());
+}
+
+#[test]
+fn test_log_throttle() {
+    let mut emitted = Vec::new();
+    {
+        let mut throttle =
+            LogThrottle::new(2, Duration::from_secs(1), |params: LoggingMessageParams| {
+                emitted.push(params.data["message"].as_str().unwrap().to_string())
+            })
+            .level_rate(LoggingLevel::Error, 10, Duration::from_millis(100));
+        let start = Instant::now();
+        let info =
+            |message: &str| LoggingMessageParams::from(LogRecord::new(LoggingLevel::Info, message));
+
+        assert!(throttle.maybe_emit_at(info("a"), start));
+        // Repeats are suppressed without using up tokens.
+        assert!(!throttle.maybe_emit_at(info("a"), start));
+        assert!(!throttle.maybe_emit_at(info("a"), start));
+        assert_eq!(throttle.suppressed(), 2);
+        assert!(throttle.maybe_emit_at(info("b"), start));
+        assert_eq!(throttle.suppressed(), 0);
+
+        // The info bucket is empty; errors have their own.
+        assert!(!throttle.maybe_emit_at(info("c"), start));
+        assert_eq!(throttle.dropped(), 1);
+        let error = LogRecord::new(LoggingLevel::Error, "boom").logger("db");
+        assert!(throttle.maybe_emit_at(error.clone().into(), start));
+        assert!(!throttle.maybe_emit_at(error.into(), start));
+
+        assert!(throttle.maybe_emit_at(info("c"), start + Duration::from_secs(1)));
+        assert!(!throttle.maybe_emit_at(info("c"), start + Duration::from_millis(1500)));
+        throttle.flush();
+        throttle.flush();
+    }
+    assert_eq!(
+        emitted,
+        vec![
+            "a",
+            "suppressed 2 similar messages",
+            "b",
+            "boom",
+            "suppressed 1 similar messages",
+            "c",
+            "suppressed 1 similar messages",
+        ]
+    );
+}
+
+#[test]
+fn test_log_throttle_repeat_window() {
+    let mut emitted = Vec::new();
+    {
+        let mut throttle =
+            LogThrottle::new(5, Duration::from_secs(1), |params: LoggingMessageParams| {
+                emitted.push(params.data["message"].as_str().unwrap().to_string())
+            });
+        let start = Instant::now();
+        let record = || LoggingMessageParams::from(LogRecord::new(LoggingLevel::Info, "a"));
+
+        assert!(throttle.maybe_emit_at(record(), start));
+        assert!(!throttle.maybe_emit_at(record(), start + Duration::from_millis(500)));
+        // Once the refill interval has passed the repeat goes out again.
+        assert!(throttle.maybe_emit_at(record(), start + Duration::from_secs(1)));
+        assert_eq!(throttle.suppressed(), 0);
+        assert!(!throttle.maybe_emit_at(record(), start + Duration::from_millis(1999)));
+        assert!(throttle.maybe_emit_at(record(), start + Duration::from_secs(3)));
+    }
+    assert_eq!(
+        emitted,
+        vec![
+            "a",
+            "suppressed 1 similar messages",
+            "a",
+            "suppressed 1 similar messages",
+            "a",
+        ]
+    );
+}
@@ -0,0 +1,115 @@
+use mcp_schema::meta_keys::{self, MetaKeyError};
+
+#[test]
+fn test_meta_key_grammar() {
+    assert!(meta_keys::validate_key(meta_keys::PROGRESS_TOKEN).is_ok());
+    assert!(meta_keys::validate_key("com.example/feature-flag").is_ok());
+    assert!(meta_keys::validate_key("com.example/").is_ok());
+    assert!(meta_keys::validate_key("plain_name.v2").is_ok());
+
+    assert!(matches!(
+        meta_keys::validate_key("1com.example/x"),
+        Err(MetaKeyError::InvalidPrefix(_))
+    ));
+    assert!(matches!(
+        meta_keys::validate_key("com..example/x"),
+        Err(MetaKeyError::InvalidPrefix(_))
+    ));
+    assert!(matches!(
+        meta_keys::validate_key("com.example/-x"),
+        Err(MetaKeyError::InvalidName(_))
+    ));
+}
+
+#[test]
+fn test_meta_key_reserved_prefixes() {
+    assert!(meta_keys::is_reserved("modelcontextprotocol.io/anything"));
+    assert!(meta_keys::is_reserved("mcp.dev/x"));
+    assert!(meta_keys::is_reserved("api.modelcontextprotocol.org/x"));
+    assert!(!meta_keys::is_reserved("com.mcp/x"));
+    assert!(!meta_keys::is_reserved(meta_keys::PROGRESS_TOKEN));
+
+    assert!(matches!(
+        meta_keys::validate_custom_key("tools.mcp.com/x"),
+        Err(MetaKeyError::Reserved(_))
+    ));
+    assert!(meta_keys::validate_custom_key("com.example/x").is_ok());
+}
+
+#[test]
+fn test_idempotency_meta() {
+    use mcp_schema::{CallToolParams, RequestMeta};
+
+    let meta = RequestMeta::new().with_idempotency_key("call-42");
+    assert_eq!(meta.idempotency_key(), Some("call-42"));
+    assert!(!meta.is_retry());
+
+    let retry = meta.next_attempt().next_attempt();
+    assert_eq!(retry.retry_count(), 2);
+    assert_eq!(retry.idempotency_key(), Some("call-42"));
+
+    let params: CallToolParams = serde_json::from_value(serde_json::json!({
+        "name": "charge",
+        "_meta": {
+            "progressToken": 1,
+            "io.github.yonaka15/idempotencyKey": "call-42",
+            "io.github.yonaka15/retryCount": 1
+        }
+    }))
+    .unwrap();
+    let meta = params.meta.unwrap();
+    assert!(meta.progress_token.is_some());
+    assert_eq!(meta.idempotency_key(), Some("call-42"));
+    assert!(meta.is_retry());
+    assert!(meta_keys::validate_custom_key(meta_keys::IDEMPOTENCY_KEY).is_ok());
+}
+
+#[test]
+fn test_trace_context_meta() {
+    use mcp_schema::{RequestMeta, TraceContext, TraceParent, TraceState};
+
+    let header = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+    let parent: TraceParent = header.parse().unwrap();
+    assert!(parent.is_sampled());
+    assert_eq!(parent.to_string(), header);
+
+    assert!("00-00000000000000000000000000000000-00f067aa0ba902b7-01"
+        .parse::<TraceParent>()
+        .is_err());
+    assert!("ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+        .parse::<TraceParent>()
+        .is_err());
+    assert!("00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01"
+        .parse::<TraceParent>()
+        .is_err());
+    assert!(
+        "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra"
+            .parse::<TraceParent>()
+            .is_ok()
+    );
+
+    let state: TraceState = "rojo=00f067aa0ba902b7, congo=t61rcWkgMzE,".parse().unwrap();
+    assert_eq!(state.get("congo"), Some("t61rcWkgMzE"));
+    assert_eq!(state.to_string(), "rojo=00f067aa0ba902b7,congo=t61rcWkgMzE");
+    let state = state.insert("congo", "updated").unwrap();
+    assert_eq!(state.to_string(), "congo=updated,rojo=00f067aa0ba902b7");
+    assert!("a=1,a=2".parse::<TraceState>().is_err());
+    assert!("1abc=x".parse::<TraceState>().is_err());
+    assert!("1abc@sys=x".parse::<TraceState>().is_ok());
+    assert!("abc@1sys=x".parse::<TraceState>().is_err());
+
+    let meta = RequestMeta::new()
+        .with_trace_context(TraceContext::new(parent).with_tracestate(state.clone()));
+    let json = serde_json::to_value(&meta).unwrap();
+    assert_eq!(json["traceparent"], header);
+    assert_eq!(json["tracestate"], "congo=updated,rojo=00f067aa0ba902b7");
+
+    let meta: RequestMeta = serde_json::from_value(serde_json::json!({
+        "traceparent": header,
+        "tracestate": "not a valid member"
+    }))
+    .unwrap();
+    let context = meta.trace_context().unwrap();
+    assert_eq!(context.traceparent, parent);
+    assert!(context.tracestate.is_none());
+}
@@ -0,0 +1,135 @@
+use mcp_schema::*;
+use serde_json::{json, Map, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+struct Echo;
+
+impl McpServerHandler for Echo {
+    fn call_tool(&self, params: CallToolParams) -> Result<CallToolResult, RPCErrorDetail> {
+        Ok(CallToolResult::text(params.name))
+    }
+}
+
+fn capabilities(value: Value) -> ServerCapabilities {
+    serde_json::from_value(value).unwrap()
+}
+
+fn call(text: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": { "name": text }
+    })
+}
+
+#[test]
+fn test_layers_compose_around_server_handler() {
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let log = seen.clone();
+    let mut meta = Map::new();
+    meta.insert("example.com/server".into(), json!("demo"));
+
+    let service = ServerService(Echo);
+    let service = InjectMeta(meta).layer(service);
+    let service = EnforceCapabilities::new(capabilities(json!({ "tools": {} }))).layer(service);
+    let mut service =
+        Logging(move |direction, _: &Value| log.borrow_mut().push(direction)).layer(service);
+
+    let reply = service.handle(call("echo")).unwrap();
+    assert_eq!(reply["result"]["content"][0]["text"], "echo");
+    assert_eq!(reply["result"]["_meta"]["example.com/server"], "demo");
+    assert_eq!(*seen.borrow(), [Direction::Incoming, Direction::Outgoing]);
+
+    let reply = service
+        .handle(json!({ "jsonrpc": "2.0", "id": 2, "method": "prompts/list" }))
+        .unwrap();
+    assert_eq!(reply["error"]["code"], METHOD_NOT_FOUND);
+    assert_eq!(reply["id"], 2);
+
+    let notification = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+    assert!(service.handle(notification).is_none());
+}
+
+#[test]
+fn test_enforce_limits() {
+    let limits = DecodeLimits {
+        max_text_chars: Some(4),
+        ..Default::default()
+    };
+    let mut service = EnforceLimits(limits).layer(|message: Value| Some(message));
+
+    let message = json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "method": "tools/call",
+        "params": { "name": "x", "arguments": { "content": { "type": "text", "text": "too long" } } }
+    });
+    let reply = service.handle(message).unwrap();
+    assert_eq!(reply["error"]["code"], INVALID_REQUEST);
+
+    let reply = service.handle(call("ok")).unwrap();
+    assert_eq!(reply["method"], "tools/call");
+}
+
+#[test]
+fn test_enforce_capabilities_subscribe_flag() {
+    let enforce = EnforceCapabilities::new(capabilities(json!({ "resources": {} })));
+    assert!(enforce.allows("resources/read"));
+    assert!(!enforce.allows("resources/subscribe"));
+    assert!(enforce.allows("ping"));
+    assert!(!enforce.allows("logging/setLevel"));
+}
+
+#[test]
+fn test_enforce_capabilities_completions() {
+    let enforce = EnforceCapabilities::new(capabilities(json!({ "prompts": {} })))
+        .with_version(ProtocolVersion::V2025_03_26);
+    assert!(!enforce.allows("completion/complete"));
+    let enforce = EnforceCapabilities::new(capabilities(json!({ "completions": {} })))
+        .with_version(ProtocolVersion::V2025_06_18);
+    assert!(enforce.allows("completion/complete"));
+
+    // 2024-11-05 has no `completions` capability to advertise.
+    let enforce = EnforceCapabilities::new(capabilities(json!({ "prompts": {} })));
+    assert!(enforce.allows("completion/complete"));
+    let enforce = enforce.with_version(ProtocolVersion::V2024_11_05);
+    assert!(enforce.allows("completion/complete"));
+}
+
+#[test]
+fn test_server_service_error_codes() {
+    let mut service = ServerService(Echo);
+    let reply = service
+        .handle(json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/nope" }))
+        .unwrap();
+    assert_eq!(reply["error"]["code"], METHOD_NOT_FOUND);
+
+    let reply = service
+        .handle(json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "sampling/createMessage",
+            "params": { "messages": [], "maxTokens": 1 }
+        }))
+        .unwrap();
+    assert_eq!(reply["error"]["code"], METHOD_NOT_FOUND);
+
+    let reply = service
+        .handle(json!({ "jsonrpc": "2.0", "id": 3, "method": "tools/call", "params": {} }))
+        .unwrap();
+    assert_eq!(reply["error"]["code"], INVALID_PARAMS);
+    assert_eq!(reply["id"], 3);
+
+    let reply = service
+        .handle(json!({
+            "jsonrpc": "1.0",
+            "id": 4,
+            "method": "tools/call",
+            "params": { "name": "echo" }
+        }))
+        .unwrap();
+    assert_eq!(reply["error"]["code"], INVALID_REQUEST);
+    assert_eq!(reply["id"], 4);
+}
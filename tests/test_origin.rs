@@ -0,0 +1,71 @@
+use mcp_schema::*;
+
+#[test]
+fn test_localhost_policy() {
+    let policy = OriginPolicy::localhost();
+    assert!(policy.check(None, Some("localhost:8080")).is_ok());
+    assert!(policy
+        .check(Some("http://127.0.0.1:3000"), Some("127.0.0.1:8080"))
+        .is_ok());
+    assert!(policy
+        .check(Some("http://[::1]:3000"), Some("[::1]:8080"))
+        .is_ok());
+
+    // DNS rebinding: same-origin request to an attacker-controlled name.
+    assert_eq!(
+        policy.check(Some("http://evil.example:8080"), Some("evil.example:8080")),
+        Err(OriginRejection::OriginNotAllowed(
+            "http://evil.example:8080".to_string()
+        ))
+    );
+    assert_eq!(
+        policy.check(None, Some("evil.example:8080")),
+        Err(OriginRejection::HostNotAllowed(
+            "evil.example:8080".to_string()
+        ))
+    );
+    // Names that only look like loopback addresses.
+    for host in ["127.a.evil.com", "127.0.0.1.nip.io"] {
+        assert_eq!(
+            policy.check(None, Some(host)),
+            Err(OriginRejection::HostNotAllowed(host.to_string()))
+        );
+        let origin = format!("http://{}", host);
+        assert_eq!(
+            policy.check(Some(&origin), Some("localhost")),
+            Err(OriginRejection::OriginNotAllowed(origin.clone()))
+        );
+    }
+    assert!(policy.check(None, Some("127.1.2.3:80")).is_ok());
+    assert_eq!(policy.check(None, None), Err(OriginRejection::MissingHost));
+    assert_eq!(OriginRejection::MissingHost.http_status(), 400);
+
+    assert!(matches!(
+        policy.check(Some("null"), Some("localhost")),
+        Err(OriginRejection::OriginNotAllowed(_))
+    ));
+    assert!(matches!(
+        policy.check(Some("not an origin"), Some("localhost")),
+        Err(OriginRejection::MalformedOrigin(_))
+    ));
+}
+
+#[test]
+fn test_allowed_origins_and_hosts() {
+    let policy = OriginPolicy::new()
+        .allow_origin("https://app.example.com")
+        .allow_host("mcp.example.com");
+    assert!(policy.is_restrictive());
+    assert!(policy
+        .check(Some("https://APP.example.com"), Some("mcp.example.com:443"))
+        .is_ok());
+    let rejection = policy
+        .check(Some("https://other.example"), Some("mcp.example.com"))
+        .unwrap_err();
+    assert_eq!(rejection.http_status(), 403);
+
+    assert!(!OriginPolicy::new().is_restrictive());
+    assert!(OriginPolicy::new()
+        .check(Some("https://anything.example"), None)
+        .is_ok());
+}
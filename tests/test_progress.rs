@@ -0,0 +1,108 @@
+use mcp_schema::*;
+use serde_json::json;
+
+fn progress(token: serde_json::Value) -> ProgressNotificationParams {
+    serde_json::from_value(json!({ "progressToken": token, "progress": 0.5 })).unwrap()
+}
+
+#[test]
+fn test_progress_router_routes_by_token() {
+    let request: ClientRequest = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "id": 10,
+        "method": "tools/call",
+        "params": { "name": "index", "_meta": { "progressToken": "tok-1" } }
+    }))
+    .unwrap();
+
+    let mut router = ProgressRouter::new();
+    assert!(router.track_request(&request).is_some());
+    assert!(matches!(
+        router.route(&progress(json!("tok-1"))),
+        ProgressRoute::Active(RequestId::Number(10))
+    ));
+    assert!(matches!(
+        router.route(&progress(json!("other"))),
+        ProgressRoute::Unknown
+    ));
+
+    router.cancel(&RequestId::Number(10));
+    assert!(matches!(
+        router.route(&progress(json!("tok-1"))),
+        ProgressRoute::Cancelled(_)
+    ));
+
+    router.complete(&RequestId::Number(10));
+    assert!(router.is_empty());
+}
+
+#[test]
+fn test_progress_router_ignores_requests_without_token() {
+    let request: ClientRequest = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/list"
+    }))
+    .unwrap();
+    let mut router = ProgressRouter::new();
+    assert!(router.track_request(&request).is_none());
+    assert!(router.is_empty());
+}
+
+#[test]
+fn test_progress_message() {
+    let params: ProgressNotificationParams = serde_json::from_value(json!({
+        "progressToken": 1,
+        "progress": 2.0,
+        "total": 4.0,
+        "message": "indexing"
+    }))
+    .unwrap();
+    assert_eq!(params.message.as_deref(), Some("indexing"));
+    assert!(params.extra.is_empty());
+    assert!(progress(json!(1)).message.is_none());
+}
+
+#[test]
+fn test_progress_router_token_reuse() {
+    let token = ProgressToken::from("tok");
+    let mut router = ProgressRouter::new();
+    router.track(RequestId::Number(1), &token);
+    router.track(RequestId::Number(2), &token);
+    assert_eq!(router.len(), 1);
+
+    router.cancel(&RequestId::Number(1));
+    router.complete(&RequestId::Number(1));
+    assert!(matches!(
+        router.route(&progress(json!("tok"))),
+        ProgressRoute::Active(RequestId::Number(2))
+    ));
+
+    router.complete(&RequestId::Number(2));
+    assert!(router.is_empty());
+}
+
+#[test]
+fn test_progress_router_expires_cancelled_requests() {
+    let mut router = ProgressRouter::new().with_cancelled_limit(2);
+    for id in 1..=3 {
+        router.track(RequestId::Number(id), &ProgressToken::Number(id));
+        router.cancel(&RequestId::Number(id));
+    }
+    assert_eq!(router.len(), 2);
+    assert!(matches!(
+        router.route(&progress(json!(1))),
+        ProgressRoute::Unknown
+    ));
+    assert!(matches!(
+        router.route(&progress(json!(3))),
+        ProgressRoute::Cancelled(RequestId::Number(3))
+    ));
+
+    router.forget(&RequestId::Number(3));
+    assert!(matches!(
+        router.route(&progress(json!(3))),
+        ProgressRoute::Unknown
+    ));
+    assert_eq!(router.len(), 1);
+}
@@ -0,0 +1,264 @@
+use mcp_schema::*;
+use serde_json::json;
+use std::collections::HashMap;
+
+fn args(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_interpolate() {
+    let values = args(&[("lang", "Rust"), ("topic", "lifetimes")]);
+    assert_eq!(
+        interpolate("Explain {{topic}} in {{ lang }}.", &values).unwrap(),
+        "Explain lifetimes in Rust."
+    );
+    assert_eq!(
+        interpolate("keep {{ open", &values).unwrap(),
+        "keep {{ open"
+    );
+    assert_eq!(
+        interpolate("{{missing}}", &values).unwrap_err(),
+        MissingArg {
+            name: "missing".into()
+        }
+    );
+}
+
+#[test]
+fn test_prompt_render() {
+    let prompt: Prompt = serde_json::from_value(json!({
+        "name": "review",
+        "description": "Code review",
+        "arguments": [
+            { "name": "code", "required": true },
+            { "name": "focus" }
+        ]
+    }))
+    .unwrap();
+    let template: Vec<PromptMessage> = serde_json::from_value(json!([
+        { "role": "user", "content": { "type": "text", "text": "Review this:\n{{code}}" } }
+    ]))
+    .unwrap();
+
+    let result = prompt
+        .render(&template, &args(&[("code", "fn main() {}")]))
+        .unwrap();
+    assert_eq!(result.description.as_deref(), Some("Code review"));
+    match &result.messages[0].content {
+        PromptContent::Text(text) => assert_eq!(text.text, "Review this:\nfn main() {}"),
+        other => panic!("unexpected content: {:?}", other),
+    }
+
+    let err = prompt.render(&template, &args(&[])).unwrap_err();
+    assert_eq!(err.name, "code");
+}
+
+fn prompt(name: &str) -> Prompt {
+    serde_json::from_value(json!({ "name": name })).unwrap()
+}
+
+#[test]
+fn test_prompt_library_pagination() {
+    let mut library = PromptLibrary::new().with_page_size(2);
+    for name in ["delta", "alpha", "charlie", "bravo", "echo"] {
+        library.register(prompt(name));
+    }
+    assert_eq!(library.len(), 5);
+    assert!(library.get("charlie").is_some());
+
+    let first = library.list(None);
+    let names: Vec<_> = first.items.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, vec!["alpha", "bravo"]);
+    assert_eq!(first.next_cursor.as_deref(), Some("bravo"));
+
+    // Removing an already-listed prompt does not disturb the next page.
+    library.remove("alpha");
+    let second = library.list(first.next_cursor.as_deref());
+    let names: Vec<_> = second.items.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, vec!["charlie", "delta"]);
+
+    let third = library.list(second.next_cursor.as_deref());
+    assert_eq!(third.items.len(), 1);
+    assert!(third.next_cursor.is_none());
+}
+
+#[test]
+fn test_prompt_library_diff() {
+    let mut library = PromptLibrary::new();
+    library.register(prompt("keep"));
+    library.register(prompt("drop"));
+    library.register(prompt("edit"));
+    let snapshot = library.clone();
+    assert!(library.diff(&snapshot).is_empty());
+
+    library.remove("drop");
+    library.register(prompt("new"));
+    let mut edited = prompt("edit");
+    edited.description = Some("now with a description".into());
+    library.register(edited);
+
+    let diff = library.diff(&snapshot);
+    assert_eq!(diff.added, vec!["new"]);
+    assert_eq!(diff.removed, vec!["drop"]);
+    assert_eq!(diff.changed, vec!["edit"]);
+}
+
+#[test]
+fn test_render_markdown() {
+    let content: Vec<PromptContent> = serde_json::from_value(json!([
+        { "type": "text", "text": "Here is the file:" },
+        {
+            "type": "resource",
+            "resource": {
+                "uri": "file:///src/main.rs",
+                "mimeType": "text/x-rust",
+                "text": "fn main() {}\n"
+            }
+        },
+        { "type": "image", "data": "iVBORw0KGgo=", "mimeType": "image/png" },
+        {
+            "type": "resource",
+            "resource": { "uri": "file:///logo.bin", "blob": "AAECAw==" }
+        }
+    ]))
+    .unwrap();
+
+    assert_eq!(
+        render_markdown(&content),
+        "Here is the file:\n\n\
+         `file:///src/main.rs`\n\n```rust\nfn main() {}\n```\n\n\
+         ![image](data:image/png;base64,iVBORw0KGgo=)\n\n\
+         `file:///logo.bin`\n\n*4 bytes of binary data*"
+    );
+
+    let options = MarkdownOptions {
+        inline_images: false,
+        ..MarkdownOptions::default()
+    };
+    assert_eq!(
+        render_markdown_with(&content[2..3], &options),
+        "![image (image/png)]()"
+    );
+
+    let fenced: Vec<PromptContent> = serde_json::from_value(json!([{
+        "type": "resource",
+        "resource": { "uri": "file:///README.md", "mimeType": "text/markdown", "text": "```sh\nls\n```" }
+    }]))
+    .unwrap();
+    assert_eq!(
+        render_markdown(&fenced),
+        "`file:///README.md`\n\n````markdown\n```sh\nls\n```\n````"
+    );
+}
+
+#[test]
+fn test_content_is_tagged_by_type() {
+    let content: PromptContent = serde_json::from_value(json!({
+        "type": "image",
+        "data": "aGk=",
+        "mimeType": "image/png",
+        "annotations": { "priority": 1.0 }
+    }))
+    .unwrap();
+    let PromptContent::Image(image) = &content else {
+        panic!("expected an image, got {:?}", content);
+    };
+    assert!(image.annotated.extra.is_empty());
+    assert_eq!(
+        serde_json::to_value(&content).unwrap(),
+        json!({
+            "type": "image",
+            "data": "aGk=",
+            "mimeType": "image/png",
+            "annotations": { "priority": 1.0 }
+        })
+    );
+
+    // A wrong or missing discriminator is an error, not another variant.
+    assert!(
+        serde_json::from_value::<PromptContent>(json!({ "type": "image", "text": "hi" })).is_err()
+    );
+    assert!(serde_json::from_value::<PromptContent>(json!({ "text": "hi" })).is_err());
+    assert!(
+        serde_json::from_value::<SamplingContent>(json!({ "type": "resource", "text": "hi" }))
+            .is_err()
+    );
+
+    let embedded = PromptContent::Resource(EmbeddedResource::new(ResourceContents::Text(
+        TextResourceContents {
+            meta: None,
+            uri: "file:///a.txt".into(),
+            mime_type: None,
+            text: "a".into(),
+        },
+    )));
+    assert_eq!(
+        serde_json::to_value(&embedded).unwrap(),
+        json!({ "type": "resource", "resource": { "uri": "file:///a.txt", "text": "a" } })
+    );
+
+    let err =
+        serde_json::from_value::<ResourceContents>(json!({ "uri": "file:///a" })).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "resource contents need a \"text\" or \"blob\" member"
+    );
+    assert!(serde_json::from_value::<ResourceContents>(
+        json!({ "uri": "file:///a", "text": "", "blob": "" })
+    )
+    .is_err());
+}
+
+#[test]
+fn test_audio_content() {
+    let message: PromptMessage = serde_json::from_value(json!({
+        "role": "user",
+        "content": { "type": "audio", "data": "UklGRg==", "mimeType": "audio/wav" }
+    }))
+    .unwrap();
+    let PromptContent::Audio(audio) = &message.content else {
+        panic!("expected audio, got {:?}", message.content);
+    };
+    assert_eq!(audio.mime_type, "audio/wav");
+    assert_eq!(
+        render_markdown(std::slice::from_ref(&message.content)),
+        "*audio (audio/wav)*"
+    );
+
+    let sampling = SamplingMessage::try_from(message).unwrap();
+    assert!(matches!(sampling.contents()[0], SamplingContent::Audio(_)));
+    assert_eq!(
+        serde_json::to_value(&sampling).unwrap()["content"]["type"],
+        "audio"
+    );
+}
+
+#[test]
+fn test_resource_link_content() {
+    let result: CallToolResult = serde_json::from_value(json!({
+        "content": [{
+            "type": "resource_link",
+            "uri": "file:///data/big.csv",
+            "name": "big.csv",
+            "title": "Quarterly numbers",
+            "size": 1048576
+        }]
+    }))
+    .unwrap();
+    let PromptContent::ResourceLink(link) = &result.content[0] else {
+        panic!("expected a resource link, got {:?}", result.content[0]);
+    };
+    assert_eq!(link.resource.size, Some(1048576));
+    assert_eq!(
+        render_markdown(&result.content),
+        "[Quarterly numbers](file:///data/big.csv)"
+    );
+    assert!(matches!(
+        SamplingContent::try_from(result.content[0].clone()),
+        Err(MessageConversionError::ResourceLink(_))
+    ));
+}
@@ -0,0 +1,137 @@
+//! Checks that every definition in the bundled official schema has a type
+//! exported from the crate root. The table is type-checked, so a type that
+//! stops being exported fails to compile.
+
+use mcp_schema::*;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+const SCHEMA: &str = include_str!("../schema/2025-06-18/schema.json");
+
+macro_rules! surface {
+    ($($definition:literal => $ty:ty,)*) => {
+        /// Schema definitions and the types or traits modelling them: the
+        /// `params` for requests and notifications, the type itself
+        /// otherwise.
+        const SURFACE: &[(&str, fn() -> &'static str)] = &[
+            $(($definition, std::any::type_name::<$ty>),)*
+        ];
+    };
+}
+
+surface! {
+    "Annotations" => Annotations,
+    "AudioContent" => AudioContent,
+    "BaseMetadata" => dyn BaseMetadata,
+    "BlobResourceContents" => BlobResourceContents,
+    "BooleanSchema" => BooleanSchema,
+    "CallToolRequest" => CallToolParams,
+    "CallToolResult" => CallToolResult,
+    "CancelledNotification" => CancelledNotificationParams,
+    "ClientCapabilities" => ClientCapabilities,
+    "ClientNotification" => ClientNotification,
+    "ClientRequest" => ClientRequest,
+    "ClientResult" => ClientResult,
+    "CompleteRequest" => CompleteParams,
+    "CompleteResult" => CompleteResult,
+    "ContentBlock" => PromptContent,
+    "CreateMessageRequest" => CreateMessageParams,
+    "CreateMessageResult" => CreateMessageResult,
+    "Cursor" => Cursor,
+    "ElicitRequest" => ElicitationCreateParams,
+    "ElicitResult" => ElicitationCreateResult,
+    "EmbeddedResource" => EmbeddedResource,
+    "EmptyResult" => EmptyResult,
+    "EnumSchema" => EnumSchema,
+    "GetPromptRequest" => GetPromptParams,
+    "GetPromptResult" => GetPromptResult,
+    "ImageContent" => ImageContent,
+    "Implementation" => Implementation,
+    "InitializeRequest" => InitializeParams,
+    "InitializeResult" => InitializeResult,
+    "InitializedNotification" => MCPNotificationParams,
+    "JSONRPCError" => JSONRPCError,
+    "JSONRPCMessage" => JSONRPCMessage,
+    "JSONRPCNotification" => JSONRPCNotification<Value>,
+    "JSONRPCRequest" => JSONRPCRequest<Value>,
+    "JSONRPCResponse" => JSONRPCResponse<Value>,
+    "ListPromptsRequest" => ListPromptsParams,
+    "ListPromptsResult" => ListPromptsResult,
+    "ListResourceTemplatesRequest" => ListResourceTemplatesParams,
+    "ListResourceTemplatesResult" => ListResourceTemplatesResult,
+    "ListResourcesRequest" => ListResourcesParams,
+    "ListResourcesResult" => ListResourcesResult,
+    "ListRootsRequest" => ListRootsParams,
+    "ListRootsResult" => ListRootsResult,
+    "ListToolsRequest" => ListToolsParams,
+    "ListToolsResult" => ListToolsResult,
+    "LoggingLevel" => LoggingLevel,
+    "LoggingMessageNotification" => LoggingMessageParams,
+    "ModelHint" => ModelHint,
+    "ModelPreferences" => ModelPreferences,
+    "Notification" => MCPNotificationParams,
+    "NumberSchema" => NumberSchema,
+    "PaginatedRequest" => PaginatedParams,
+    "PaginatedResult" => PaginatedResult,
+    "PingRequest" => PingParams,
+    "PrimitiveSchemaDefinition" => PrimitiveSchemaDefinition,
+    "ProgressNotification" => ProgressNotificationParams,
+    "ProgressToken" => ProgressToken,
+    "Prompt" => Prompt,
+    "PromptArgument" => PromptArgument,
+    "PromptListChangedNotification" => MCPNotificationParams,
+    "PromptMessage" => PromptMessage,
+    "PromptReference" => ReferenceType,
+    "ReadResourceRequest" => ReadResourceParams,
+    "ReadResourceResult" => ReadResourceResult,
+    "Request" => MCPRequestParams,
+    "RequestId" => RequestId,
+    "Resource" => Resource,
+    "ResourceContents" => ResourceContents,
+    "ResourceLink" => ResourceLink,
+    "ResourceListChangedNotification" => MCPNotificationParams,
+    "ResourceTemplate" => ResourceTemplate,
+    "ResourceTemplateReference" => ReferenceType,
+    "ResourceUpdatedNotification" => ResourceUpdatedParams,
+    "Result" => MCPResultBase,
+    "Role" => Role,
+    "Root" => Root,
+    "RootsListChangedNotification" => MCPNotificationParams,
+    "SamplingMessage" => SamplingMessage,
+    "ServerCapabilities" => ServerCapabilities,
+    "ServerNotification" => ServerNotification,
+    "ServerRequest" => ServerRequest,
+    "ServerResult" => ServerResult,
+    "SetLevelRequest" => SetLevelParams,
+    "StringSchema" => StringSchema,
+    "SubscribeRequest" => SubscribeParams,
+    "TextContent" => TextContent,
+    "TextResourceContents" => TextResourceContents,
+    "Tool" => Tool,
+    "ToolAnnotations" => ToolAnnotations,
+    "ToolListChangedNotification" => MCPNotificationParams,
+    "UnsubscribeRequest" => UnsubscribeParams,
+}
+
+#[test]
+fn test_reexports_cover_schema() {
+    let schema: Value = serde_json::from_str(SCHEMA).unwrap();
+    let definitions: BTreeSet<&str> = schema["definitions"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    let covered: BTreeSet<&str> = SURFACE.iter().map(|(definition, _)| *definition).collect();
+    assert_eq!(covered, definitions);
+    // `Cursor` is an alias of `String`.
+    for (definition, type_name) in SURFACE.iter().filter(|(d, _)| *d != "Cursor") {
+        assert!(
+            type_name()
+                .trim_start_matches("dyn ")
+                .starts_with("mcp_schema::"),
+            "{} is modelled outside the crate",
+            definition
+        );
+    }
+}
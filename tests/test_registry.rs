@@ -0,0 +1,81 @@
+use mcp_schema::registry::*;
+use serde_json::json;
+
+fn weather() -> serde_json::Value {
+    json!({
+        "$schema": "https://static.modelcontextprotocol.io/schemas/2025-09-29/server.schema.json",
+        "name": "io.github.alice/weather",
+        "description": "Forecasts for any city",
+        "version": "1.0.2",
+        "repository": { "url": "https://github.com/alice/weather", "source": "github" },
+        "packages": [{
+            "registryType": "npm",
+            "identifier": "@alice/weather-mcp",
+            "version": "1.0.2",
+            "runtimeHint": "npx",
+            "transport": { "type": "stdio" },
+            "packageArguments": [
+                { "type": "named", "name": "--units", "default": "metric", "choices": ["metric", "imperial"] },
+                { "type": "positional", "valueHint": "city", "isRequired": true }
+            ],
+            "environmentVariables": [
+                { "name": "WEATHER_API_KEY", "isRequired": true, "isSecret": true }
+            ]
+        }],
+        "remotes": [{
+            "type": "streamable-http",
+            "url": "https://weather.example/mcp",
+            "headers": [{ "name": "Authorization", "isSecret": true }]
+        }],
+        "_meta": {
+            "io.modelcontextprotocol.registry/publisher-provided": { "tool": "ci" }
+        }
+    })
+}
+
+#[test]
+fn test_server_json_roundtrip() {
+    let server: ServerJson = serde_json::from_value(weather()).unwrap();
+    assert!(server.validate().is_ok());
+    assert_eq!(server.namespace(), Some("io.github.alice"));
+    assert_eq!(server.publisher_meta(), Some(&json!({ "tool": "ci" })));
+    let package = &server.packages[0];
+    assert!(matches!(
+        &package.package_arguments[0],
+        Argument::Named { name, input, .. } if name == "--units" && input.choices.len() == 2
+    ));
+    assert!(package.environment_variables[0].input.is_secret);
+    assert_eq!(serde_json::to_value(&server).unwrap(), weather());
+}
+
+#[test]
+fn test_server_json_validation() {
+    let mut server: ServerJson = serde_json::from_value(weather()).unwrap();
+    server.packages[0].version = "^1.0.0".to_string();
+    assert!(matches!(
+        server.validate(),
+        Err(RegistryError::VersionRange {
+            field: "packages[].version",
+            ..
+        })
+    ));
+
+    let mut server: ServerJson = serde_json::from_value(weather()).unwrap();
+    server.name = "weather".to_string();
+    assert!(matches!(
+        server.validate(),
+        Err(RegistryError::InvalidName(_))
+    ));
+
+    let mut server: ServerJson = serde_json::from_value(weather()).unwrap();
+    server.remotes[0].url = None;
+    assert!(matches!(
+        server.validate(),
+        Err(RegistryError::InvalidTransport(_))
+    ));
+
+    assert!(is_version_range("1.x"));
+    assert!(is_version_range(">=1.0"));
+    assert!(is_version_range("latest"));
+    assert!(!is_version_range("1.0.0-beta.1"));
+}
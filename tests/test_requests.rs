@@ -0,0 +1,464 @@
+use mcp_schema::*;
+use serde_json::json;
+
+#[test]
+fn test_client_request_display() {
+    let request: ClientRequest = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": "tools/call",
+        "id": 7,
+        "params": {
+            "name": "get_weather",
+            "arguments": { "city": "Tokyo" }
+        }
+    }))
+    .unwrap();
+    assert_eq!(request.to_string(), "tools/call #7 name=get_weather");
+
+    let request: ClientRequest = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": "resources/read",
+        "id": "req-1",
+        "params": { "uri": "file:///tmp/a.txt" }
+    }))
+    .unwrap();
+    assert_eq!(
+        request.to_string(),
+        "resources/read #req-1 uri=file:///tmp/a.txt"
+    );
+
+    let request: ClientRequest = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": "tools/list",
+        "id": 2,
+        "params": { "cursor": "abc" }
+    }))
+    .unwrap();
+    assert_eq!(request.to_string(), "tools/list #2 cursor=abc");
+}
+
+#[test]
+fn test_server_request_display() {
+    let request: ServerRequest = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": "sampling/createMessage",
+        "id": 3,
+        "params": {
+            "messages": [
+                { "role": "user", "content": { "type": "text", "text": "hi" } }
+            ],
+            "maxTokens": 100
+        }
+    }))
+    .unwrap();
+    assert_eq!(
+        request.to_string(),
+        "sampling/createMessage #3 messages=1 max_tokens=100"
+    );
+
+    let request: ServerRequest = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": "roots/list",
+        "id": 4
+    }))
+    .unwrap();
+    assert_eq!(request.to_string(), "roots/list #4");
+}
+
+#[test]
+fn test_resource_template_reference() {
+    let resource = json!({ "type": "ref/resource", "uri": "file:///{path}" });
+    let reference: ReferenceType = serde_json::from_value(resource.clone()).unwrap();
+    assert!(matches!(reference, ReferenceType::Resource { .. }));
+    assert_eq!(reference.uri(), Some("file:///{path}"));
+    assert_eq!(reference.to_string(), "resource:file:///{path}");
+    assert_eq!(serde_json::to_value(&reference).unwrap(), resource);
+    let legacy = ReferenceType::resource("file:///{path}");
+    assert_eq!(serde_json::to_value(&legacy).unwrap(), resource);
+
+    let prompt = json!({ "type": "ref/prompt", "name": "greeting" });
+    let reference: ReferenceType = serde_json::from_value(prompt.clone()).unwrap();
+    assert_eq!(reference.prompt_name(), Some("greeting"));
+    assert_eq!(serde_json::to_value(&reference).unwrap(), prompt);
+    assert_eq!(
+        serde_json::to_value(ReferenceType::prompt("greeting")).unwrap(),
+        prompt
+    );
+}
+
+#[test]
+fn test_optional_params_may_be_omitted_or_null() {
+    let client_methods = [
+        "ping",
+        "prompts/list",
+        "resources/list",
+        "resources/templates/list",
+        "tools/list",
+    ];
+    for method in client_methods {
+        for params in [None, Some(serde_json::Value::Null)] {
+            let mut message = json!({ "jsonrpc": "2.0", "id": 1, "method": method });
+            if let Some(params) = params {
+                message["params"] = params;
+            }
+            let result = serde_json::from_value::<ClientRequest>(message.clone());
+            assert!(result.is_ok(), "{}: {:?}", message, result.err());
+        }
+    }
+
+    for method in ["ping", "roots/list"] {
+        for message in [
+            json!({ "jsonrpc": "2.0", "id": 1, "method": method }),
+            json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": null }),
+        ] {
+            let result = serde_json::from_value::<ServerRequest>(message.clone());
+            assert!(result.is_ok(), "{}: {:?}", message, result.err());
+        }
+    }
+}
+
+#[test]
+fn test_optional_notification_params_may_be_omitted_or_null() {
+    for method in [
+        "notifications/initialized",
+        "notifications/roots/list_changed",
+    ] {
+        for message in [
+            json!({ "jsonrpc": "2.0", "method": method }),
+            json!({ "jsonrpc": "2.0", "method": method, "params": null }),
+        ] {
+            let result = serde_json::from_value::<ClientNotification>(message.clone());
+            assert!(result.is_ok(), "{}: {:?}", message, result.err());
+        }
+    }
+
+    for method in [
+        "notifications/resources/list_changed",
+        "notifications/tools/list_changed",
+        "notifications/prompts/list_changed",
+    ] {
+        for message in [
+            json!({ "jsonrpc": "2.0", "method": method }),
+            json!({ "jsonrpc": "2.0", "method": method, "params": null }),
+        ] {
+            let result = serde_json::from_value::<ServerNotification>(message.clone());
+            assert!(result.is_ok(), "{}: {:?}", message, result.err());
+        }
+    }
+}
+
+#[test]
+fn test_required_params_are_still_required() {
+    for method in ["initialize", "tools/call", "resources/read", "prompts/get"] {
+        let message = json!({ "jsonrpc": "2.0", "id": 1, "method": method });
+        assert!(
+            serde_json::from_value::<ClientRequest>(message).is_err(),
+            "{} accepted without params",
+            method
+        );
+    }
+}
+
+#[test]
+fn test_paginated_params_constructors() {
+    assert_eq!(
+        serde_json::to_value(PaginatedParams::first_page()).unwrap(),
+        json!({})
+    );
+    assert_eq!(
+        serde_json::to_value(PaginatedParams::from_cursor("page-2").with_progress_token(7))
+            .unwrap(),
+        json!({ "cursor": "page-2", "_meta": { "progressToken": 7 } })
+    );
+}
+
+#[test]
+fn test_client_request_builder() {
+    let request = ClientRequest::call_tool(7, "get_weather")
+        .arg("city", "Tokyo")
+        .unwrap()
+        .arg("days", 3)
+        .unwrap()
+        .progress_token("p1")
+        .build();
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        json!({
+            "jsonrpc": "2.0",
+            "method": "tools/call",
+            "id": 7,
+            "params": {
+                "_meta": { "progressToken": "p1" },
+                "name": "get_weather",
+                "arguments": { "city": "Tokyo", "days": 3 }
+            }
+        })
+    );
+
+    let request = ClientRequest::list_tools("req-2").cursor("abc").build();
+    assert_eq!(request.to_string(), "tools/list #req-2 cursor=abc");
+
+    let ids = RequestIdGenerator::new();
+    let request = ClientRequest::get_prompt(ids.next_id(), "greet")
+        .argument("name", "Ada")
+        .build();
+    let value = serde_json::to_value(&request).unwrap();
+    assert_eq!(value["id"], json!(1));
+    assert_eq!(value["params"]["arguments"], json!({ "name": "Ada" }));
+    let decoded: ClientRequest = serde_json::from_value(value).unwrap();
+    assert!(matches!(decoded, ClientRequest::GetPrompt { .. }));
+
+    let client_info = Implementation {
+        name: "client".into(),
+        title: None,
+        version: "1.0".into(),
+        extra: Default::default(),
+    };
+    let request = ClientRequest::initialize(1, client_info).build();
+    assert_eq!(
+        request.to_string(),
+        format!(
+            "initialize #1 client=client/1.0 protocol={}",
+            LATEST_PROTOCOL_VERSION
+        )
+    );
+
+    let request = JSONRPCRequest::builder(9, "custom/method", json!({ "x": 1 })).build();
+    assert_eq!(request.json_rpc, JSONRPC_VERSION);
+    assert_eq!(request.method, "custom/method");
+
+    let mut map = std::collections::HashMap::new();
+    map.insert(vec![1], "not a string key");
+    assert!(ClientRequest::call_tool(1, "t").arg("map", map).is_err());
+}
+
+#[test]
+fn test_envelope_constructors() {
+    let request = JSONRPCRequest::new(1, "tools/list", json!({}));
+    let response = JSONRPCResponse::new("a", json!({ "tools": [] }));
+    let notification = JSONRPCNotification::new("notifications/initialized", json!({}));
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        json!({ "jsonrpc": "2.0", "method": "tools/list", "id": 1, "params": {} })
+    );
+    assert_eq!(
+        serde_json::to_value(&response).unwrap(),
+        json!({ "jsonrpc": "2.0", "id": "a", "result": { "tools": [] } })
+    );
+    assert_eq!(notification.json_rpc, JSONRPC_VERSION);
+}
+
+#[test]
+fn test_jsonrpc_version_is_validated() {
+    let request: JSONRPCRequest<serde_json::Value> = serde_json::from_value(
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "ping", "params": {} }),
+    )
+    .unwrap();
+    assert_eq!(request.json_rpc, JsonRpcVersion);
+    assert_eq!(request.json_rpc.to_string(), "2.0");
+
+    for version in [json!("1.0"), json!("2"), json!(2.0)] {
+        let message = json!({ "jsonrpc": version, "id": 1, "method": "ping" });
+        assert!(serde_json::from_value::<JSONRPCMessage>(message.clone()).is_err());
+        assert!(serde_json::from_value::<ClientRequest>(message).is_err());
+    }
+}
+
+#[test]
+fn test_server_request_builder() {
+    let message = SamplingMessage::new(Role::User, SamplingContent::Text(TextContent::new("hi")));
+    let request: ServerRequest = ServerRequest::create_message(4, vec![message], 100)
+        .system_prompt("be brief")
+        .stop_sequence("END")
+        .build();
+    let value = serde_json::to_value(&request).unwrap();
+    assert_eq!(value["jsonrpc"], "2.0");
+    assert_eq!(value["method"], "sampling/createMessage");
+    assert_eq!(value["params"]["systemPrompt"], "be brief");
+    assert_eq!(value["params"]["stopSequences"], json!(["END"]));
+    assert_eq!(
+        request.to_string(),
+        "sampling/createMessage #4 messages=1 max_tokens=100"
+    );
+
+    let request: ServerRequest = ServerRequest::list_roots("r").build();
+    assert_eq!(request.to_string(), "roots/list #r");
+    let request: ServerRequest =
+        ServerRequest::elicitation_create(5, "name?", RequestedSchema::default()).build();
+    assert_eq!(request.method(), Method::ElicitationCreate);
+}
+
+#[test]
+fn test_jsonrpc_message() {
+    let parse = |line: &str| serde_json::from_str::<JSONRPCMessage>(line);
+
+    let message = parse(r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#).unwrap();
+    assert!(matches!(&message, JSONRPCMessage::Request(r) if r.params.is_none()));
+    assert_eq!(
+        serde_json::to_value(&message).unwrap(),
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "ping" })
+    );
+    assert_eq!(message.method(), Some("ping"));
+    assert_eq!(message.id().map(ToString::to_string).as_deref(), Some("1"));
+
+    let message = parse(
+        r#"{"jsonrpc":"2.0","method":"notifications/progress","params":{"progressToken":"t","progress":1}}"#,
+    )
+    .unwrap();
+    assert!(matches!(message, JSONRPCMessage::Notification(_)));
+    assert!(message.id().is_none());
+
+    let raw = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+    let message: JSONRPCMessage = serde_json::from_value(raw.clone()).unwrap();
+    assert!(matches!(&message, JSONRPCMessage::Notification(n) if n.params.is_none()));
+    assert_eq!(serde_json::to_value(&message).unwrap(), raw);
+
+    let message = parse(r#"{"jsonrpc":"2.0","id":"a","result":{"tools":[]}}"#).unwrap();
+    assert!(matches!(&message, JSONRPCMessage::Response(r) if r.result == json!({ "tools": [] })));
+
+    let message =
+        parse(r#"{"jsonrpc":"2.0","id":2,"error":{"code":-32601,"message":"nope"}}"#).unwrap();
+    assert!(matches!(&message, JSONRPCMessage::Error(e) if e.error.code == METHOD_NOT_FOUND));
+    assert_eq!(
+        serde_json::to_value(&message).unwrap(),
+        json!({ "jsonrpc": "2.0", "id": 2, "error": { "code": -32601, "message": "nope" } })
+    );
+
+    let err = parse(r#"{"jsonrpc":"2.0","id":3,"result":{},"error":{"code":1,"message":"x"}}"#)
+        .unwrap_err();
+    assert!(err.to_string().contains("both"));
+    assert!(parse(r#"{"jsonrpc":"2.0","id":3}"#).is_err());
+    assert!(parse(r#"[1]"#).is_err());
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_method_enum() {
+    for method in Method::ALL {
+        assert_eq!(method.to_string().parse::<Method>(), Ok(*method));
+    }
+    assert_eq!(
+        "tools/cal".parse::<Method>().unwrap_err().to_string(),
+        "unknown method \"tools/cal\""
+    );
+    assert!(Method::ToolListChanged.is_notification());
+    assert!(!Method::ListTools.is_notification());
+    assert!(Method::Ping.is_client_request() && Method::Ping.is_server_request());
+    assert!(!Method::ListRoots.is_client_request() && Method::ListRoots.is_server_request());
+    assert!(Method::Initialized.is_client_notification());
+    assert!(!Method::Initialized.is_server_notification());
+    assert!(Method::Progress.is_client_notification() && Method::Progress.is_server_notification());
+    assert_eq!(
+        serde_json::from_value::<Method>(json!("roots/list")).unwrap(),
+        Method::ListRoots
+    );
+
+    let requests = [
+        ClientRequest::ping(1).build(),
+        ClientRequest::set_level(1, LoggingLevel::Info).build(),
+        ClientRequest::get_prompt(1, "p").build(),
+        ClientRequest::list_prompts(1).build(),
+        ClientRequest::list_resources(1).build(),
+        ClientRequest::list_resource_templates(1).build(),
+        ClientRequest::read_resource(1, "file:///a").build(),
+        ClientRequest::subscribe(1, "file:///a").build(),
+        ClientRequest::unsubscribe(1, "file:///a").build(),
+        ClientRequest::call_tool(1, "t").build(),
+        ClientRequest::list_tools(1).build(),
+        ClientRequest::elicitation_create(1, "m", RequestedSchema::default()).build(),
+    ];
+    for request in &requests {
+        let value = serde_json::to_value(request).unwrap();
+        assert_eq!(value["method"], request.method().as_str());
+    }
+
+    let notification: ServerNotification = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "method": "notifications/tools/list_changed"
+    }))
+    .unwrap();
+    assert_eq!(notification.method(), Method::ToolListChanged);
+}
+
+#[test]
+fn test_request_id_as_key() {
+    let mut pending = std::collections::HashMap::new();
+    pending.insert(RequestId::from(1), "tools/list");
+    pending.insert(RequestId::from("1"), "prompts/list");
+    assert_eq!(pending[&RequestId::Number(1)], "tools/list");
+    assert_eq!(pending[&RequestId::String("1".into())], "prompts/list");
+
+    let mut ids = vec![
+        RequestId::from("b"),
+        RequestId::from(10),
+        RequestId::from("a"),
+        RequestId::from(2),
+    ];
+    ids.sort();
+    assert_eq!(
+        ids,
+        vec![
+            RequestId::from(2),
+            RequestId::from(10),
+            RequestId::from("a"),
+            RequestId::from("b"),
+        ]
+    );
+    assert!(ProgressToken::from(5) < ProgressToken::from("5"));
+    assert_ne!(ProgressToken::from(5), ProgressToken::from("5"));
+}
+
+#[test]
+fn test_request_id_generator() {
+    let ids = RequestIdGenerator::new();
+    assert_eq!(ids.next_id(), RequestId::from(1));
+    assert_eq!(ids.next_id(), RequestId::from(2));
+
+    let ids = std::sync::Arc::new(RequestIdGenerator::with_prefix("cli-"));
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let ids = ids.clone();
+            std::thread::spawn(move || (0..100).map(|_| ids.next_id()).collect::<Vec<_>>())
+        })
+        .collect();
+    let mut seen = std::collections::HashSet::new();
+    for handle in handles {
+        for id in handle.join().unwrap() {
+            assert!(id.to_string().starts_with("cli-"));
+            assert!(seen.insert(id));
+        }
+    }
+    assert_eq!(seen.len(), 400);
+    assert_eq!(ids.next_id(), RequestId::from("cli-401"));
+}
+
+#[test]
+#[cfg(feature = "uuid")]
+fn test_uuid_ids() {
+    let a = RequestId::new_uuid();
+    let b = RequestId::new_uuid();
+    assert_ne!(a, b);
+    let uuid = a.as_uuid().unwrap();
+    assert_eq!(RequestId::from(uuid), a);
+    assert!(RequestId::from(1).as_uuid().is_none());
+    assert!(RequestId::from("cli-1").as_uuid().is_none());
+
+    let token = ProgressToken::new_uuid();
+    assert_eq!(token.as_uuid().unwrap().get_version_num(), 4);
+}
+
+#[test]
+fn test_mcp_str() {
+    let owned = McpStr::from(String::from("tools/call"));
+    let borrowed = McpStr::from("tools/call");
+    assert_eq!(owned, borrowed);
+    assert_eq!(owned, "tools/call");
+    assert!(owned.starts_with("tools/"));
+    assert_eq!(
+        format!("{} {:?}", owned, owned),
+        "tools/call \"tools/call\""
+    );
+    assert_eq!(serde_json::to_value(&owned).unwrap(), json!("tools/call"));
+    let parsed: McpStr = serde_json::from_value(json!("ping")).unwrap();
+    assert_eq!(String::from(parsed), "ping");
+}
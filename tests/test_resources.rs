@@ -0,0 +1,312 @@
+use mcp_schema::*;
+use serde_json::json;
+
+#[test]
+fn test_resource_scheme() {
+    assert_eq!(
+        ResourceScheme::of("file:///etc/hosts"),
+        Some(ResourceScheme::File)
+    );
+    assert_eq!(
+        ResourceScheme::of("HTTPS://example.com"),
+        Some(ResourceScheme::Https)
+    );
+    assert_eq!(
+        ResourceScheme::of("postgres://db/users"),
+        Some(ResourceScheme::Custom("postgres".into()))
+    );
+    assert_eq!(ResourceScheme::of("no-scheme"), None);
+    assert_eq!(ResourceScheme::of("1abc:x"), None);
+
+    let root: Root = serde_json::from_value(json!({ "uri": "file:///home/user" })).unwrap();
+    assert_eq!(root.scheme(), Some(ResourceScheme::File));
+}
+
+#[test]
+fn test_filter_scheme() {
+    let resources: Vec<Resource> = serde_json::from_value(json!([
+        { "uri": "file:///a.txt", "name": "a" },
+        { "uri": "https://example.com/b", "name": "b" },
+        { "uri": "file:///c.txt", "name": "c" }
+    ]))
+    .unwrap();
+    let files: Vec<&str> = resources
+        .iter()
+        .filter_scheme(ResourceScheme::File)
+        .map(|resource| resource.name.as_str())
+        .collect();
+    assert_eq!(files, vec!["a", "c"]);
+}
+
+fn roots(uris: &[&str]) -> ListRootsResult {
+    serde_json::from_value(json!({
+        "roots": uris.iter().map(|uri| json!({ "uri": uri })).collect::<Vec<_>>()
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_roots_watcher() {
+    let mut watcher = RootsWatcher::new();
+    let change = watcher.update(&roots(&["file:///work/app", "file:///work/lib/"]));
+    assert!(change.needs_rescan());
+    assert_eq!(change.added.len(), 2);
+
+    watcher.subscribe("file:///work/app/src/main.rs");
+    watcher.subscribe("file:///work/lib/mod.rs");
+    assert!(watcher.is_within_roots("file:///work/lib"));
+    assert!(!watcher.is_within_roots("file:///work/application"));
+
+    let change = watcher.update(&roots(&["file:///work/app", "file:///work/lib/"]));
+    assert!(!change.needs_rescan());
+    assert_eq!(change.affected_subscriptions().count(), 0);
+
+    let change = watcher.update(&roots(&["file:///work/app"]));
+    assert!(change.needs_rescan());
+    assert_eq!(change.removed[0].uri, "file:///work/lib/");
+    assert_eq!(
+        change.affected_subscriptions().collect::<Vec<_>>(),
+        vec!["file:///work/lib/mod.rs"]
+    );
+    assert_eq!(
+        watcher.subscriptions().collect::<Vec<_>>(),
+        vec!["file:///work/app/src/main.rs"]
+    );
+}
+
+fn template(uri_template: &str) -> ResourceTemplate {
+    serde_json::from_value(json!({ "uriTemplate": uri_template, "name": uri_template })).unwrap()
+}
+
+#[test]
+fn test_resource_template_matching() {
+    let vars = template("github://{owner}/{repo}/issues/{number}")
+        .match_uri("github://rust-lang/rust/issues/42")
+        .unwrap();
+    assert_eq!(vars["owner"], "rust-lang");
+    assert_eq!(vars["repo"], "rust");
+    assert_eq!(vars["number"], "42");
+
+    assert!(template("file:///{name}")
+        .match_uri("file:///a/b")
+        .is_none());
+    let vars = template("file:///{+path}")
+        .match_uri("file:///a/b")
+        .unwrap();
+    assert_eq!(vars["path"], "a/b");
+    assert!(template("file:///{name}.txt")
+        .match_uri("file:///.txt")
+        .is_none());
+    assert!(template("file:///{name}").match_uri("file:///").is_none());
+    assert!(template("file:///{+path}").match_uri("file:///").is_none());
+    assert!(template("db://{a}/{b}").match_uri("db:///x").is_none());
+}
+
+#[test]
+fn test_resource_index() {
+    let mut index = ResourceIndex::new().with_page_size(10);
+    index.add_resource(
+        serde_json::from_value(json!({ "uri": "file:///README.md", "name": "readme" })).unwrap(),
+    );
+    index.add_template(template("file:///{+path}"));
+
+    assert!(matches!(
+        index.resolve("file:///README.md").unwrap(),
+        Resolved::Resource(_)
+    ));
+    match index.resolve("file:///src/lib.rs").unwrap() {
+        Resolved::Template { variables, .. } => assert_eq!(variables["path"], "src/lib.rs"),
+        other => panic!("unexpected resolution: {:?}", other),
+    }
+    assert!(index.resolve("https://example.com").is_err());
+
+    assert_eq!(index.list_resources(None).items.len(), 1);
+    assert_eq!(index.list_templates(None).items.len(), 1);
+
+    index.add_resource(
+        serde_json::from_value(json!({ "uri": "file:///a.bin", "name": "a", "size": 2048 }))
+            .unwrap(),
+    );
+    let page = index.list_resources(None);
+    assert_eq!(page.known_size(), 2048);
+    let listed = serde_json::to_value(&page).unwrap();
+    assert_eq!(listed["resources"][1]["size"], json!(2048));
+    assert!(listed["resources"][0].get("size").is_none());
+
+    index.subscribe("file:///src/lib.rs").unwrap();
+    assert!(index.subscribe("https://example.com").is_err());
+    assert!(index.is_subscribed("file:///src/lib.rs"));
+    assert!(index.unsubscribe("file:///src/lib.rs"));
+    assert_eq!(index.subscriptions().count(), 0);
+}
+
+#[test]
+fn test_chunked_text_contents() {
+    let contents: ResourceContents = serde_json::from_value(json!({
+        "uri": "file:///big.txt",
+        "mimeType": "text/plain",
+        "text": "héllo wörld, chunked"
+    }))
+    .unwrap();
+    let chunks = split_contents(&contents, 8).unwrap();
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(
+        chunks[1].chunk_meta(),
+        Some(ResourceChunkMeta {
+            index: 1,
+            total: 3,
+            byte_range: 8..16
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(&chunks[0]).unwrap()["_meta"][meta_keys::RESOURCE_CHUNK],
+        json!({ "index": 0, "total": 3, "byteRange": { "start": 0, "end": 8 } })
+    );
+
+    let mut assembler = ChunkAssembler::new();
+    assert!(assembler.push(chunks[0].clone()).unwrap().is_none());
+    assert_eq!(
+        assembler.push(chunks[2].clone()).unwrap_err(),
+        ChunkError::OutOfOrder {
+            expected: 1,
+            found: 2
+        }
+    );
+    assert!(assembler.push(chunks[1].clone()).unwrap().is_none());
+    let whole = assembler.push(chunks[2].clone()).unwrap().unwrap();
+    assert!(assembler.is_complete());
+    assert_eq!(
+        serde_json::to_value(&whole).unwrap(),
+        serde_json::to_value(&contents).unwrap()
+    );
+
+    let unsplit = split_contents(&contents, 1024).unwrap();
+    assert_eq!(unsplit.len(), 1);
+    assert!(unsplit[0].chunk_meta().is_none());
+
+    let mut assembler = ChunkAssembler::new();
+    let bad = chunks[0].clone().with_chunk_meta(ResourceChunkMeta {
+        index: 3,
+        total: 3,
+        byte_range: 0..8,
+    });
+    assert_eq!(
+        assembler.push(bad.clone()).unwrap_err(),
+        ChunkError::InvalidChunkMeta
+    );
+    let bad = bad.with_chunk_meta(ResourceChunkMeta {
+        index: 0,
+        total: 0,
+        byte_range: 0..8,
+    });
+    assert_eq!(
+        assembler.push(bad).unwrap_err(),
+        ChunkError::InvalidChunkMeta
+    );
+}
+
+#[test]
+fn test_chunked_blob_contents() {
+    // 10 bytes: "0123456789".
+    let contents: ResourceContents = serde_json::from_value(json!({
+        "uri": "file:///data.bin",
+        "blob": "MDEyMzQ1Njc4OQ=="
+    }))
+    .unwrap();
+    let chunks = split_contents(&contents, 4).unwrap();
+    assert_eq!(chunks.len(), 4);
+    let ranges: Vec<_> = chunks
+        .iter()
+        .map(|chunk| chunk.chunk_meta().unwrap().byte_range)
+        .collect();
+    assert_eq!(ranges, [0..3, 3..6, 6..9, 9..10]);
+
+    let mut assembler = ChunkAssembler::new();
+    let mut whole = None;
+    for chunk in chunks {
+        whole = assembler.push(chunk).unwrap();
+    }
+    match whole.unwrap() {
+        ResourceContents::Blob(blob) => assert_eq!(blob.blob, "MDEyMzQ1Njc4OQ=="),
+        ResourceContents::Text(_) => panic!("expected a blob"),
+    }
+
+    for blob in [
+        "MDEyMzQ1Njc4OQ",
+        "MDEy!zQ1Njc4OQ==",
+        "MDEyMzQ1Njc4O===",
+        "MDEyMzQ1Njc4Oa\u{e9}",
+    ] {
+        let contents: ResourceContents =
+            serde_json::from_value(json!({ "uri": "file:///data.bin", "blob": blob })).unwrap();
+        assert_eq!(
+            split_contents(&contents, 4).unwrap_err(),
+            ChunkError::InvalidBase64
+        );
+    }
+}
+
+#[test]
+fn test_last_modified_annotation() {
+    let resource: Resource = serde_json::from_value(json!({
+        "uri": "file:///a.txt",
+        "name": "a.txt",
+        "annotations": { "lastModified": "2025-01-12T15:00:58Z" }
+    }))
+    .unwrap();
+    let last_modified = resource
+        .annotated
+        .annotations
+        .as_ref()
+        .and_then(|annotations| annotations.last_modified.as_ref())
+        .unwrap();
+    assert_eq!(last_modified.to_string().get(..10), Some("2025-01-12"));
+    assert_eq!(
+        serde_json::to_value(&resource).unwrap()["annotations"]["lastModified"],
+        "2025-01-12T15:00:58Z"
+    );
+
+    for invalid in ["yesterday", "2025-13-01T00:00:00Z", "2025-01-12T15:00:58"] {
+        let annotations = json!({ "lastModified": invalid });
+        assert!(serde_json::from_value::<Annotations>(annotations).is_err());
+    }
+    assert_eq!(
+        "2025-01-12 15:00".parse::<Timestamp>(),
+        Err(InvalidTimestamp("2025-01-12 15:00".into()))
+    );
+    assert!("2025-01-12T15:00:58.123+09:00".parse::<Timestamp>().is_ok());
+}
+
+#[test]
+fn test_timestamp_from_system_time() {
+    let time = std::time::UNIX_EPOCH + std::time::Duration::from_millis(1_736_694_058_250);
+    let timestamp = Timestamp::from(time);
+    assert_eq!(timestamp.as_str(), "2025-01-12T15:00:58.250Z");
+    assert_eq!(timestamp.as_str().parse::<Timestamp>(), Ok(timestamp));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_timestamp_chrono() {
+    let timestamp: Timestamp = "2025-01-12T15:00:58+09:00".parse().unwrap();
+    let time = timestamp.to_chrono().unwrap();
+    assert_eq!(time.to_rfc3339(), "2025-01-12T06:00:58+00:00");
+    let back = Timestamp::try_from(time).unwrap();
+    assert_eq!(back.as_str(), "2025-01-12T06:00:58Z");
+    assert!("2025-02-30T00:00:00Z"
+        .parse::<Timestamp>()
+        .unwrap()
+        .to_chrono()
+        .is_err());
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn test_timestamp_time() {
+    let timestamp: Timestamp = "2025-01-12T15:00:58.5+09:00".parse().unwrap();
+    let time = timestamp.to_time().unwrap();
+    assert_eq!(time.offset().whole_hours(), 9);
+    let back = Timestamp::try_from(time).unwrap();
+    assert_eq!(back.as_str(), "2025-01-12T15:00:58.5+09:00");
+    assert!(time::OffsetDateTime::try_from(back).is_ok());
+}
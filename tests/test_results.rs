@@ -0,0 +1,192 @@
+use mcp_schema::*;
+use serde_json::json;
+
+#[test]
+fn test_server_result_kind_inference() {
+    let result: ServerResult = serde_json::from_value(json!({
+        "tools": [
+            { "name": "echo", "inputSchema": { "type": "object" } }
+        ],
+        "nextCursor": "page-2"
+    }))
+    .unwrap();
+    assert_eq!(result.kind(), ServerResultKind::ListTools);
+    assert_eq!(result.likely_method(), &["tools/list"]);
+
+    let result: ServerResult = serde_json::from_value(json!({
+        "content": [{ "type": "text", "text": "done" }]
+    }))
+    .unwrap();
+    assert_eq!(result.kind(), ServerResultKind::CallTool);
+    assert_eq!(result.likely_method(), &["tools/call"]);
+}
+
+#[test]
+fn test_server_result_empty_is_ambiguous() {
+    let result: ServerResult = serde_json::from_value(json!({})).unwrap();
+    assert_eq!(result.kind(), ServerResultKind::Empty);
+    assert!(result.likely_method().contains(&"ping"));
+    assert!(result.likely_method().contains(&"resources/subscribe"));
+}
+
+#[test]
+#[cfg(not(feature = "strict-fields"))]
+fn test_paginated_roundtrip() {
+    let value = json!({
+        "_meta": { "page": 1 },
+        "nextCursor": "next",
+        "prompts": [{ "name": "summarize" }],
+        "vendorField": true
+    });
+    let result: ListPromptsResult = serde_json::from_value(value.clone()).unwrap();
+    assert_eq!(result.items.len(), 1);
+    assert_eq!(result.items[0].name, "summarize");
+    assert_eq!(result.next_cursor.as_deref(), Some("next"));
+    assert_eq!(result.extra["vendorField"], true);
+    assert_eq!(serde_json::to_value(&result).unwrap(), value);
+
+    let templates: ListResourceTemplatesResult = serde_json::from_value(json!({
+        "resourceTemplates": [{ "uriTemplate": "file:///{path}", "name": "files" }]
+    }))
+    .unwrap();
+    assert!(templates.next_cursor.is_none());
+    assert_eq!(templates.items[0].uri_template, "file:///{path}");
+}
+
+#[test]
+fn test_paginated_requires_items_field() {
+    let err = serde_json::from_value::<ListResourcesResult>(json!({ "tools": [] })).unwrap_err();
+    assert!(err.to_string().contains("resources"));
+}
+
+#[test]
+#[cfg(not(feature = "strict-fields"))]
+fn test_with_meta_roundtrip() {
+    let value = json!({
+        "_meta": { "com.example/source": "vscode" },
+        "roots": [{ "uri": "file:///work", "name": "work" }],
+        "com.example/extra": true
+    });
+    let result: ListRootsResult = serde_json::from_value(value.clone()).unwrap();
+    assert_eq!(result.roots[0].uri, "file:///work");
+    assert_eq!(
+        result.meta.as_ref().unwrap()["com.example/source"],
+        "vscode"
+    );
+    assert_eq!(result.extra.len(), 1);
+    assert!(!result.extra.contains_key("roots"));
+    assert_eq!(serde_json::to_value(&result).unwrap(), value);
+
+    let result = ListRootsResult::new(ListRootsPayload { roots: Vec::new() })
+        .with_meta("com.example/source", json!("cli"));
+    assert_eq!(
+        serde_json::to_value(&result).unwrap(),
+        json!({ "_meta": { "com.example/source": "cli" }, "roots": [] })
+    );
+}
+
+#[test]
+fn test_complete_result_truncates_values() {
+    let result = CompleteResult::from_values((0..150).map(|i| format!("value-{}", i)));
+    assert_eq!(result.completion.values.len(), MAX_COMPLETION_VALUES);
+    assert_eq!(result.completion.total, Some(150));
+    assert_eq!(result.completion.has_more, Some(true));
+
+    let result = CompleteResult::from_values(["a", "b"]);
+    assert_eq!(result.completion.values, vec!["a", "b"]);
+    assert_eq!(result.completion.total, Some(2));
+    assert_eq!(result.completion.has_more, Some(false));
+
+    let empty = CompletionData::empty();
+    assert!(empty.values.is_empty());
+    assert_eq!(empty.has_more, Some(false));
+}
+
+#[cfg(feature = "hmac")]
+#[test]
+fn test_signed_cursor_roundtrip_and_tampering() {
+    #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+    struct State {
+        offset: usize,
+    }
+
+    let key = b"server secret";
+    let cursor = SignedCursor::encode(&State { offset: 50 }, key).unwrap();
+    let text: Cursor = cursor.clone().into();
+    let back = SignedCursor::from(text.clone());
+    assert_eq!(back.decode::<State>(key).unwrap(), State { offset: 50 });
+
+    assert!(matches!(
+        back.decode::<State>(b"other key"),
+        Err(CursorError::BadSignature)
+    ));
+    let forged = SignedCursor::encode(&State { offset: 0 }, b"other key").unwrap();
+    let (payload, _) = forged.as_str().split_once('.').unwrap();
+    let (_, signature) = text.split_once('.').unwrap();
+    let tampered = SignedCursor::from(format!("{}.{}", payload, signature));
+    assert!(matches!(
+        tampered.decode::<State>(key),
+        Err(CursorError::BadSignature)
+    ));
+    assert!(matches!(
+        SignedCursor::from("garbage".to_string()).decode::<State>(key),
+        Err(CursorError::Malformed)
+    ));
+}
+
+#[test]
+fn test_server_result_conversions() {
+    let response: AnyServerResponse = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "result": { "tools": [{ "name": "echo", "inputSchema": { "type": "object" } }] }
+    }))
+    .unwrap();
+    let typed = response.clone().into_typed::<ListToolsResult>().unwrap();
+    assert_eq!(typed.result.items[0].name, "echo");
+
+    let err = response.result.expect_call_tool().unwrap_err();
+    assert_eq!(err.expected, ServerResultKind::CallTool);
+    assert_eq!(err.found, ServerResultKind::ListTools);
+    assert_eq!(
+        err.to_string(),
+        "expected a tools/call result, got a tools/list result"
+    );
+
+    let result = ServerResult::from(CallToolResult::text("done"));
+    assert!(result.clone().expect_empty().is_err());
+    assert_eq!(CallToolResult::try_from(result).unwrap().content.len(), 1);
+}
+
+#[test]
+fn test_client_result_conversions() {
+    let response: AnyClientResponse = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "id": 4,
+        "result": { "roots": [{ "uri": "file:///repo", "name": "repo" }] }
+    }))
+    .unwrap();
+    assert_eq!(response.result.kind(), ClientResultKind::ListRoots);
+    let typed = response.clone().into_typed::<ListRootsResult>().unwrap();
+    assert_eq!(typed.result.roots[0].uri, "file:///repo");
+
+    let err = response.result.expect_create_message().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "expected a sampling/createMessage result, got a roots/list result"
+    );
+
+    let result: ClientResult = serde_json::from_value(json!({
+        "role": "assistant",
+        "content": { "type": "text", "text": "hi" },
+        "model": "m"
+    }))
+    .unwrap();
+    assert_eq!(result.likely_method(), ["sampling/createMessage"]);
+
+    let result: ClientResult = serde_json::from_value(json!({ "action": "cancel" })).unwrap();
+    assert_eq!(result.kind(), ClientResultKind::ElicitationCreate);
+
+    let result: ClientResult = serde_json::from_value(json!({})).unwrap();
+    assert_eq!(result.kind(), ClientResultKind::Empty);
+}
@@ -0,0 +1,86 @@
+use mcp_schema::*;
+
+#[test]
+fn test_event_id_header() {
+    let id = EventId::from_header(" stream-a:4 ").unwrap();
+    assert_eq!(id.parts(), Some(("stream-a", 4)));
+    assert_eq!(id, EventId::new("stream-a", 4));
+    assert!(EventId::from_header("").is_none());
+    assert!(EventId::from_header("a\nb").is_none());
+    assert!(EventId::from_header("opaque").unwrap().parts().is_none());
+
+    let id = EventId::new("a\nb%", 1);
+    assert_eq!(id.as_str(), "a%0Ab%25:1");
+    assert_eq!(EventId::from_header(id.as_str()), Some(id));
+}
+
+#[test]
+fn test_replay_buffer() {
+    let mut buffer = ReplayBuffer::new(3);
+    let a0 = buffer.push("a", "a0");
+    let b0 = buffer.push("b", "b0");
+    let a1 = buffer.push("a", "a1");
+    assert_eq!(a1, EventId::new("a", 1));
+    // A stream's numbering starts at the number of messages pushed before it.
+    assert_eq!(b0, EventId::new("b", 1));
+
+    let replayed: Vec<_> = buffer.replay_after(&a0).unwrap().map(|(_, m)| *m).collect();
+    assert_eq!(replayed, vec!["a1"]);
+    assert_eq!(buffer.replay_after(&a1).unwrap().count(), 0);
+    assert!(matches!(
+        buffer.replay_after(&EventId::new("a", 7)),
+        Err(ReplayError::UnknownEventId(_))
+    ));
+
+    // Evicts a0 and b0; b0 itself need not be buffered to resume after it.
+    buffer.push("a", "a2");
+    buffer.push("b", "b1");
+    assert_eq!(buffer.len(), 3);
+    let replayed: Vec<_> = buffer.replay_after(&b0).unwrap().map(|(_, m)| *m).collect();
+    assert_eq!(replayed, vec!["b1"]);
+    let a3 = buffer.push("a", "a3");
+    buffer.push("a", "a4");
+    assert!(matches!(
+        buffer.replay_after(&a1),
+        Err(ReplayError::Evicted(_))
+    ));
+
+    // Evicting b1 leaves nothing of stream b, but its numbering goes on.
+    buffer.push("a", "a5");
+    assert!(matches!(
+        buffer.replay_after(&b0),
+        Err(ReplayError::Evicted(_))
+    ));
+    assert_eq!(buffer.push("b", "b2"), EventId::new("b", 3));
+    let replayed: Vec<_> = buffer.replay_after(&a3).unwrap().map(|(_, m)| *m).collect();
+    assert_eq!(replayed, vec!["a4", "a5"]);
+}
+
+#[test]
+fn test_replay_buffer_ids_survive_eviction() {
+    let mut buffer = ReplayBuffer::new(2);
+    assert_eq!(buffer.push("a", ()), EventId::new("a", 0));
+    buffer.push("b", ());
+    buffer.push("b", ());
+    assert_eq!(buffer.push("a", ()), EventId::new("a", 1));
+
+    let mut buffer = ReplayBuffer::new(0);
+    assert_eq!(buffer.push("a", ()), EventId::new("a", 0));
+    assert_eq!(buffer.push("a", ()), EventId::new("a", 1));
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_replay_buffer_forgets_idle_streams() {
+    let mut buffer = ReplayBuffer::new(2).with_idle_limit(4);
+    let a0 = buffer.push("a", ());
+    for _ in 0..8 {
+        buffer.push("b", ());
+    }
+    assert!(matches!(
+        buffer.replay_after(&a0),
+        Err(ReplayError::UnknownEventId(_))
+    ));
+    // The name can be reused without issuing a0's id again.
+    assert_eq!(buffer.push("a", ()), EventId::new("a", 9));
+}
@@ -0,0 +1,95 @@
+use mcp_schema::*;
+use serde_json::json;
+use serde_json::value::RawValue;
+
+fn request(value: serde_json::Value) -> JSONRPCRequest<Option<Box<RawValue>>> {
+    serde_json::from_str(&value.to_string()).unwrap()
+}
+
+fn router() -> Router {
+    let mut router = Router::new();
+    router
+        .on(|params: CallToolParams| Ok(CallToolResult::text(format!("called {}", params.name))))
+        .on(|params: ListToolsParams| {
+            assert!(params.0.cursor.is_none());
+            Ok(ToolRegistry::new().list(None))
+        });
+    router
+}
+
+#[test]
+fn test_router_decodes_and_encodes_typed_payloads() {
+    let router = router();
+    assert!(router.handles("tools/call"));
+
+    let response = router
+        .route(request(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "echo" }
+        })))
+        .unwrap();
+    let result: CallToolResult = serde_json::from_str(response.result.get()).unwrap();
+    assert_eq!(
+        serde_json::to_value(&result).unwrap()["content"][0]["text"],
+        "called echo"
+    );
+
+    let response = router
+        .route(request(
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }),
+        ))
+        .unwrap();
+    assert_eq!(response.result.get(), r#"{"tools":[]}"#);
+
+    let response = router
+        .route(request(
+            json!({ "jsonrpc": "2.0", "id": 3, "method": "tools/list", "params": null }),
+        ))
+        .unwrap();
+    assert_eq!(response.result.get(), r#"{"tools":[]}"#);
+}
+
+#[test]
+fn test_router_errors() {
+    let router = router();
+    let error = router
+        .route(request(
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "prompts/list" }),
+        ))
+        .unwrap_err();
+    assert_eq!(error.error.code, METHOD_NOT_FOUND);
+
+    let error = router
+        .route(request(json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": { "arguments": {} }
+        })))
+        .unwrap_err();
+    assert_eq!(error.error.code, INVALID_PARAMS);
+    assert_eq!(error.id, Some(RequestId::from(2)));
+}
+
+#[test]
+fn test_mcp_request_links_params_to_results() {
+    fn result_of<R: McpRequest>(_: &R, result: serde_json::Value) -> R::Result {
+        serde_json::from_value(result).unwrap()
+    }
+
+    let params: CallToolParams = serde_json::from_value(json!({ "name": "echo" })).unwrap();
+    let request = params.into_request("req-1");
+    assert_eq!(request.method, CallToolParams::METHOD.as_str());
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        json!({ "jsonrpc": "2.0", "method": "tools/call", "id": "req-1", "params": { "name": "echo" } })
+    );
+    let result = result_of(&request.params, json!({ "content": [] }));
+    assert!(result.content.is_empty());
+
+    assert_eq!(ListRootsParams::METHOD, Method::ListRoots);
+    let roots = result_of(&ListRootsParams::default(), json!({ "roots": [] }));
+    assert!(roots.roots.is_empty());
+}
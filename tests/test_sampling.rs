@@ -0,0 +1,286 @@
+use mcp_schema::*;
+use serde_json::json;
+
+#[test]
+fn test_sampling_message_contents() {
+    let message: SamplingMessage = serde_json::from_value(json!({
+        "role": "user",
+        "content": { "type": "text", "text": "hello" }
+    }))
+    .unwrap();
+    assert_eq!(message.contents().len(), 1);
+    assert!(matches!(message.contents()[0], SamplingContent::Text(_)));
+}
+
+#[cfg(feature = "draft")]
+#[test]
+fn test_sampling_message_multiple_contents() {
+    let value = json!({
+        "role": "user",
+        "content": [
+            { "type": "text", "text": "describe this" },
+            { "type": "image", "data": "aGVsbG8=", "mimeType": "image/png" }
+        ]
+    });
+    let message: draft::SamplingMessage = serde_json::from_value(value.clone()).unwrap();
+    assert_eq!(message.contents().len(), 2);
+    assert!(matches!(message.contents()[1], SamplingContent::Image(_)));
+    assert_eq!(serde_json::to_value(&message).unwrap(), value);
+    assert!(serde_json::from_value::<SamplingMessage>(value).is_err());
+
+    let err = SamplingMessage::try_from(message.clone()).unwrap_err();
+    assert!(matches!(err, MessageConversionError::MultipleContents(2)));
+    assert!(PromptMessage::try_from(message).is_err());
+}
+
+#[cfg(feature = "draft")]
+#[test]
+fn test_draft_sampling_message_conversions() {
+    let single = SamplingMessage::new(Role::User, SamplingContent::Text(TextContent::new("hi")));
+    let draft = draft::SamplingMessage::from(single);
+    assert_eq!(draft.contents().len(), 1);
+    let prompt = PromptMessage::try_from(draft.clone()).unwrap();
+    assert!(matches!(prompt.content, PromptContent::Text(_)));
+    let back = SamplingMessage::try_from(draft).unwrap();
+    assert!(matches!(back.content, SamplingContent::Text(_)));
+}
+
+#[test]
+fn test_create_message_params_from_prompt() {
+    let prompt: GetPromptResult = serde_json::from_value(json!({
+        "description": "Review code",
+        "messages": [
+            { "role": "user", "content": { "type": "text", "text": "Please review:" } },
+            {
+                "role": "user",
+                "content": {
+                    "type": "resource",
+                    "resource": { "uri": "file:///main.rs", "mimeType": "text/x-rust", "text": "fn main() {}" }
+                }
+            },
+            {
+                "role": "user",
+                "content": {
+                    "type": "resource",
+                    "resource": { "uri": "file:///archive.zip", "mimeType": "application/zip", "blob": "UEsDBA==" }
+                }
+            }
+        ]
+    }))
+    .unwrap();
+
+    let params = CreateMessageParams::from_prompt(&prompt, 512);
+    assert_eq!(params.max_tokens, 512);
+    assert_eq!(params.messages.len(), 3);
+
+    let texts: Vec<&str> = params
+        .messages
+        .iter()
+        .map(|message| match &message.contents()[0] {
+            SamplingContent::Text(text) => text.text.as_str(),
+            other => panic!("unexpected content {:?}", other),
+        })
+        .collect();
+    assert_eq!(texts[0], "Please review:");
+    assert_eq!(texts[1], "fn main() {}");
+    assert!(texts[2].contains("file:///archive.zip"));
+}
+
+#[test]
+fn test_create_message_params_from_prompt_audio() {
+    let prompt: GetPromptResult = serde_json::from_value(json!({
+        "messages": [{
+            "role": "user",
+            "content": {
+                "type": "resource",
+                "resource": { "uri": "file:///memo.wav", "mimeType": "audio/wav", "blob": "UklGRg==" }
+            }
+        }]
+    }))
+    .unwrap();
+    let params = CreateMessageParams::from_prompt(&prompt, 64);
+    let SamplingContent::Audio(audio) = &params.messages[0].contents()[0] else {
+        panic!("expected audio, got {:?}", params.messages[0].contents());
+    };
+    assert_eq!(audio.data, "UklGRg==");
+    assert_eq!(audio.mime_type, "audio/wav");
+}
+
+#[test]
+fn test_prompt_message_to_sampling_message() {
+    let message: PromptMessage = serde_json::from_value(json!({
+        "role": "assistant",
+        "content": { "type": "text", "text": "Sure." }
+    }))
+    .unwrap();
+    let sampling = SamplingMessage::try_from(message).unwrap();
+    assert!(matches!(sampling.role, Role::Assistant));
+    assert!(matches!(sampling.contents()[0], SamplingContent::Text(_)));
+
+    let message: PromptMessage = serde_json::from_value(json!({
+        "role": "user",
+        "content": {
+            "type": "resource",
+            "resource": { "uri": "file:///a.txt", "text": "a" }
+        }
+    }))
+    .unwrap();
+    let err = SamplingMessage::try_from(message).unwrap_err();
+    assert!(matches!(err, MessageConversionError::EmbeddedResource(_)));
+}
+
+#[test]
+fn test_sampling_message_to_prompt_message() {
+    let message: SamplingMessage = serde_json::from_value(json!({
+        "role": "user",
+        "content": { "type": "image", "data": "aGk=", "mimeType": "image/png" }
+    }))
+    .unwrap();
+    let prompt = PromptMessage::from(message);
+    assert!(matches!(prompt.content, PromptContent::Image(_)));
+}
+
+#[test]
+fn test_model_hint_matching() {
+    let hint = ModelHint::new("sonnet");
+    assert!(hint.matches("claude-3-5-sonnet-20241022"));
+    assert!(hint.matches("Claude-3-5-Sonnet"));
+    assert!(!hint.matches("claude-3-opus-20240229"));
+
+    assert!(ModelHint::new("claude").matches("claude-3-haiku"));
+
+    let unnamed: ModelHint = serde_json::from_value(json!({})).unwrap();
+    assert!(!unnamed.matches("claude-3-haiku"));
+}
+
+fn candidates() -> Vec<ModelCandidate> {
+    vec![
+        ModelCandidate::new("claude-3-haiku", 0.1, 0.9, 0.4),
+        ModelCandidate::new("claude-3-5-sonnet", 0.5, 0.6, 0.8),
+        ModelCandidate::new("claude-3-opus", 0.9, 0.2, 0.9),
+    ]
+}
+
+#[test]
+fn test_select_model_by_priorities() {
+    let prefs: ModelPreferences = serde_json::from_value(json!({
+        "costPriority": 0.9,
+        "speedPriority": 0.8
+    }))
+    .unwrap();
+    let candidates = candidates();
+    assert_eq!(select_model(&candidates, &prefs).unwrap().name, "claude-3-haiku");
+
+    let prefs: ModelPreferences =
+        serde_json::from_value(json!({ "intelligencePriority": 1.0 })).unwrap();
+    assert_eq!(select_model(&candidates, &prefs).unwrap().name, "claude-3-opus");
+}
+
+#[test]
+fn test_select_model_hints_outrank_priorities() {
+    let prefs: ModelPreferences = serde_json::from_value(json!({
+        "hints": [{ "name": "sonnet" }, { "name": "haiku" }],
+        "costPriority": 1.0,
+        "speedPriority": 1.0
+    }))
+    .unwrap();
+    let candidates = candidates();
+    assert_eq!(select_model(&candidates, &prefs).unwrap().name, "claude-3-5-sonnet");
+    assert!(prefs.score(&candidates[0]) > prefs.score(&candidates[2]));
+    assert!(select_model(&[], &prefs).is_none());
+}
+
+#[cfg(feature = "provider-metadata")]
+#[test]
+fn test_provider_sampling_metadata() {
+    let mut params: CreateMessageParams = serde_json::from_value(json!({
+        "messages": [],
+        "maxTokens": 100,
+        "metadata": { "anthropic": { "top_k": 40, "top_p": 0.9 } }
+    }))
+    .unwrap();
+
+    let anthropic = params
+        .metadata_for::<AnthropicSamplingMeta>()
+        .unwrap()
+        .unwrap();
+    assert_eq!(anthropic.top_k, Some(40));
+    assert_eq!(anthropic.top_p, Some(0.9));
+    assert!(params
+        .metadata_for::<OpenAiSamplingMeta>()
+        .unwrap()
+        .is_none());
+
+    let openai = OpenAiSamplingMeta {
+        seed: Some(7),
+        ..Default::default()
+    };
+    params.set_metadata_for(&openai).unwrap();
+    assert_eq!(
+        serde_json::to_value(&params).unwrap()["metadata"]["openai"],
+        json!({ "seed": 7 })
+    );
+}
+
+#[test]
+fn test_estimate_tokens_and_validate() {
+    let params: CreateMessageParams = serde_json::from_value(json!({
+        "messages": [
+            { "role": "user", "content": { "type": "text", "text": "abcdefgh" } },
+            { "role": "user", "content": { "type": "image", "data": "AAAA", "mimeType": "image/png" } }
+        ],
+        "systemPrompt": "abcd",
+        "maxTokens": 10
+    }))
+    .unwrap();
+    let estimator = CharCountEstimator::default();
+    assert_eq!(params.estimate_tokens(&estimator), 3);
+    assert!(params.fits_context(&estimator, 13));
+    assert!(!params.fits_context(&estimator, 12));
+    assert!(params.validate().is_ok());
+
+    let mut params = params;
+    params.max_tokens = 0;
+    assert_eq!(
+        params.validate(),
+        Err(SamplingError::NonPositiveMaxTokens(0))
+    );
+}
+
+#[test]
+fn test_stop_sequence_validation_and_normalize() {
+    let mut params: CreateMessageParams = serde_json::from_value(json!({
+        "messages": [],
+        "maxTokens": 10,
+        "stopSequences": ["\n\nHuman:", "", "END", "\n\nHuman:"]
+    }))
+    .unwrap();
+    assert_eq!(params.validate(), Err(SamplingError::EmptyStopSequence));
+
+    params.normalize();
+    assert_eq!(
+        params.stop_sequences.as_deref().unwrap(),
+        ["\n\nHuman:", "END"]
+    );
+    assert!(params.validate().is_ok());
+
+    params.stop_sequences = Some(vec!["a".into(), "a".into()]);
+    assert_eq!(
+        params.validate(),
+        Err(SamplingError::DuplicateStopSequence("a".into()))
+    );
+    params.stop_sequences = Some(vec!["x".repeat(MAX_STOP_SEQUENCE_CHARS + 1)]);
+    assert!(matches!(
+        params.validate(),
+        Err(SamplingError::StopSequenceTooLong(_))
+    ));
+    params.stop_sequences = Some((0..=MAX_STOP_SEQUENCES).map(|i| i.to_string()).collect());
+    assert_eq!(
+        params.validate(),
+        Err(SamplingError::TooManyStopSequences(MAX_STOP_SEQUENCES + 1))
+    );
+
+    params.stop_sequences = Some(vec![String::new()]);
+    params.normalize();
+    assert!(params.stop_sequences.is_none());
+}
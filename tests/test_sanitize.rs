@@ -0,0 +1,38 @@
+use mcp_schema::sanitize::{self, SanitizeOptions};
+use mcp_schema::TextContent;
+
+#[test]
+fn test_strip_ansi() {
+    assert_eq!(
+        sanitize::strip_ansi("\u{1b}[1;31merror\u{1b}[0m: failed"),
+        "error: failed"
+    );
+    assert_eq!(
+        sanitize::strip_ansi("\u{1b}]0;window title\u{7}done"),
+        "done"
+    );
+    assert_eq!(sanitize::strip_ansi("plain"), "plain");
+}
+
+#[test]
+fn test_strip_ansi_nf_escapes() {
+    assert_eq!(sanitize::strip_ansi("hi\u{1b}(Bthere"), "hithere");
+    assert_eq!(
+        sanitize::strip_ansi("\u{1b}[m\u{1b}(B\u{1b}#8done"),
+        "done"
+    );
+    assert_eq!(sanitize::strip_ansi("a\u{1b}7b"), "ab");
+}
+
+#[test]
+fn test_text_content_sanitized() {
+    let text = TextContent::new("\u{1b}[32mok\u{1b}[0m\r\nnext\rline\u{0}");
+    assert_eq!(text.sanitized().text, "ok\nnext\nline\u{0}");
+
+    let options = SanitizeOptions {
+        remove_control_chars: true,
+        ..SanitizeOptions::default()
+    };
+    assert_eq!(text.sanitized_with(&options).text, "ok\nnext\nline");
+    assert_eq!(sanitize::remove_control_chars("a\tb\u{7}c\n"), "a\tbc\n");
+}
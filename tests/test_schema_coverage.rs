@@ -0,0 +1,188 @@
+//! Checks that every message in the bundled official schema has a
+//! counterpart in this crate, and that the crate models nothing the schema
+//! does not define.
+
+use mcp_schema::*;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+
+const SCHEMA: &str = include_str!("../schema/2025-06-18/schema.json");
+
+/// Discrepancies the crate has not caught up with yet, as reported by
+/// `coverage_report`. Fixing one makes the test fail until it is removed.
+const KNOWN_GAPS: &[&str] = &[
+    "ClientRequest: elicitation/create is not in the schema",
+    "ServerResult: elicitation/create is not in the schema",
+];
+
+/// The `method` constants of the definitions referenced by `union`.
+fn schema_methods(schema: &Value, union: &str) -> BTreeSet<String> {
+    schema["definitions"][union]["anyOf"]
+        .as_array()
+        .unwrap_or_else(|| panic!("schema has no {} union", union))
+        .iter()
+        .filter_map(|member| {
+            let name = member["$ref"].as_str()?.rsplit('/').next()?;
+            let name = name
+                .strip_suffix("Result")
+                .map(|stem| format!("{}Request", stem))
+                .unwrap_or_else(|| name.to_string());
+            let method = &schema["definitions"][&name]["properties"]["method"]["const"];
+            method.as_str().map(str::to_string)
+        })
+        .collect()
+}
+
+/// The methods a `method`-tagged enum accepts, read from the list of
+/// expected variants in the error for an unknown one.
+fn enum_methods<T: DeserializeOwned>() -> BTreeSet<String> {
+    let probe = json!({ "jsonrpc": "2.0", "id": 0, "method": "?", "params": {} });
+    let err = serde_json::from_value::<T>(probe)
+        .err()
+        .expect("probe method is unknown")
+        .to_string();
+    let expected = err
+        .split_once("expected")
+        .expect("serde lists the expected variants")
+        .1;
+    expected
+        .split('`')
+        .skip(1)
+        .step_by(2)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Every kind, so that a new variant must be added here to compile.
+#[allow(deprecated)]
+fn server_result_kinds() -> Vec<ServerResultKind> {
+    use ServerResultKind::*;
+    let all = vec![
+        Initialize,
+        Complete,
+        GetPrompt,
+        ListPrompts,
+        ListResources,
+        ListResourceTemplates,
+        ReadResource,
+        CallTool,
+        ListTools,
+        ElicitationCreate,
+        Empty,
+    ];
+    for kind in &all {
+        match kind {
+            Initialize
+            | Complete
+            | GetPrompt
+            | ListPrompts
+            | ListResources
+            | ListResourceTemplates
+            | ReadResource
+            | CallTool
+            | ListTools
+            | ElicitationCreate
+            | Empty => {}
+        }
+    }
+    all
+}
+
+/// Every client result kind, checked the same way as the server ones.
+fn client_result_kinds() -> Vec<ClientResultKind> {
+    use ClientResultKind::*;
+    let all = vec![CreateMessage, ListRoots, ElicitationCreate, Empty];
+    for kind in &all {
+        match kind {
+            CreateMessage | ListRoots | ElicitationCreate | Empty => {}
+        }
+    }
+    all
+}
+
+fn compare(
+    report: &mut Vec<String>,
+    union: &str,
+    schema: &BTreeSet<String>,
+    ours: &BTreeSet<String>,
+) {
+    for method in schema.difference(ours) {
+        report.push(format!("{}: {} is missing", union, method));
+    }
+    for method in ours.difference(schema) {
+        report.push(format!("{}: {} is not in the schema", union, method));
+    }
+}
+
+fn coverage_report() -> Vec<String> {
+    let schema: Value = serde_json::from_str(SCHEMA).unwrap();
+    let mut report = Vec::new();
+    compare(
+        &mut report,
+        "ClientRequest",
+        &schema_methods(&schema, "ClientRequest"),
+        &enum_methods::<ClientRequest>(),
+    );
+    compare(
+        &mut report,
+        "ClientNotification",
+        &schema_methods(&schema, "ClientNotification"),
+        &enum_methods::<ClientNotification>(),
+    );
+    compare(
+        &mut report,
+        "ServerRequest",
+        &schema_methods(&schema, "ServerRequest"),
+        &enum_methods::<ServerRequest>(),
+    );
+    compare(
+        &mut report,
+        "ServerNotification",
+        &schema_methods(&schema, "ServerNotification"),
+        &enum_methods::<ServerNotification>(),
+    );
+    // Empty results answer several methods and have no request of their own.
+    let ours = server_result_kinds()
+        .into_iter()
+        .filter(|kind| *kind != ServerResultKind::Empty)
+        .flat_map(|kind| kind.methods().iter().map(|m| m.to_string()))
+        .collect();
+    compare(
+        &mut report,
+        "ServerResult",
+        &schema_methods(&schema, "ServerResult"),
+        &ours,
+    );
+    let ours = client_result_kinds()
+        .into_iter()
+        .filter(|kind| *kind != ClientResultKind::Empty)
+        .flat_map(|kind| kind.methods().iter().map(|m| m.to_string()))
+        .collect();
+    compare(
+        &mut report,
+        "ClientResult",
+        &schema_methods(&schema, "ClientResult"),
+        &ours,
+    );
+    report
+}
+
+#[test]
+fn test_schema_coverage() {
+    let report = coverage_report();
+    let unexpected: Vec<_> = report
+        .iter()
+        .filter(|gap| !KNOWN_GAPS.contains(&gap.as_str()))
+        .collect();
+    let fixed: Vec<_> = KNOWN_GAPS
+        .iter()
+        .filter(|gap| !report.iter().any(|found| found == *gap))
+        .collect();
+    assert!(
+        unexpected.is_empty() && fixed.is_empty(),
+        "schema coverage changed\nnew gaps:\n  {:#?}\nfixed, remove from KNOWN_GAPS:\n  {:#?}",
+        unexpected,
+        fixed
+    );
+}
@@ -0,0 +1,276 @@
+//! Checks that the payload types carry every property of their definition
+//! in the bundled official schema, by reading a value that sets all of them
+//! and writing it back.
+
+// `strict-fields` rejects the properties the crate lacks instead of keeping
+// them in `extra`.
+#![cfg(not(feature = "strict-fields"))]
+
+use mcp_schema::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeSet, HashMap};
+
+const SCHEMA: &str = include_str!("../schema/2025-06-18/schema.json");
+
+/// Payload discrepancies the crate has not caught up with yet, as reported
+/// by `definitions_report`.
+const KNOWN_GAPS: &[&str] = &[];
+
+/// A value for `node` that sets every property the schema defines, so
+/// that a type missing one of them leaves it in `extra` or drops it.
+fn example(schema: &Value, node: &Value, name: &str) -> Value {
+    if let Some(target) = node["$ref"].as_str() {
+        let definition = target.rsplit('/').next().unwrap();
+        return example(schema, &schema["definitions"][definition], name);
+    }
+    if let Some(value) = node.get("const") {
+        return value.clone();
+    }
+    if let Some(first) = node["enum"].get(0).or_else(|| node["anyOf"].get(0)) {
+        return match node.get("enum") {
+            Some(_) => first.clone(),
+            None => example(schema, first, name),
+        };
+    }
+    let kind = match &node["type"] {
+        Value::Array(kinds) => kinds[0].as_str(),
+        kind => kind.as_str(),
+    };
+    match kind {
+        Some("object") => {
+            let properties = node["properties"].as_object().cloned().unwrap_or_default();
+            let mut object: Map<_, _> = properties
+                .iter()
+                .map(|(key, property)| (key.clone(), example(schema, property, key)))
+                .collect();
+            // Maps get one entry, so that an empty map is not skipped.
+            if let Some(values) = node.get("additionalProperties").filter(|v| v.is_object()) {
+                object.insert("a".into(), example(schema, values, "a"));
+            }
+            Value::Object(object)
+        }
+        Some("array") => json!([example(schema, &node["items"], name)]),
+        Some("integer") => json!(1),
+        Some("number") => json!(0.5),
+        Some("boolean") => json!(true),
+        Some("string") => match (node["format"].as_str(), name) {
+            (Some("byte"), _) => json!("aGk="),
+            (Some("uri"), _) => json!("file:///a"),
+            (Some("uri-template"), _) => json!("file:///{a}"),
+            (_, "lastModified") => json!("2025-01-01T00:00:00Z"),
+            (_, "mimeType") => json!("text/plain"),
+            (_, "protocolVersion") => json!(LATEST_PROTOCOL_VERSION),
+            _ => json!("a"),
+        },
+        _ => json!("a"),
+    }
+}
+
+/// The schema node at `path`, a definition name optionally followed by
+/// property names, e.g. `InitializeRequest/params`.
+fn node<'a>(schema: &'a Value, path: &str) -> &'a Value {
+    let mut parts = path.split('/');
+    let mut node = &schema["definitions"][parts.next().unwrap()];
+    for part in parts {
+        node = &node["properties"][part];
+    }
+    assert!(node.is_object(), "schema has no {}", path);
+    node
+}
+
+/// Compares `ours`, the crate's round trip of `theirs`, key by key.
+fn compare_values(report: &mut Vec<String>, path: &str, theirs: &Value, ours: &Value) {
+    match (theirs, ours) {
+        (Value::Object(theirs), Value::Object(ours)) => {
+            for (key, value) in theirs {
+                match ours.get(key) {
+                    Some(ours) => compare_values(report, &format!("{}.{}", path, key), value, ours),
+                    None => report.push(format!("{}: {} is missing", path, key)),
+                }
+            }
+        }
+        (Value::Array(theirs), Value::Array(ours)) => {
+            for (theirs, ours) in theirs.iter().zip(ours) {
+                compare_values(report, &format!("{}[]", path), theirs, ours);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Checks that `T` reads every property of the schema node at `path`,
+/// keeping none of them in `extra`, and writes them all back.
+fn check_definition<T>(report: &mut Vec<String>, schema: &Value, path: &str)
+where
+    T: DeserializeOwned + Serialize + Extras,
+{
+    check_round_trip::<T>(report, schema, path, |value| unread("", value.extra()));
+}
+
+/// Checks a type without `extra`, which drops unknown properties.
+fn check_plain_definition<T>(report: &mut Vec<String>, schema: &Value, path: &str)
+where
+    T: DeserializeOwned + Serialize,
+{
+    check_round_trip::<T>(report, schema, path, |_| Vec::new());
+}
+
+/// The properties kept in `extra`, as `(location, key)` pairs relative to
+/// the definition being checked.
+fn unread(location: &str, extra: &HashMap<String, Value>) -> Vec<(String, String)> {
+    extra
+        .keys()
+        .map(|key| (location.to_string(), key.clone()))
+        .collect()
+}
+
+/// The annotations of a content block, whose `extra` holds the block's
+/// unknown properties.
+fn content_annotated(content: &PromptContent) -> &Annotated {
+    match content {
+        PromptContent::Text(text) => &text.annotated,
+        PromptContent::Image(image) => &image.annotated,
+        PromptContent::Audio(audio) => &audio.annotated,
+        PromptContent::ResourceLink(link) => &link.resource.annotated,
+        PromptContent::Resource(embedded) => &embedded.annotated,
+        other => panic!("unexpected content: {:?}", other),
+    }
+}
+
+/// Reads the example for `path` into a `T`, reports the properties `unread`
+/// finds in its `extra` maps, and compares the written-back value with the
+/// example. Properties kept in `extra` are written back unchanged, so only
+/// `unread` can tell them apart from typed fields.
+fn check_round_trip<T>(
+    report: &mut Vec<String>,
+    schema: &Value,
+    path: &str,
+    unread: impl Fn(&T) -> Vec<(String, String)>,
+) where
+    T: DeserializeOwned + Serialize,
+{
+    let theirs = example(schema, node(schema, path), "");
+    let value = match serde_json::from_value::<T>(theirs.clone()) {
+        Ok(value) => value,
+        Err(err) => return report.push(format!("{}: rejects {}: {}", path, theirs, err)),
+    };
+    let unread: BTreeSet<_> = unread(&value).into_iter().collect();
+    for (location, key) in &unread {
+        report.push(format!("{}{}: {} is missing", path, location, key));
+    }
+    let ours = serde_json::to_value(&value).unwrap();
+    compare_values(report, path, &theirs, &ours);
+}
+
+fn definitions_report() -> Vec<String> {
+    let schema: Value = serde_json::from_str(SCHEMA).unwrap();
+    let schema = &schema;
+    let mut report = Vec::new();
+    let report_ref = &mut report;
+    check_definition::<ClientCapabilities>(report_ref, schema, "ClientCapabilities");
+    check_definition::<ServerCapabilities>(report_ref, schema, "ServerCapabilities");
+    check_definition::<Implementation>(report_ref, schema, "Implementation");
+    check_plain_definition::<InitializeParams>(report_ref, schema, "InitializeRequest/params");
+    check_definition::<InitializeResult>(report_ref, schema, "InitializeResult");
+    check_plain_definition::<CancelledNotificationParams>(
+        report_ref,
+        schema,
+        "CancelledNotification/params",
+    );
+    check_definition::<ProgressNotificationParams>(
+        report_ref,
+        schema,
+        "ProgressNotification/params",
+    );
+    check_definition::<PaginatedParams>(report_ref, schema, "PaginatedRequest/params");
+    check_round_trip::<ListResourcesResult>(report_ref, schema, "ListResourcesResult", |value| {
+        let mut keys = unread("", value.extra());
+        for resource in &value.items {
+            keys.extend(unread(".resources[]", resource.annotated.extra()));
+        }
+        keys
+    });
+    check_round_trip::<ListResourceTemplatesResult>(
+        report_ref,
+        schema,
+        "ListResourceTemplatesResult",
+        |value| {
+            let mut keys = unread("", value.extra());
+            for template in &value.items {
+                keys.extend(unread(".resourceTemplates[]", template.annotated.extra()));
+            }
+            keys
+        },
+    );
+    check_definition::<ListPromptsResult>(report_ref, schema, "ListPromptsResult");
+    check_definition::<ListToolsResult>(report_ref, schema, "ListToolsResult");
+    check_definition::<ReadResourceParams>(report_ref, schema, "ReadResourceRequest/params");
+    check_definition::<ReadResourceResult>(report_ref, schema, "ReadResourceResult");
+    check_definition::<SubscribeParams>(report_ref, schema, "SubscribeRequest/params");
+    check_definition::<UnsubscribeParams>(report_ref, schema, "UnsubscribeRequest/params");
+    check_definition::<ResourceUpdatedParams>(
+        report_ref,
+        schema,
+        "ResourceUpdatedNotification/params",
+    );
+    check_round_trip::<Resource>(report_ref, schema, "Resource", |value| {
+        unread("", value.annotated.extra())
+    });
+    check_round_trip::<ResourceTemplate>(report_ref, schema, "ResourceTemplate", |value| {
+        unread("", value.annotated.extra())
+    });
+    for path in [
+        "TextContent",
+        "ImageContent",
+        "AudioContent",
+        "ResourceLink",
+        "EmbeddedResource",
+    ] {
+        check_round_trip::<PromptContent>(report_ref, schema, path, |value| {
+            unread("", content_annotated(value).extra())
+        });
+    }
+    check_definition::<GetPromptParams>(report_ref, schema, "GetPromptRequest/params");
+    check_definition::<GetPromptResult>(report_ref, schema, "GetPromptResult");
+    check_definition::<Prompt>(report_ref, schema, "Prompt");
+    check_definition::<PromptArgument>(report_ref, schema, "PromptArgument");
+    check_definition::<CallToolParams>(report_ref, schema, "CallToolRequest/params");
+    check_definition::<CallToolResult>(report_ref, schema, "CallToolResult");
+    check_definition::<Tool>(report_ref, schema, "Tool");
+    check_definition::<SetLevelParams>(report_ref, schema, "SetLevelRequest/params");
+    check_definition::<LoggingMessageParams>(
+        report_ref,
+        schema,
+        "LoggingMessageNotification/params",
+    );
+    check_definition::<CreateMessageParams>(report_ref, schema, "CreateMessageRequest/params");
+    check_definition::<CreateMessageResult>(report_ref, schema, "CreateMessageResult");
+    check_definition::<CompleteParams>(report_ref, schema, "CompleteRequest/params");
+    check_definition::<CompleteResult>(report_ref, schema, "CompleteResult");
+    check_definition::<ListRootsResult>(report_ref, schema, "ListRootsResult");
+    check_definition::<Root>(report_ref, schema, "Root");
+    check_definition::<ElicitationCreateParams>(report_ref, schema, "ElicitRequest/params");
+    check_definition::<ElicitationCreateResult>(report_ref, schema, "ElicitResult");
+    report
+}
+
+#[test]
+fn test_schema_definitions() {
+    let report = definitions_report();
+    let unexpected: Vec<_> = report
+        .iter()
+        .filter(|gap| !KNOWN_GAPS.contains(&gap.as_str()))
+        .collect();
+    let fixed: Vec<_> = KNOWN_GAPS
+        .iter()
+        .filter(|gap| !report.iter().any(|found| found == *gap))
+        .collect();
+    assert!(
+        unexpected.is_empty() && fixed.is_empty(),
+        "schema definitions changed\nnew gaps:\n  {:#?}\nfixed, remove from KNOWN_GAPS:\n  {:#?}",
+        unexpected,
+        fixed
+    );
+}
@@ -0,0 +1,211 @@
+#![cfg(feature = "axum")]
+
+use axum::body::{to_bytes, Body};
+use axum::extract::FromRequest;
+use axum::http::{Request, StatusCode};
+use axum::response::IntoResponse;
+use mcp_schema::*;
+use serde_json::json;
+
+fn post(accept: &str, body: serde_json::Value) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .header("content-type", "application/json")
+        .header("accept", accept)
+        .header(SESSION_ID_HEADER, "session-1")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_extract_post() {
+    let request = post(
+        "application/json, text/event-stream",
+        json!([
+            { "jsonrpc": "2.0", "id": 1, "method": "tools/list" },
+            { "jsonrpc": "2.0", "method": "notifications/initialized" },
+            { "jsonrpc": "2.0", "id": 9, "result": { "roots": [] } }
+        ]),
+    );
+    let extracted = McpPost::from_request(request, &()).await.unwrap();
+    assert_eq!(extracted.session_id.as_deref(), Some("session-1"));
+    assert!(extracted.batch && extracted.errors.is_empty());
+    assert!(extracted.has_requests());
+    assert!(matches!(
+        extracted.messages.as_slice(),
+        [
+            ClientMessage::Request(ClientRequest::ListTools { .. }),
+            ClientMessage::Notification(ClientNotification::Initialized { .. }),
+            ClientMessage::Response(_),
+        ]
+    ));
+
+    let rejection = McpPost::from_request(post_text("image/png"), &())
+        .await
+        .unwrap_err();
+    assert!(matches!(rejection, McpRejection::NotAcceptable));
+
+    let rejection = McpPost::from_request(post_text("application/json"), &())
+        .await
+        .unwrap_err();
+    assert!(matches!(rejection, McpRejection::NotAcceptable));
+    let mut request = post_text("*/*");
+    request.headers_mut().remove("accept");
+    let rejection = McpPost::from_request(request, &()).await.unwrap_err();
+    assert_eq!(
+        rejection.into_response().status(),
+        StatusCode::NOT_ACCEPTABLE
+    );
+    assert!(McpPost::from_request(post_text("*/*"), &()).await.is_ok());
+
+    let rejection = McpPost::from_request(
+        post(
+            "application/json, text/event-stream",
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "nope" }),
+        ),
+        &(),
+    )
+    .await
+    .unwrap_err();
+    let response = rejection.into_response();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body: serde_json::Value =
+        serde_json::from_slice(&to_bytes(response.into_body(), 1 << 16).await.unwrap()).unwrap();
+    assert_eq!(body["error"]["code"], INVALID_REQUEST);
+}
+
+#[tokio::test]
+async fn test_batches_follow_negotiated_version() {
+    let batch = || {
+        json!([
+            { "jsonrpc": "2.0", "id": 1, "method": "ping" },
+            { "jsonrpc": "2.0", "id": 2, "method": "ping" }
+        ])
+    };
+    let with_version = |version: &str| {
+        let mut request = post("application/json, text/event-stream", batch());
+        request
+            .headers_mut()
+            .insert(PROTOCOL_VERSION_HEADER, version.parse().unwrap());
+        request
+    };
+
+    let extracted =
+        McpPost::from_request(post("application/json, text/event-stream", batch()), &())
+            .await
+            .unwrap();
+    assert_eq!(extracted.protocol_version, ProtocolVersion::V2025_03_26);
+    assert_eq!(extracted.messages.len(), 2);
+
+    let extracted = McpPost::from_request(with_version("2024-11-05"), &())
+        .await
+        .unwrap();
+    assert!(extracted.batch);
+
+    let rejection = McpPost::from_request(with_version("2025-06-18"), &())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        rejection,
+        McpRejection::BatchNotSupported(ProtocolVersion::V2025_06_18)
+    ));
+    let response = rejection.into_response();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body: serde_json::Value =
+        serde_json::from_slice(&to_bytes(response.into_body(), 1 << 16).await.unwrap()).unwrap();
+    assert_eq!(body["error"]["code"], INVALID_REQUEST);
+
+    let rejection = McpPost::from_request(with_version("1999-01-01"), &())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        rejection,
+        McpRejection::UnsupportedProtocolVersion(_)
+    ));
+}
+
+#[tokio::test]
+async fn test_malformed_batch_entries_get_their_own_errors() {
+    let request = post(
+        "application/json, text/event-stream",
+        json!([
+            { "jsonrpc": "2.0", "id": 1, "method": "tools/list" },
+            { "jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": {} },
+            { "jsonrpc": "2.0", "id": 3, "method": "nope" },
+            "garbage"
+        ]),
+    );
+    let extracted = McpPost::from_request(request, &()).await.unwrap();
+    assert!(matches!(
+        extracted.messages.as_slice(),
+        [ClientMessage::Request(ClientRequest::ListTools { .. })]
+    ));
+    let errors: Vec<_> = extracted
+        .errors
+        .iter()
+        .map(|error| (error.id.clone(), error.error.code))
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            (Some(RequestId::Number(2)), INVALID_PARAMS),
+            (Some(RequestId::Number(3)), METHOD_NOT_FOUND),
+            (None, INVALID_REQUEST),
+        ]
+    );
+    assert!(extracted.has_requests());
+}
+
+fn post_text(accept: &str) -> Request<Body> {
+    post(
+        accept,
+        json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+    )
+}
+
+#[tokio::test]
+async fn test_mcp_responses() {
+    let response = McpResponse::accepted().into_response();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+    let response = McpResponse::json(&json!({ "jsonrpc": "2.0", "id": 1, "result": {} }))
+        .unwrap()
+        .session_id("session-2")
+        .into_response();
+    assert_eq!(response.headers()[SESSION_ID_HEADER], "session-2");
+    assert_eq!(response.headers()["content-type"], "application/json");
+
+    let notification = ServerMessage::Notification(ServerNotification::ToolListChanged {
+        json_rpc: JsonRpcVersion,
+        params: Default::default(),
+    });
+    let response = McpResponse::sse(futures_util::stream::iter(vec![notification])).into_response();
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+    let body = to_bytes(response.into_body(), 1 << 16).await.unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert!(body.starts_with("event: message\ndata: "));
+    assert!(body.contains("notifications/tools/list_changed"));
+}
+
+#[tokio::test]
+async fn test_resumable_sse() {
+    let mut buffer = ReplayBuffer::new(16);
+    let message = ServerMessage::Notification(ServerNotification::PromptListChanged {
+        json_rpc: JsonRpcVersion,
+        params: Default::default(),
+    });
+    let id = buffer.push("post-1", message.clone());
+    let response =
+        McpResponse::resumable_sse(futures_util::stream::iter(vec![(id, message)])).into_response();
+    let body = to_bytes(response.into_body(), 1 << 16).await.unwrap();
+    assert!(String::from_utf8(body.to_vec())
+        .unwrap()
+        .contains("\nid: post-1:0\n"));
+
+    let request = Request::builder()
+        .header("last-event-id", "post-1:0")
+        .body(Body::empty())
+        .unwrap();
+    let last = last_event_id(request.headers()).unwrap();
+    assert_eq!(buffer.replay_after(&last).unwrap().count(), 0);
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "strict-fields")]
+
+use mcp_schema::*;
+use serde_json::json;
+
+#[test]
+fn test_strict_fields_reject_unknown_members() {
+    let error = serde_json::from_value::<InitializeResult>(json!({
+        "protocolversion": "2025-06-18",
+        "protocolVersion": "2025-06-18",
+        "capabilities": {},
+        "serverInfo": { "name": "server", "version": "1.0" }
+    }))
+    .unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("unknown field `protocolversion`"));
+
+    let notification = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "notifications/initialized"
+    });
+    assert!(serde_json::from_value::<ClientNotification>(notification).is_err());
+}
+
+#[test]
+fn test_open_objects_keep_unknown_members() {
+    let params: CallToolParams = serde_json::from_value(json!({
+        "name": "search",
+        "_meta": { "com.example/trace": "abc" }
+    }))
+    .unwrap();
+    assert!(params.meta.unwrap().extra.contains_key("com.example/trace"));
+    assert!(serde_json::from_value::<ClientCapabilities>(json!({
+        "sampling": { "com.example/maxTokens": 4096 }
+    }))
+    .is_ok());
+}
+
+#[test]
+fn test_strict_fields_reject_unknown_list_result_members() {
+    let error = serde_json::from_value::<ListToolsResult>(json!({
+        "tools": [],
+        "toolz": 1
+    }))
+    .unwrap_err();
+    assert!(error.to_string().contains("unknown field `toolz`"));
+    assert!(serde_json::from_value::<ListToolsResult>(json!({
+        "tools": [],
+        "nextCursor": "abc"
+    }))
+    .is_ok());
+}
+
+#[test]
+fn test_strict_fields_reject_unknown_schema_and_contents_members() {
+    assert!(serde_json::from_value::<EnumSchema>(json!({
+        "type": "string",
+        "enum": ["a"],
+        "bogus": 1
+    }))
+    .is_err());
+    assert!(serde_json::from_value::<EnumSchema>(json!({
+        "type": "string",
+        "enum": ["a"]
+    }))
+    .is_ok());
+    assert!(serde_json::from_value::<RequestedSchema>(json!({
+        "type": "object",
+        "properties": { "ok": { "type": "boolean", "bogus": 1 } }
+    }))
+    .is_err());
+    assert!(serde_json::from_value::<ResourceContents>(json!({
+        "uri": "file:///a.txt",
+        "text": "hi",
+        "mimetype": "text/plain"
+    }))
+    .is_err());
+}
@@ -0,0 +1,243 @@
+use mcp_schema::*;
+use serde_json::json;
+use std::cmp::Ordering;
+
+#[derive(serde::Serialize)]
+struct Weather {
+    temperature: f64,
+}
+
+#[test]
+fn test_call_tool_result_from_ok() {
+    let result: CallToolResult = Ok::<_, String>(Weather { temperature: 21.5 }).into();
+    assert_eq!(result.is_error, None);
+    assert_eq!(result.structured_content, Some(json!({ "temperature": 21.5 })));
+    match &result.content[0] {
+        PromptContent::Text(text) => assert_eq!(text.text, r#"{"temperature":21.5}"#),
+        other => panic!("unexpected content: {:?}", other),
+    }
+}
+
+#[test]
+fn test_call_tool_result_from_non_object() {
+    let result: CallToolResult = Ok::<_, String>("sunny").into();
+    assert!(result.structured_content.is_none());
+    match &result.content[0] {
+        PromptContent::Text(text) => assert_eq!(text.text, "sunny"),
+        other => panic!("unexpected content: {:?}", other),
+    }
+
+    let result: CallToolResult = Ok::<_, String>(vec![1, 2]).into();
+    assert!(result.structured_content.is_none());
+    match &result.content[0] {
+        PromptContent::Text(text) => assert_eq!(text.text, "[1,2]"),
+        other => panic!("unexpected content: {:?}", other),
+    }
+}
+
+#[test]
+fn test_call_tool_result_from_err() {
+    let result: CallToolResult = Err::<Weather, _>("city not found").into();
+    assert_eq!(result.is_error, Some(true));
+    assert!(result.structured_content.is_none());
+    match &result.content[0] {
+        PromptContent::Text(text) => assert_eq!(text.text, "city not found"),
+        other => panic!("unexpected content: {:?}", other),
+    }
+}
+
+fn tool(name: &str, description: &str) -> Tool {
+    serde_json::from_value(json!({
+        "name": name,
+        "description": description,
+        "inputSchema": { "type": "object" }
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_tool_registry_mutations() {
+    let mut registry = ToolRegistry::new();
+    registry.add(tool("search", "Search")).unwrap();
+    assert_eq!(
+        registry.add(tool("search", "Again")).unwrap_err(),
+        ToolRegistryError::Duplicate("search".into())
+    );
+    assert_eq!(
+        registry.replace(tool("fetch", "Fetch")).unwrap_err(),
+        ToolRegistryError::NotFound("fetch".into())
+    );
+    let previous = registry.replace(tool("search", "Search v2")).unwrap();
+    assert_eq!(previous.description.as_deref(), Some("Search"));
+    assert!(registry.remove("missing").is_err());
+}
+
+#[test]
+fn test_tool_registry_change_tracking() {
+    let mut registry = ToolRegistry::new().with_page_size(1);
+    registry.add(tool("a", "A")).unwrap();
+    registry.add(tool("b", "B")).unwrap();
+    assert_eq!(registry.take_changes().added, vec!["a", "b"]);
+    assert!(registry.take_changes().is_empty());
+
+    registry.replace(tool("a", "A2")).unwrap();
+    registry.remove("b").unwrap();
+    registry.add(tool("b", "B")).unwrap();
+    let changes = registry.take_changes();
+    assert_eq!(changes.changed, vec!["a"]);
+    assert!(changes.added.is_empty() && changes.removed.is_empty());
+
+    let page = registry.list(None);
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.next_cursor.as_deref(), Some("a"));
+    let page = registry.list(page.next_cursor.as_deref());
+    assert_eq!(page.items[0].name, "b");
+    assert!(page.next_cursor.is_none());
+}
+
+#[test]
+fn test_tool_output_schema_builder() {
+    let tool: Tool = serde_json::from_value(json!({
+        "name": "weather",
+        "inputSchema": { "type": "object" }
+    }))
+    .unwrap();
+    let schema = ToolOutputSchema::object()
+        .property("temperature", json!({ "type": "number" }))
+        .required("temperature");
+    let tool = tool.with_output_schema(schema.clone());
+    assert_eq!(
+        tool.output_schema,
+        Some(json!({
+            "type": "object",
+            "properties": { "temperature": { "type": "number" } },
+            "required": ["temperature"]
+        }))
+    );
+    assert_eq!(tool.typed_output_schema(), Some(schema));
+}
+
+#[test]
+fn test_tool_version_meta() {
+    let unversioned = tool("search", "Search");
+    assert_eq!(unversioned.meta_version(), None);
+    assert_eq!(unversioned.compare_version("1.0"), None);
+
+    let versioned = unversioned.with_version("2.10");
+    assert_eq!(versioned.meta_version(), Some("2.10"));
+    assert_eq!(
+        serde_json::to_value(&versioned).unwrap()["_meta"][meta_keys::TOOL_VERSION],
+        "2.10"
+    );
+    assert!(meta_keys::validate_custom_key(meta_keys::TOOL_VERSION).is_ok());
+    assert_eq!(versioned.compare_version("2.9"), Some(Ordering::Greater));
+    assert_eq!(versioned.compare_version("2.10.0"), Some(Ordering::Equal));
+    assert_eq!(compare_versions("1.0-beta", "1.0-alpha"), Ordering::Greater);
+}
+
+#[test]
+fn test_compare_versions_mixed_segments() {
+    assert_eq!(compare_versions("2.10-rc1", "2.9"), Ordering::Greater);
+    assert_eq!(compare_versions("2.10-rc1", "2.10"), Ordering::Less);
+    assert_eq!(compare_versions("1.0-beta", "1.0"), Ordering::Less);
+    assert_eq!(compare_versions("1.0", "1.0-beta"), Ordering::Greater);
+    assert_eq!(compare_versions("1.01", "1.1"), Ordering::Equal);
+}
+
+#[test]
+fn test_deprecation_meta() {
+    let tool: Tool = serde_json::from_value(json!({
+        "name": "search_v1",
+        "inputSchema": { "type": "object" }
+    }))
+    .unwrap();
+    assert!(tool.deprecation().is_none());
+
+    let notice = Deprecation::new()
+        .since("2.0")
+        .message("slow")
+        .replacement("search");
+    let tool = tool.with_deprecation(notice.clone());
+    assert_eq!(tool.deprecation(), Some(notice.clone()));
+    assert_eq!(
+        serde_json::to_value(&tool).unwrap()["_meta"][meta_keys::DEPRECATION],
+        json!({ "since": "2.0", "message": "slow", "replacement": "search" })
+    );
+    assert_eq!(
+        notice.to_string(),
+        "deprecated since 2.0: slow (use search instead)"
+    );
+
+    let prompt: Prompt = serde_json::from_value(json!({
+        "name": "summary",
+        "_meta": { "io.github.yonaka15/deprecation": {} }
+    }))
+    .unwrap();
+    assert_eq!(prompt.deprecation(), Some(Deprecation::new()));
+}
+
+#[test]
+fn test_required_scopes_meta() {
+    let tool: Tool = serde_json::from_value(json!({
+        "name": "delete_file",
+        "inputSchema": { "type": "object" }
+    }))
+    .unwrap();
+    assert!(tool.required_scopes().is_none());
+
+    let tool = tool.with_required_scopes(RequiredScopes::from_scope_str("files:read files:write"));
+    assert_eq!(
+        serde_json::to_value(&tool).unwrap()["_meta"][meta_keys::REQUIRED_SCOPES],
+        json!(["files:read", "files:write"])
+    );
+    let scopes = tool.required_scopes().unwrap();
+    assert!(scopes.is_satisfied_by(&["files:write", "files:read", "admin"]));
+    assert!(!scopes.is_satisfied_by(&["files:read"]));
+    assert_eq!(scopes.missing(&["files:read"]), ["files:write"]);
+    assert!(RequiredScopes::new().is_satisfied_by::<&str>(&[]));
+    assert_eq!(
+        scopes.challenge().to_string(),
+        r#"Bearer error="insufficient_scope", scope="files:read files:write""#
+    );
+}
+
+#[test]
+fn test_display_names() {
+    let mut search = tool("search", "Search");
+    assert_eq!(search.display_name(), "search");
+    search.annotations = Some(ToolAnnotations {
+        title: Some("Web search".into()),
+        read_only_hint: None,
+        destructive_hint: None,
+        idempotent_hint: None,
+        open_world_hint: None,
+    });
+    assert_eq!(search.display_name(), "Web search");
+    search.title = Some("Search the web".into());
+    assert_eq!(search.display_name(), "Search the web");
+    assert_eq!(BaseMetadata::name(&search), "search");
+
+    let server: Implementation = serde_json::from_value(json!({
+        "name": "weather",
+        "title": "Weather Server",
+        "version": "1.0"
+    }))
+    .unwrap();
+    assert_eq!(server.display_name(), "Weather Server");
+    assert!(server.extra.is_empty());
+
+    let resource: Resource =
+        serde_json::from_value(json!({ "uri": "file:///a.txt", "name": "a.txt" })).unwrap();
+    assert_eq!(resource.display_name(), "a.txt");
+
+    let template: ResourceTemplate = serde_json::from_value(json!({
+        "uriTemplate": "file:///{path}",
+        "name": "files",
+        "title": "Project files"
+    }))
+    .unwrap();
+    assert_eq!(template.display_name(), "Project files");
+
+    let argument: PromptArgument = serde_json::from_value(json!({ "name": "code" })).unwrap();
+    assert_eq!(argument.display_name(), "code");
+}
@@ -0,0 +1,296 @@
+use mcp_schema::*;
+use serde_json::json;
+
+fn rules(violations: &[Violation]) -> Vec<(&str, &str)> {
+    violations
+        .iter()
+        .map(|v| (v.rule.id(), v.pointer.as_str()))
+        .collect()
+}
+
+#[test]
+fn test_validate_message_reports_violations() {
+    let valid = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": { "name": "search" }
+    });
+    assert!(validate_message(
+        &valid,
+        MessageDirection::ClientToServer,
+        ProtocolVersion::V2024_11_05
+    )
+    .is_empty());
+
+    let violations = validate_message(
+        &json!({ "jsonrpc": "1.0", "id": null, "method": "tools/call", "params": {} }),
+        MessageDirection::ClientToServer,
+        ProtocolVersion::V2024_11_05,
+    );
+    assert_eq!(
+        rules(&violations),
+        vec![
+            ("jsonrpc-version", "/jsonrpc"),
+            ("missing-field", "/id"),
+            ("missing-field", "/params/name"),
+        ]
+    );
+
+    let violations = validate_message(
+        &json!({ "jsonrpc": "2.0", "id": 2, "method": "sampling/createMessage", "params": {} }),
+        MessageDirection::ClientToServer,
+        ProtocolVersion::V2024_11_05,
+    );
+    assert_eq!(rules(&violations), vec![("unknown-method", "/method")]);
+    assert!(violations[0].message.contains("direction"));
+
+    let violations = validate_message(
+        &json!({ "jsonrpc": "2.0", "method": "notifications/custom" }),
+        MessageDirection::ServerToClient,
+        ProtocolVersion::V2024_11_05,
+    );
+    assert_eq!(violations[0].severity, Severity::Warning);
+
+    let elicitation = json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "method": "elicitation/create",
+        "params": { "message": "Name?", "requestedSchema": {} }
+    });
+    assert_eq!(
+        rules(&validate_message(
+            &elicitation,
+            MessageDirection::ServerToClient,
+            ProtocolVersion::V2024_11_05
+        )),
+        vec![("unknown-method", "/method")]
+    );
+    assert!(validate_message(
+        &elicitation,
+        MessageDirection::ServerToClient,
+        ProtocolVersion::V2025_06_18
+    )
+    .is_empty());
+    assert_eq!(
+        rules(&validate_message(
+            &elicitation,
+            MessageDirection::ClientToServer,
+            ProtocolVersion::V2025_06_18
+        )),
+        vec![("unknown-method", "/method")]
+    );
+}
+
+#[test]
+fn test_validate_responses_and_batches() {
+    let batch = json!([
+        { "jsonrpc": "2.0", "id": 1, "result": {} },
+        { "jsonrpc": "2.0", "id": 2, "error": { "code": "oops" } },
+        "garbage"
+    ]);
+    let violations = validate_message(
+        &batch,
+        MessageDirection::ServerToClient,
+        ProtocolVersion::V2025_03_26,
+    );
+    assert_eq!(
+        rules(&violations),
+        vec![
+            ("missing-field", "/1/error/code"),
+            ("missing-field", "/1/error/message"),
+            ("invalid-message", "/2"),
+        ]
+    );
+
+    // Batching was removed again in 2025-06-18.
+    let violations = validate_message(
+        &batch,
+        MessageDirection::ServerToClient,
+        ProtocolVersion::V2025_06_18,
+    );
+    assert_eq!(rules(&violations), vec![("invalid-message", "")]);
+    assert_eq!(
+        violations[0].message,
+        "batches are not supported in 2025-06-18"
+    );
+
+    // ...and did not exist yet in 2024-11-05.
+    let violations = validate_message(
+        &batch,
+        MessageDirection::ServerToClient,
+        ProtocolVersion::V2024_11_05,
+    );
+    assert_eq!(rules(&violations), vec![("invalid-message", "")]);
+    assert_eq!(
+        violations[0].message,
+        "batches are not supported in 2024-11-05"
+    );
+}
+
+#[test]
+fn test_validate_error_with_null_id() {
+    let error = json!({
+        "jsonrpc": "2.0",
+        "id": null,
+        "error": { "code": -32700, "message": "parse error" }
+    });
+    let violations = validate_message(
+        &error,
+        MessageDirection::ServerToClient,
+        ProtocolVersion::V2025_06_18,
+    );
+    assert!(violations.is_empty(), "{:?}", violations);
+
+    let result = json!({ "jsonrpc": "2.0", "id": null, "result": {} });
+    let violations = validate_message(
+        &result,
+        MessageDirection::ServerToClient,
+        ProtocolVersion::V2025_06_18,
+    );
+    assert_eq!(rules(&violations), vec![("missing-field", "/id")]);
+}
+
+#[test]
+fn test_validator_capability_mismatch() {
+    let server: ServerCapabilities = serde_json::from_value(json!({ "tools": {} })).unwrap();
+    let client: ClientCapabilities = serde_json::from_value(json!({})).unwrap();
+    let validator = Validator::new(ProtocolVersion::V2025_06_18)
+        .server_capabilities(server)
+        .client_capabilities(client);
+
+    let list_prompts = json!({ "jsonrpc": "2.0", "id": 1, "method": "prompts/list" });
+    let violations = validator.validate(&list_prompts, MessageDirection::ClientToServer);
+    assert_eq!(rules(&violations), vec![("capability-mismatch", "/method")]);
+
+    let list_tools = json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" });
+    assert!(validator
+        .validate(&list_tools, MessageDirection::ClientToServer)
+        .is_empty());
+
+    let sampling = json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "method": "sampling/createMessage",
+        "params": { "messages": [], "maxTokens": 10 }
+    });
+    let violations = validator.validate(&sampling, MessageDirection::ServerToClient);
+    assert_eq!(rules(&violations), vec![("capability-mismatch", "/method")]);
+
+    let elicitation = json!({
+        "jsonrpc": "2.0",
+        "id": 4,
+        "method": "elicitation/create",
+        "params": { "message": "Name?", "requestedSchema": { "type": "object", "properties": {} } }
+    });
+    let violations = validator.validate(&elicitation, MessageDirection::ServerToClient);
+    assert_eq!(rules(&violations), vec![("capability-mismatch", "/method")]);
+
+    let client: ClientCapabilities = serde_json::from_value(json!({ "elicitation": {} })).unwrap();
+    let validator = Validator::new(ProtocolVersion::V2025_06_18).client_capabilities(client);
+    assert!(validator
+        .validate(&elicitation, MessageDirection::ServerToClient)
+        .is_empty());
+}
+
+#[test]
+fn test_validator_completions_capability_by_version() {
+    let server: ServerCapabilities = serde_json::from_value(json!({ "prompts": {} })).unwrap();
+    let complete = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "completion/complete",
+        "params": {
+            "ref": { "type": "ref/prompt", "name": "greeting" },
+            "argument": { "name": "style", "value": "f" }
+        }
+    });
+
+    // 2024-11-05 has no `completions` capability to advertise.
+    let validator =
+        Validator::new(ProtocolVersion::V2024_11_05).server_capabilities(server.clone());
+    assert!(validator
+        .validate(&complete, MessageDirection::ClientToServer)
+        .is_empty());
+
+    let validator = Validator::new(ProtocolVersion::V2025_03_26).server_capabilities(server);
+    let violations = validator.validate(&complete, MessageDirection::ClientToServer);
+    assert_eq!(rules(&violations), vec![("capability-mismatch", "/method")]);
+}
+
+#[test]
+fn test_protocol_version_strings() {
+    let version: ProtocolVersion = "2025-03-26".parse().unwrap();
+    assert_eq!(version, ProtocolVersion::V2025_03_26);
+    assert!(ProtocolVersion::V2024_11_05 < version);
+    assert_eq!(serde_json::to_value(version).unwrap(), json!("2025-03-26"));
+    assert!("2023-01-01".parse::<ProtocolVersion>().is_err());
+    #[cfg(not(feature = "v2025_03_26"))]
+    assert_eq!(
+        ProtocolVersion::V2024_11_05.as_str(),
+        LATEST_PROTOCOL_VERSION
+    );
+    #[cfg(all(feature = "v2025_03_26", not(feature = "v2025_06_18")))]
+    assert_eq!(
+        ProtocolVersion::V2025_03_26.as_str(),
+        LATEST_PROTOCOL_VERSION
+    );
+    #[cfg(feature = "v2025_06_18")]
+    assert_eq!(
+        ProtocolVersion::V2025_06_18.as_str(),
+        LATEST_PROTOCOL_VERSION
+    );
+}
+
+#[test]
+fn test_sniff_protocol_version() {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 0,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-06-18",
+            "capabilities": {},
+            "clientInfo": { "name": "c", "version": "1" }
+        }
+    });
+    let response = json!({
+        "jsonrpc": "2.0",
+        "id": 0,
+        "result": {
+            "protocolVersion": "2025-03-26",
+            "capabilities": {},
+            "serverInfo": { "name": "s", "version": "1" }
+        }
+    });
+    assert_eq!(
+        sniff_protocol_version([&request, &response].into_iter()),
+        Some(ProtocolVersion::V2025_03_26)
+    );
+    assert_eq!(
+        sniff_protocol_version([&request].into_iter()),
+        Some(ProtocolVersion::V2025_06_18)
+    );
+
+    let audio = json!({
+        "jsonrpc": "2.0",
+        "id": 4,
+        "result": { "content": [{ "type": "audio", "data": "", "mimeType": "audio/wav" }] }
+    });
+    let structured = json!({
+        "jsonrpc": "2.0",
+        "id": 5,
+        "result": { "content": [], "structuredContent": { "ok": true } }
+    });
+    assert_eq!(
+        sniff_protocol_version([&audio].into_iter()),
+        Some(ProtocolVersion::V2025_03_26)
+    );
+    assert_eq!(
+        sniff_protocol_version([&audio, &structured].into_iter()),
+        Some(ProtocolVersion::V2025_06_18)
+    );
+
+    let ping = json!({ "jsonrpc": "2.0", "id": 6, "method": "ping" });
+    assert_eq!(sniff_protocol_version([&ping].into_iter()), None);
+}
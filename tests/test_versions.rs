@@ -0,0 +1,274 @@
+use mcp_schema::*;
+use serde_json::json;
+
+#[test]
+fn test_revision_content() {
+    let audio = json!({"type": "audio", "data": "AAAA", "mimeType": "audio/wav"});
+    assert!(serde_json::from_value::<v2024_11_05::PromptContent>(audio.clone()).is_err());
+    assert!(matches!(
+        serde_json::from_value(audio.clone()).unwrap(),
+        v2025_03_26::PromptContent::Audio(_)
+    ));
+    assert!(matches!(
+        serde_json::from_value(audio.clone()).unwrap(),
+        v2025_06_18::SamplingContent::Audio(_)
+    ));
+
+    let result = json!({"content": [audio], "isError": false});
+    assert!(serde_json::from_value::<v2024_11_05::CallToolResult>(result.clone()).is_err());
+    let parsed: v2025_03_26::CallToolResult = serde_json::from_value(result.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&parsed).unwrap(), result);
+
+    let link = json!({
+        "content": [{ "type": "resource_link", "uri": "file:///big.csv", "name": "big.csv" }]
+    });
+    assert!(serde_json::from_value::<v2025_03_26::CallToolResult>(link.clone()).is_err());
+    let parsed: v2025_06_18::CallToolResult = serde_json::from_value(link.clone()).unwrap();
+    assert!(matches!(
+        &parsed.content[0],
+        v2025_06_18::PromptContent::ResourceLink(link) if link.resource.size.is_none()
+    ));
+    assert_eq!(serde_json::to_value(&parsed).unwrap(), link);
+
+    // Everything else is shared with the crate root.
+    let _: RequestId = v2024_11_05::RequestId::Number(1);
+    assert_eq!(v2025_06_18::VERSION.as_str(), "2025-06-18");
+}
+
+#[test]
+fn test_revision_server_requests() {
+    let elicit = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "elicitation/create",
+        "params": {
+            "message": "Name?",
+            "requestedSchema": {"type": "object", "properties": {}}
+        }
+    });
+    assert!(serde_json::from_value::<v2024_11_05::ServerRequest>(elicit.clone()).is_err());
+    assert!(serde_json::from_value::<v2025_03_26::ServerRequest>(elicit.clone()).is_err());
+    assert!(matches!(
+        serde_json::from_value(elicit).unwrap(),
+        v2025_06_18::ServerRequest::ElicitationCreate { .. }
+    ));
+
+    let ping = json!({"jsonrpc": "2.0", "id": 2, "method": "ping"});
+    assert!(serde_json::from_value::<v2024_11_05::ServerRequest>(ping).is_ok());
+}
+
+#[test]
+fn test_revision_aliases() {
+    let _: v2025_06_18::ServerRequest = ServerRequest::Ping {
+        json_rpc: JsonRpcVersion,
+        id: RequestId::Number(1),
+        params: PingParams {},
+    };
+    let _: ProtocolVersion = v2024_11_05::VERSION;
+}
+
+#[test]
+fn test_revision_content_converts_to_root() {
+    let audio = v2025_03_26::PromptContent::Audio(v2025_03_26::AudioContent {
+        data: "UklGRg==".into(),
+        mime_type: "audio/wav".into(),
+        annotated: v2025_03_26::Annotated::default(),
+    });
+    assert!(matches!(
+        PromptContent::from(audio),
+        PromptContent::Audio(_)
+    ));
+    let text = v2024_11_05::SamplingContent::Text(TextContent::new("hi").into());
+    assert!(matches!(
+        SamplingContent::from(text),
+        SamplingContent::Text(_)
+    ));
+}
+
+#[test]
+fn test_revision_structs_drop_later_members() {
+    let tool: Tool = serde_json::from_value(json!({
+        "name": "search",
+        "title": "Search",
+        "inputSchema": {"type": "object"},
+        "annotations": {"readOnlyHint": true}
+    }))
+    .unwrap();
+    let tool = v2024_11_05::Tool::from(tool);
+    assert_eq!(
+        serde_json::to_value(&tool).unwrap(),
+        json!({"name": "search", "inputSchema": {"type": "object"}})
+    );
+    let tool = Tool::from(tool);
+    assert!(tool.title.is_none() && tool.annotations.is_none());
+}
+
+#[test]
+fn test_revision_2025_03_26_structs_drop_later_members() {
+    let tool: Tool = serde_json::from_value(json!({
+        "_meta": {"com.example/v": 1},
+        "name": "search",
+        "title": "Search",
+        "inputSchema": {"type": "object"},
+        "outputSchema": {"type": "object"},
+        "annotations": {"readOnlyHint": true}
+    }))
+    .unwrap();
+    let tool = v2025_03_26::Tool::from(tool);
+    assert_eq!(
+        serde_json::to_value(&tool).unwrap(),
+        json!({
+            "name": "search",
+            "inputSchema": {"type": "object"},
+            "annotations": {"readOnlyHint": true}
+        })
+    );
+    let tool = Tool::from(tool);
+    assert!(tool.title.is_none() && tool.output_schema.is_none() && tool.meta.is_none());
+    assert_eq!(tool.annotations.unwrap().read_only_hint, Some(true));
+
+    let mut text = TextContent::new("hi");
+    text.annotated.annotations = Some(
+        serde_json::from_value(json!({"lastModified": "2025-01-12T15:00:58Z"})).unwrap(),
+    );
+    let text = v2025_03_26::TextContent::from(text);
+    assert_eq!(
+        serde_json::to_value(&text).unwrap(),
+        json!({"text": "hi", "annotations": {}})
+    );
+
+    let capabilities: ClientCapabilities =
+        serde_json::from_value(json!({"elicitation": {}, "roots": {}})).unwrap();
+    let capabilities = v2025_03_26::ClientCapabilities::from(capabilities);
+    assert_eq!(
+        serde_json::to_value(&capabilities).unwrap(),
+        json!({"roots": {}})
+    );
+
+    let elicit = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "elicitation/create",
+        "params": {"message": "?", "requestedSchema": {"type": "object", "properties": {}}}
+    });
+    assert!(serde_json::from_value::<v2025_03_26::ClientRequest>(elicit).is_err());
+
+    let prompt: Prompt = serde_json::from_value(json!({
+        "name": "review",
+        "arguments": [{"name": "code", "title": "Code", "required": true}]
+    }))
+    .unwrap();
+    let prompt = v2025_03_26::Prompt::from(prompt);
+    assert_eq!(
+        serde_json::to_value(&prompt).unwrap(),
+        json!({"name": "review", "arguments": [{"name": "code", "required": true}]})
+    );
+
+    let roots: ListRootsResult = serde_json::from_value(json!({
+        "roots": [{"_meta": {"a": 1}, "uri": "file:///work"}]
+    }))
+    .unwrap();
+    let roots = v2025_03_26::ListRootsResult::from(roots);
+    assert_eq!(
+        serde_json::to_value(&roots).unwrap(),
+        json!({"roots": [{"uri": "file:///work"}]})
+    );
+
+    let reference = ReferenceType::Prompt {
+        name: "review".into(),
+        title: Some("Review".into()),
+    };
+    let reference = v2025_03_26::ReferenceType::from(reference);
+    assert_eq!(
+        serde_json::to_value(&reference).unwrap(),
+        json!({"type": "ref/prompt", "name": "review"})
+    );
+}
+
+#[test]
+fn test_revision_initialize_request() {
+    let init = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2024-11-05",
+            "capabilities": {"roots": {"listChanged": true}},
+            "clientInfo": {"name": "client", "version": "1.0"}
+        }
+    });
+    let request: v2024_11_05::ClientRequest = serde_json::from_value(init.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&request).unwrap(), init);
+    let v2024_11_05::ClientRequest::Initialize { params, .. } = request else {
+        panic!("expected initialize");
+    };
+    let params = InitializeParams::from(params);
+    assert!(params.capabilities.elicitation.is_none());
+    assert!(params.client_info.title.is_none());
+}
+
+#[test]
+fn test_revision_results_omit_elicitation() {
+    let accept = json!({ "action": "accept", "content": { "name": "x" } });
+    assert!(matches!(
+        serde_json::from_value::<v2025_06_18::ClientResult>(accept.clone()).unwrap(),
+        ClientResult::ElicitationCreate(_)
+    ));
+    // Without an elicitation variant the payload is at best an empty result
+    // with extra members, which `strict-fields` rejects outright.
+    assert!(
+        serde_json::from_value::<v2025_03_26::ClientResult>(accept).map_or(
+            true,
+            |result| matches!(result, v2025_03_26::ClientResult::Empty(_))
+        )
+    );
+}
+
+#[test]
+fn test_revision_requests_and_contents_omit_other_revisions() {
+    let elicit = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "elicitation/create",
+        "params": {"message": "?", "requestedSchema": {"type": "object", "properties": {}}}
+    });
+    // No revision lets clients send elicitation/create.
+    assert!(serde_json::from_value::<v2025_06_18::ClientRequest>(elicit).is_err());
+    let ping = json!({"jsonrpc": "2.0", "id": 2, "method": "ping"});
+    assert!(matches!(
+        serde_json::from_value(ping).unwrap(),
+        v2025_06_18::ClientRequest::Ping { .. }
+    ));
+
+    // `_meta` on resource contents arrived in 2025-06-18.
+    let contents = json!({
+        "_meta": {"com.example/etag": "1"},
+        "uri": "file:///a.txt",
+        "text": "hi"
+    });
+    let root: ResourceContents = serde_json::from_value(contents.clone()).unwrap();
+    let older = v2025_03_26::ResourceContents::from(root);
+    assert_eq!(
+        serde_json::to_value(&older).unwrap(),
+        json!({"uri": "file:///a.txt", "text": "hi"})
+    );
+    let root = ResourceContents::from(older);
+    assert!(matches!(root, ResourceContents::Text(text) if text.meta.is_none()));
+    let read: v2024_11_05::ReadResourceResult =
+        serde_json::from_value(json!({"contents": [{"uri": "file:///b", "blob": "AAAA"}]}))
+            .unwrap();
+    assert!(matches!(
+        &read.contents[0],
+        v2024_11_05::ResourceContents::Blob(blob) if blob.blob == "AAAA"
+    ));
+    #[cfg(feature = "strict-fields")]
+    assert!(serde_json::from_value::<v2025_03_26::ResourceContents>(contents).is_err());
+    #[cfg(not(feature = "strict-fields"))]
+    assert_eq!(
+        serde_json::to_value(
+            serde_json::from_value::<v2024_11_05::ResourceContents>(contents).unwrap()
+        )
+        .unwrap(),
+        json!({"uri": "file:///a.txt", "text": "hi"})
+    );
+}